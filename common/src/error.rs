@@ -1,9 +1,16 @@
-use std::any::{Any, TypeId};
-use std::borrow::Cow;
-use std::collections::HashMap;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+use core::any::Any;
+#[cfg(feature = "alloc")]
+use core::any::TypeId;
+use core::fmt::{Debug, Display};
+use core::num::{ParseIntError, TryFromIntError};
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt::{Debug, Display};
-use std::num::{ParseIntError, TryFromIntError};
 
 pub const DEFAULT_CATEGORY: Category = Category("default");
 pub const DEFAULT_CODE: ErrCode = ErrCode(0);
@@ -14,21 +21,63 @@ pub struct Category(&'static str);
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ErrCode(usize);
 
+/// The message of an error. Without the `alloc` feature messages can only be static strings
+/// (see `lq_msg`).
+#[cfg(feature = "alloc")]
+pub type ErrMsg = Cow<'static, str>;
+
+/// The message of an error. Without the `alloc` feature messages can only be static strings
+/// (see `lq_msg`).
+#[cfg(not(feature = "alloc"))]
+pub type ErrMsg = &'static str;
+
+/// Creates an error message (`ErrMsg`) like `format!`.
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! lq_msg {
+    ($($arg:tt)*) => {
+        $crate::error::format_msg(format_args!($($arg)*))
+    };
+}
+
+/// Creates an error message (`ErrMsg`) like `format!`. Without the `alloc` feature messages
+/// cannot be formatted: The format string is used as message (arguments are ignored).
+#[cfg(not(feature = "alloc"))]
+#[macro_export]
+macro_rules! lq_msg {
+    ($msg:expr $(, $arg:expr)* $(,)?) => {{
+        $(let _ = &$arg;)*
+        $msg
+    }};
+}
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub fn format_msg(arguments: core::fmt::Arguments) -> ErrMsg {
+    Cow::Owned(alloc::fmt::format(arguments))
+}
+
 /// A liquesco error. Has a message, a category, an error code and optionally some data.
 #[derive(Debug)]
 pub struct LqError {
-    msg: Cow<'static, str>,
+    msg: ErrMsg,
     category: Category,
     code: ErrCode,
-    data: Option<HashMap<TypeId, Box<dyn ErrData>>>,
+    #[cfg(feature = "alloc")]
+    data: Option<BTreeMap<TypeId, Box<dyn ErrData>>>,
 }
 
-pub trait ErrData: Any + Send + Sync + Debug {}
+/// Additional data of an error (see `LqError::with_data`).
+pub trait ErrData: Any + Send + Sync + Debug {
+    /// Returns `self` (needed to downcast the data; implement as `{ self }`).
+    fn as_any(&self) -> &dyn Any;
+}
 
+#[cfg(feature = "std")]
 impl Error for LqError {}
 
 impl Display for LqError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "LqError({:?})", self)
     }
 }
@@ -41,22 +90,23 @@ impl Category {
 
 impl LqError {
     /// Creates a new error with a message.
-    pub fn new<T: Into<Cow<'static, str>>>(msg: T) -> Self {
+    pub fn new<T: Into<ErrMsg>>(msg: T) -> Self {
         LqError {
             msg: msg.into(),
             category: DEFAULT_CATEGORY,
             code: DEFAULT_CODE,
+            #[cfg(feature = "alloc")]
             data: None,
         }
     }
 
     /// Creates a new `Result::Err` with a message.
-    pub fn err_new<Ok, T: Into<Cow<'static, str>>>(msg: T) -> Result<Ok, Self> {
+    pub fn err_new<Ok, T: Into<ErrMsg>>(msg: T) -> Result<Ok, Self> {
         Result::Err(Self::new(msg))
     }
 
     /// With a different message.
-    pub fn with_msg<T: Into<Cow<'static, str>>>(mut self, msg: T) -> LqError {
+    pub fn with_msg<T: Into<ErrMsg>>(mut self, msg: T) -> LqError {
         self.msg = msg.into();
         self
     }
@@ -76,11 +126,27 @@ impl LqError {
     pub fn category(&self) -> &Category {
         &self.category
     }
+
+    /// With additional data (replaces existing data of the same type).
+    #[cfg(feature = "alloc")]
+    pub fn with_data<T: ErrData>(mut self, data: T) -> Self {
+        self.data
+            .get_or_insert_with(BTreeMap::new)
+            .insert(TypeId::of::<T>(), Box::new(data));
+        self
+    }
+
+    /// Returns the data of given type (if this error has such data).
+    #[cfg(feature = "alloc")]
+    pub fn data<T: ErrData>(&self) -> Option<&T> {
+        let data = self.data.as_ref()?.get(&TypeId::of::<T>())?;
+        data.as_any().downcast_ref::<T>()
+    }
 }
 
 impl From<TryFromIntError> for LqError {
     fn from(value: TryFromIntError) -> Self {
-        LqError::new(format!(
+        LqError::new(lq_msg!(
             "The given integers could not be converted (casted); this \
              can either happen on platforms with small usize (in general this library only works \
              with things as big as this platform supports) - or there's a serialization \
@@ -90,6 +156,7 @@ impl From<TryFromIntError> for LqError {
     }
 }
 
+#[cfg(feature = "std")]
 impl From<std::io::Error> for LqError {
     fn from(value: std::io::Error) -> Self {
         LqError::new(format!("Got an I/O error: {:?}", value))
@@ -98,7 +165,7 @@ impl From<std::io::Error> for LqError {
 
 impl From<ParseIntError> for LqError {
     fn from(value: ParseIntError) -> Self {
-        LqError::new(format!(
+        LqError::new(lq_msg!(
             "Unable to parse given integer (converting from string to integer); error: {:?}",
             value
         ))
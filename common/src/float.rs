@@ -1,19 +1,19 @@
+use core::cmp::Ordering;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::hash::Hash;
+use core::hash::Hasher;
+use core::ops::Deref;
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
-use std::fmt::Display;
-use std::fmt::Formatter;
-use std::hash::Hash;
-use std::hash::Hasher;
-use std::ops::Deref;
 
 /// Extends float to add missing implementation for hash, ord and eq (see
 /// implementation fot rules).
-#[derive(Clone, Copy, Debug, PartialOrd, Serialize, Deserialize, From, Into)]
+#[derive(Clone, Copy, Debug, PartialOrd, Serialize, Deserialize)]
 pub struct F32Ext(f32);
 
 /// Extends float to add missing implementation for hash, ord and eq (see
 /// implementation fot rules).
-#[derive(Clone, Copy, Debug, PartialOrd, Serialize, Deserialize, From, Into)]
+#[derive(Clone, Copy, Debug, PartialOrd, Serialize, Deserialize)]
 pub struct F64Ext(f64);
 
 /// Unfortunately we MUST have ord for the floats (need something to make sure there is
@@ -64,6 +64,30 @@ impl Ord for F64Ext {
     }
 }
 
+impl From<f32> for F32Ext {
+    fn from(value: f32) -> Self {
+        F32Ext(value)
+    }
+}
+
+impl From<F32Ext> for f32 {
+    fn from(value: F32Ext) -> Self {
+        value.0
+    }
+}
+
+impl From<f64> for F64Ext {
+    fn from(value: f64) -> Self {
+        F64Ext(value)
+    }
+}
+
+impl From<F64Ext> for f64 {
+    fn from(value: F64Ext) -> Self {
+        value.0
+    }
+}
+
 impl Eq for F32Ext {}
 
 impl Eq for F64Ext {}
@@ -109,13 +133,13 @@ impl Deref for F64Ext {
 }
 
 impl Display for F32Ext {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
 
 impl Display for F64Ext {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
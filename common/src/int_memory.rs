@@ -1,6 +1,6 @@
 // Integer memory representation.
 
-use std::cmp::max;
+use core::cmp::max;
 
 /// Integer memory representation; number of bits required to store the integer.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
@@ -35,13 +35,13 @@ impl IntMemory {
 
 impl From<&u128> for IntMemory {
     fn from(value: &u128) -> Self {
-        if value <= &(std::u8::MAX as u128) {
+        if value <= &(u8::MAX as u128) {
             IntMemory::M8
-        } else if value <= &(std::u16::MAX as u128) {
+        } else if value <= &(u16::MAX as u128) {
             IntMemory::M16
-        } else if value <= &(std::u32::MAX as u128) {
+        } else if value <= &(u32::MAX as u128) {
             IntMemory::M32
-        } else if value <= &(std::u64::MAX as u128) {
+        } else if value <= &(u64::MAX as u128) {
             IntMemory::M64
         } else {
             IntMemory::M128
@@ -51,13 +51,13 @@ impl From<&u128> for IntMemory {
 
 impl From<&i128> for IntMemory {
     fn from(value: &i128) -> Self {
-        if value <= &(std::i8::MAX as i128) && value >= &(std::i8::MIN as i128) {
+        if value <= &(i8::MAX as i128) && value >= &(i8::MIN as i128) {
             IntMemory::M8
-        } else if value <= &(std::i16::MAX as i128) && value >= &(std::i16::MIN as i128) {
+        } else if value <= &(i16::MAX as i128) && value >= &(i16::MIN as i128) {
             IntMemory::M16
-        } else if value <= &(std::i32::MAX as i128) && value >= &(std::i32::MIN as i128) {
+        } else if value <= &(i32::MAX as i128) && value >= &(i32::MIN as i128) {
             IntMemory::M32
-        } else if value <= &(std::i64::MAX as i128) && value >= &(std::i64::MIN as i128) {
+        } else if value <= &(i64::MAX as i128) && value >= &(i64::MIN as i128) {
             IntMemory::M64
         } else {
             IntMemory::M128
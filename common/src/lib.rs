@@ -1,10 +1,18 @@
-pub mod decimal;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[macro_use]
 pub mod error;
+
+#[cfg(feature = "std")]
+pub mod decimal;
 pub mod float;
+#[cfg(feature = "std")]
 pub mod ine_range;
 pub mod int_memory;
+#[cfg(feature = "std")]
 pub mod range;
+#[cfg(feature = "std")]
 pub mod hex_bin;
-
-#[macro_use]
-extern crate derive_more;
@@ -0,0 +1,223 @@
+use proc_macro2::Span;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::Attribute;
+use syn::Error;
+use syn::Expr;
+use syn::ExprLit;
+use syn::ExprUnary;
+use syn::Ident;
+use syn::Lit;
+use syn::Meta;
+use syn::Token;
+use syn::UnOp;
+
+/// The attributes (`#[liquesco(...)]` and doc comments) of a type, a field or an enum variant.
+#[derive(Default)]
+pub(crate) struct Attributes {
+    /// Identifier of the type / name of the field or variant.
+    pub name: Option<String>,
+    /// From `#[liquesco(doc = "...")]` or the doc comments.
+    pub doc: Option<String>,
+    /// `true` if the documentation has been given explicitly (not from doc comments).
+    pub explicit_doc: bool,
+    pub implements: Vec<[u8; 16]>,
+    pub min: Option<i128>,
+    pub max: Option<i128>,
+    pub min_len: Option<u64>,
+    pub max_len: Option<u64>,
+    pub sorted: Option<Direction>,
+    pub unique: bool,
+    /// Span of the first constraint (used for error messages).
+    pub constraint_span: Option<Span>,
+}
+
+#[derive(Copy, Clone)]
+pub(crate) enum Direction {
+    Ascending,
+    Descending,
+}
+
+impl Attributes {
+    /// `true` if there are constraints (range, length, sorting).
+    pub(crate) fn has_constraints(&self) -> bool {
+        self.constraint_span.is_some()
+    }
+
+    /// `true` if there's metadata (documentation or implements).
+    pub(crate) fn has_meta(&self) -> bool {
+        self.doc.is_some() || !self.implements.is_empty()
+    }
+
+    pub(crate) fn parse(attributes: &[Attribute]) -> Result<Self, Error> {
+        let mut result = Attributes::default();
+        let mut doc_lines = Vec::new();
+        for attribute in attributes {
+            if attribute.path.is_ident("doc") {
+                if let Ok(Meta::NameValue(name_value)) = attribute.parse_meta() {
+                    if let Lit::Str(line) = name_value.lit {
+                        doc_lines.push(line.value());
+                    }
+                }
+            } else if attribute.path.is_ident("liquesco") {
+                let args =
+                    attribute.parse_args_with(Punctuated::<Arg, Token![,]>::parse_terminated)?;
+                for arg in args {
+                    result.apply(arg)?;
+                }
+            }
+        }
+        if result.doc.is_none() {
+            let doc = doc_lines
+                .iter()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !doc.is_empty() {
+                result.doc = Some(doc);
+            }
+        }
+        Ok(result)
+    }
+
+    fn apply(&mut self, arg: Arg) -> Result<(), Error> {
+        let key = arg.key.to_string();
+        let span = arg.key.span();
+        match key.as_str() {
+            "name" | "id" => self.name = Some(arg.string()?),
+            "doc" => {
+                self.doc = Some(arg.string()?);
+                self.explicit_doc = true;
+            }
+            "implements" => {
+                let value = arg.string()?;
+                let uuid = parse_uuid(&value)
+                    .ok_or_else(|| Error::new(span, format!("`{}` is not a valid UUID.", value)))?;
+                self.implements.push(uuid);
+            }
+            "min" => self.min = Some(arg.int()?),
+            "max" => self.max = Some(arg.int()?),
+            "min_len" => self.min_len = Some(arg.len()?),
+            "max_len" => self.max_len = Some(arg.len()?),
+            "sorted" => {
+                self.sorted = Some(match &arg.value {
+                    None => Direction::Ascending,
+                    Some(_) => match arg.string()?.as_str() {
+                        "ascending" => Direction::Ascending,
+                        "descending" => Direction::Descending,
+                        _ => {
+                            return Err(Error::new(
+                                span,
+                                "Sorting is either `ascending` or `descending`.",
+                            ))
+                        }
+                    },
+                })
+            }
+            "unique" => self.unique = true,
+            _ => return Err(Error::new(span, format!("Unknown attribute `{}`.", key))),
+        }
+        if ["min", "max", "min_len", "max_len", "sorted", "unique"].contains(&key.as_str())
+            && self.constraint_span.is_none()
+        {
+            self.constraint_span = Some(span);
+        }
+        Ok(())
+    }
+}
+
+/// A single argument: `key` or `key = value`.
+struct Arg {
+    key: Ident,
+    value: Option<Expr>,
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Arg { key, value })
+    }
+}
+
+impl Arg {
+    fn string(&self) -> Result<String, Error> {
+        match &self.value {
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Str(string),
+                ..
+            })) => Ok(string.value()),
+            _ => Err(Error::new(
+                self.key.span(),
+                format!(
+                    "`{}` needs a string value (`{} = \"...\"`).",
+                    self.key, self.key
+                ),
+            )),
+        }
+    }
+
+    fn int(&self) -> Result<i128, Error> {
+        let error = || {
+            Error::new(
+                self.key.span(),
+                format!(
+                    "`{}` needs an integer value (`{} = 42`).",
+                    self.key, self.key
+                ),
+            )
+        };
+        match &self.value {
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Int(int), ..
+            })) => int.base10_parse::<i128>(),
+            Some(Expr::Unary(ExprUnary {
+                op: UnOp::Neg(_),
+                expr,
+                ..
+            })) => match &**expr {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(int), ..
+                }) => int.base10_parse::<i128>().map(|value| -value),
+                _ => Err(error()),
+            },
+            _ => Err(error()),
+        }
+    }
+
+    fn len(&self) -> Result<u64, Error> {
+        let value = self.int()?;
+        if value < 0 || value > i128::from(u64::MAX) {
+            Err(Error::new(
+                self.key.span(),
+                format!("`{}` must be within 0 and {}.", self.key, u64::MAX),
+            ))
+        } else {
+            Ok(value as u64)
+        }
+    }
+}
+
+/// Parses a UUID in the hex representation (hyphens are optional).
+fn parse_uuid(value: &str) -> Option<[u8; 16]> {
+    let digits: Vec<u8> = value
+        .chars()
+        .filter(|chr| *chr != '-')
+        .map(|chr| chr.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<_>>()?;
+    if digits.len() != 32 {
+        return None;
+    }
+    let mut uuid = [0u8; 16];
+    for (index, byte) in uuid.iter_mut().enumerate() {
+        *byte = digits[index * 2] * 16 + digits[index * 2 + 1];
+    }
+    Some(uuid)
+}
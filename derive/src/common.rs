@@ -0,0 +1,62 @@
+use proc_macro2::Ident;
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Fields;
+
+/// How the fields of a struct or an enum variant are encoded.
+pub(crate) enum Shape {
+    /// No fields; `struct Unit;` or `Enum::Unit`.
+    Unit,
+    /// Exactly one unnamed field; `struct Newtype(u32);` or `Enum::Newtype(u32)`.
+    Newtype,
+    /// Named fields or a number of unnamed fields other than one.
+    Fields,
+}
+
+impl Shape {
+    pub(crate) fn of(fields: &Fields) -> Self {
+        match fields {
+            Fields::Unit => Shape::Unit,
+            Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => Shape::Newtype,
+            _ => Shape::Fields,
+        }
+    }
+}
+
+/// Name of the local variable the n-th field is bound to.
+pub(crate) fn binding(index: usize) -> Ident {
+    Ident::new(&format!("__field{}", index), Span::call_site())
+}
+
+/// A pattern binding all fields of given variant (see `binding`).
+pub(crate) fn variant_pattern(variant: &Ident, fields: &Fields) -> TokenStream {
+    match fields {
+        Fields::Unit => quote!(Self::#variant),
+        Fields::Unnamed(unnamed) => {
+            let bindings = (0..unnamed.unnamed.len()).map(binding);
+            quote!(Self::#variant(#(#bindings),*))
+        }
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            let bindings = (0..named.named.len()).map(binding);
+            quote!(Self::#variant { #(#names: #bindings),* })
+        }
+    }
+}
+
+/// Constructs `path` (e.g. `Self` or `Self::Variant`) from given field values.
+pub(crate) fn construct(path: TokenStream, fields: &Fields, values: &[TokenStream]) -> TokenStream {
+    match fields {
+        Fields::Unit => path,
+        Fields::Unnamed(_) => quote!(#path(#(#values),*)),
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!(#path { #(#names: #values),* })
+        }
+    }
+}
+
+pub(crate) fn number_of_fields(fields: &Fields) -> u32 {
+    fields.iter().count() as u32
+}
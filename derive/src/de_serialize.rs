@@ -0,0 +1,172 @@
+use crate::common::construct;
+use crate::common::number_of_fields;
+use crate::common::Shape;
+use proc_macro2::Literal;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_quote;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Fields;
+use syn::GenericParam;
+use syn::LifetimeDef;
+
+pub(crate) fn derive(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => de_serialize_struct(&name.to_string(), &data.fields),
+        Data::Enum(data) => {
+            let arms = data.variants.iter().enumerate().map(|(index, variant)| {
+                let ordinal = Literal::u32_unsuffixed(index as u32);
+                let variant_ident = &variant.ident;
+                let body = de_serialize_variant(
+                    &format!("{}::{}", name, variant_ident),
+                    quote!(Self::#variant_ident),
+                    &variant.fields,
+                );
+                quote!(#ordinal => { #body })
+            });
+            let unknown_ordinal = format!("Unknown ordinal {{}} for enum `{}`.", name);
+            quote! {
+                let header = <::liquesco_serialization::types::enumeration::EnumHeader
+                    as ::liquesco_serialization::core::DeSerializer<'__de>>::de_serialize(reader)?;
+                let number_of_values = header.number_of_values();
+                match header.ordinal() {
+                    #(#arms)*
+                    ordinal => ::liquesco_serialization::__private::LqError::err_new(
+                        ::liquesco_serialization::__private::lq_msg!(#unknown_ordinal, ordinal),
+                    ),
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "LqDeserialize cannot be derived for unions.",
+            ))
+        }
+    };
+
+    // The data lifetime `'__de` has to outlive all lifetimes of the type (fields may borrow
+    // from the reader).
+    let mut generics = input.generics.clone();
+    let lifetimes: Vec<_> = input
+        .generics
+        .lifetimes()
+        .map(|def| def.lifetime.clone())
+        .collect();
+    let mut de_lifetime: LifetimeDef = parse_quote!('__de);
+    de_lifetime.bounds.extend(lifetimes);
+    generics
+        .params
+        .insert(0, GenericParam::Lifetime(de_lifetime));
+    {
+        let where_clause = generics.make_where_clause();
+        for param in input.generics.type_params() {
+            let ident = &param.ident;
+            where_clause.predicates.push(parse_quote!(
+                #ident: ::liquesco_serialization::core::DeSerializer<'__de, Item = #ident>
+            ));
+        }
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::liquesco_serialization::core::DeSerializer<'__de>
+            for #name #ty_generics #where_clause
+        {
+            type Item = Self;
+
+            fn de_serialize<__R: ::liquesco_serialization::core::LqReader<'__de>>(
+                reader: &mut __R,
+            ) -> ::core::result::Result<Self::Item, ::liquesco_serialization::__private::LqError> {
+                #body
+            }
+        }
+    })
+}
+
+fn de_serialize_struct(name: &str, fields: &Fields) -> TokenStream {
+    match Shape::of(fields) {
+        Shape::Unit => {
+            let not_absent = format!("Expecting an absent option for unit struct `{}`.", name);
+            quote! {
+                match <::liquesco_serialization::types::option::Presence
+                    as ::liquesco_serialization::core::DeSerializer<'__de>>::de_serialize(reader)?
+                {
+                    ::liquesco_serialization::types::option::Presence::Absent => {
+                        ::core::result::Result::Ok(Self)
+                    }
+                    ::liquesco_serialization::types::option::Presence::Present => {
+                        ::liquesco_serialization::__private::LqError::err_new(#not_absent)
+                    }
+                }
+            }
+        }
+        Shape::Newtype => {
+            let value = de_serialize_value(&fields.iter().next().unwrap().ty);
+            quote!(::core::result::Result::Ok(Self(#value)))
+        }
+        Shape::Fields => {
+            let number_of_fields = Literal::u32_unsuffixed(number_of_fields(fields));
+            let value = construct(quote!(Self), fields, &de_serialize_values(fields));
+            quote! {
+                let header = <::liquesco_serialization::types::seq::SeqHeader
+                    as ::liquesco_serialization::core::DeSerializer<'__de>>::de_serialize(reader)?;
+                header.read_struct(reader, #number_of_fields, |reader| {
+                    ::liquesco_serialization::core::LqReader::limit_enter(reader)?;
+                    let value = #value;
+                    ::liquesco_serialization::core::LqReader::limit_exit(reader);
+                    ::core::result::Result::Ok(value)
+                })
+            }
+        }
+    }
+}
+
+/// Expects `number_of_values` (the number of values in the data) to be in scope.
+fn de_serialize_variant(name: &str, path: TokenStream, fields: &Fields) -> TokenStream {
+    let number_of_fields = number_of_fields(fields);
+    let value = construct(path, fields, &de_serialize_values(fields));
+    if number_of_fields == 0 {
+        return quote! {
+            ::liquesco_serialization::core::LqReader::skip_n_values_u32(reader, number_of_values)?;
+            ::core::result::Result::Ok(#value)
+        };
+    }
+    let too_few_values = format!(
+        "Enum variant `{}` needs at least {} values; data only has {{}} values.",
+        name, number_of_fields
+    );
+    let number_of_fields = Literal::u32_unsuffixed(number_of_fields);
+    quote! {
+        if number_of_values < #number_of_fields {
+            return ::liquesco_serialization::__private::LqError::err_new(
+                ::liquesco_serialization::__private::lq_msg!(#too_few_values, number_of_values),
+            );
+        }
+        ::liquesco_serialization::core::LqReader::limit_enter(reader)?;
+        let value = #value;
+        ::liquesco_serialization::core::LqReader::limit_exit(reader);
+        ::liquesco_serialization::core::LqReader::skip_n_values_u32(
+            reader,
+            number_of_values - #number_of_fields,
+        )?;
+        ::core::result::Result::Ok(value)
+    }
+}
+
+fn de_serialize_values(fields: &Fields) -> Vec<TokenStream> {
+    fields
+        .iter()
+        .map(|field| de_serialize_value(&field.ty))
+        .collect()
+}
+
+fn de_serialize_value(ty: &syn::Type) -> TokenStream {
+    quote! {
+        <#ty as ::liquesco_serialization::core::DeSerializer<'__de>>::de_serialize(reader)?
+    }
+}
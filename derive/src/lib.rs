@@ -0,0 +1,68 @@
+//! Derive macros for `liquesco_serialization::core::Serializer` (`LqSerialize`) and
+//! `liquesco_serialization::core::DeSerializer` (`LqDeserialize`).
+//!
+//! The encoding is the same as the one used by serde (see `liquesco_serialization::serde`):
+//!
+//!  - Structs and tuple structs are sequences of their fields.
+//!  - Newtype structs (tuple structs with exactly one field) are encoded as their field.
+//!  - Unit structs are absent options.
+//!  - Enum variants are enums with the variant index as ordinal and the variant fields as values.
+//!
+//! When reading, data may contain more struct fields / enum variant values than the type
+//! (fields added by a newer version of the type); these trailing values are skipped.
+//! Text (`&str`) and binary (`&[u8]`) fields borrow from the reader.
+//!
+//! `LqSchema` implements `liquesco_schema::schema_builder::BuildsOwnSchema` for structs and
+//! enums. Recursive types (e.g. `next: Option<Box<Node>>`) are supported if the schema builder
+//! supports them (`SchemaBuilder::begin_type`; the `DefaultSchemaBuilder` does); other builders
+//! recurse infinitely. Not supported: types with type parameters, unit structs, enums without
+//! variants and unions. Types are identified by their name in snake case; fields and types can
+//! be customized using `#[liquesco(...)]`:
+//!
+//!  - `name = "..."` (or `id = "..."`): Identifier of the type / name of the field or variant.
+//!  - `doc = "..."`: Documentation (by default the doc comments are used).
+//!  - `implements = "<uuid>"`: Adds a UUID to what the type implements (repeatable).
+//!  - `min = n`, `max = n`: Range of integers.
+//!  - `min_len = n`, `max_len = n`: Length of strings, binaries, sequences, sets and maps.
+//!  - `sorted`, `sorted = "descending"`, `unique`: Sorting of sequences (sets are sorted).
+//!
+//! Fields with constraints or documentation get their own type (`<type id>_0_<field name>`;
+//! values of enum variants: `<type id>_0_<variant name>_0_<field name>`). Segments consisting of
+//! digits only are reserved for these generated identifiers (and the identifiers of
+//! `liquesco_schema::native`); they cannot be used in identifiers of types and variants.
+
+extern crate proc_macro;
+
+mod attributes;
+mod common;
+mod de_serialize;
+mod schema;
+mod serialize;
+
+use proc_macro::TokenStream;
+use syn::parse_macro_input;
+use syn::DeriveInput;
+
+#[proc_macro_derive(LqSerialize)]
+pub fn derive_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    serialize::derive(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(LqDeserialize)]
+pub fn derive_de_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    de_serialize::derive(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(LqSchema, attributes(liquesco))]
+pub fn derive_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    schema::derive(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
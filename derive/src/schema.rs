@@ -0,0 +1,605 @@
+use crate::attributes::Attributes;
+use crate::attributes::Direction;
+use crate::common::Shape;
+use proc_macro2::Literal;
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Field;
+use syn::Fields;
+use syn::GenericArgument;
+use syn::PathArguments;
+use syn::Type;
+
+/// Maximum number of segments of an identifier (see `liquesco_schema::identifier`).
+const MAX_NUMBER_OF_SEGMENTS: usize = 12;
+/// Maximum length of a single segment of an identifier.
+const SEGMENT_MAX_LEN: usize = 30;
+
+pub(crate) fn derive(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    if let Some(param) = input.generics.type_params().next() {
+        return Err(Error::new_spanned(
+            param,
+            "LqSchema cannot be derived for types with type parameters (the identifier of the \
+             type would be the same for all type arguments).",
+        ));
+    }
+    let attributes = Attributes::parse(&input.attrs)?;
+    let id = match &attributes.name {
+        Some(id) => id.clone(),
+        None => snake_case(&name.to_string()),
+    };
+    validate_identifier(&id, Span::call_site())?;
+    validate_no_number_segment(&id, Span::call_site())?;
+
+    let body = match &input.data {
+        Data::Struct(data) => match Shape::of(&data.fields) {
+            Shape::Unit => {
+                return Err(Error::new_spanned(
+                    input,
+                    "LqSchema cannot be derived for unit structs (there's no schema type for \
+                     unit structs).",
+                ))
+            }
+            // The newtype is encoded as its field: So the schema is the schema of the field
+            // (the attributes of the type apply to the field).
+            Shape::Newtype => {
+                let field = data.fields.iter().next().unwrap();
+                let mut field_attributes = Attributes::parse(&field.attrs)?;
+                merge(&mut field_attributes, attributes);
+                field_type(&id, &field.ty, &field_attributes)?
+            }
+            Shape::Fields => {
+                let fields = struct_fields(&id, &data.fields)?;
+                let meta = meta(&attributes);
+                recursive(
+                    &id,
+                    quote! {
+                        let __type = ::liquesco_schema::types::structure::TStruct::default()
+                            #(.add(#fields))*;
+                        #meta
+                    },
+                )
+            }
+        },
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(Error::new_spanned(
+                    input,
+                    "LqSchema cannot be derived for enums without variants (an enum needs at \
+                     least one variant).",
+                ));
+            }
+            let mut variants = Vec::new();
+            for variant in &data.variants {
+                let variant_attributes = Attributes::parse(&variant.attrs)?;
+                let variant_name = match variant_attributes.name {
+                    Some(name) => name,
+                    None => snake_case(&variant.ident.to_string()),
+                };
+                validate_identifier(&variant_name, variant.ident.span())?;
+                validate_no_number_segment(&variant_name, variant.ident.span())?;
+                let mut values = Vec::new();
+                for (index, field) in variant.fields.iter().enumerate() {
+                    let field_name = field_name(field, index)?;
+                    let field_id = format!("{}_0_{}_0_{}", id, variant_name, field_name);
+                    validate_identifier(&field_id, variant.ident.span())?;
+                    values.push(field_type(
+                        &field_id,
+                        &field.ty,
+                        &Attributes::parse(&field.attrs)?,
+                    )?);
+                }
+                variants.push(quote! {
+                    ::liquesco_schema::types::enumeration::Variant::new(
+                        <::liquesco_schema::identifier::Identifier
+                            as ::core::convert::TryFrom<&str>>::try_from(#variant_name).unwrap(),
+                    )
+                    #(.add_value({ #values }))*
+                });
+            }
+            let meta = meta(&attributes);
+            recursive(
+                &id,
+                quote! {
+                    let __type = ::liquesco_schema::types::enumeration::TEnum::default()
+                        #(.add_variant(#variants))*;
+                    #meta
+                },
+            )
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "LqSchema cannot be derived for unions.",
+            ))
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::liquesco_schema::schema_builder::BuildsOwnSchema
+            for #name #ty_generics #where_clause
+        {
+            fn build_schema<__B>(builder: &mut __B) -> ::liquesco_schema::core::TypeRef
+            where
+                __B: ::liquesco_schema::schema_builder::SchemaBuilder<'static>,
+            {
+                #body
+            }
+        }
+    })
+}
+
+/// Adds the type (`__type`, `__meta`; declared by `build`) with given id. Structures and enums
+/// might reference themselves: In this case a reference (by identifier) is returned instead of
+/// building the type again (see `SchemaBuilder::begin_type`).
+fn recursive(id: &str, build: TokenStream) -> TokenStream {
+    quote! {
+        let __id = <::liquesco_schema::identifier::StrIdentifier
+            as ::core::convert::TryFrom<::std::borrow::Cow<'static, str>>>::try_from(
+            ::std::borrow::Cow::Borrowed(#id),
+        )
+        .unwrap();
+        if let ::core::option::Option::Some(__reference) =
+            ::liquesco_schema::schema_builder::SchemaBuilder::begin_type(builder, &__id)
+        {
+            return __reference;
+        }
+        #build
+        ::liquesco_schema::schema_builder::SchemaBuilder::end_type(builder, &__id);
+        ::liquesco_schema::schema_builder::SchemaBuilder::add_or_defer(
+            builder,
+            ::core::result::Result::Ok(__id),
+            ::liquesco_schema::metadata::MetadataSetter::with_meta(__type, __meta),
+        )
+    }
+}
+
+/// Returns the `Field::new(...)` expressions.
+fn struct_fields(id: &str, fields: &Fields) -> Result<Vec<TokenStream>, Error> {
+    let mut result = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let attributes = Attributes::parse(&field.attrs)?;
+        let name = match &attributes.name {
+            Some(name) => name.clone(),
+            None => field_name(field, index)?,
+        };
+        validate_identifier(&name, Span::call_site())?;
+        let field_id = format!("{}_0_{}", id, name);
+        validate_identifier(&field_id, Span::call_site())?;
+        let field_type = field_type(&field_id, &field.ty, &attributes)?;
+        result.push(quote! {
+            ::liquesco_schema::types::structure::Field::new(
+                <::liquesco_schema::identifier::Identifier
+                    as ::core::convert::TryFrom<&str>>::try_from(#name).unwrap(),
+                { #field_type },
+            )
+        });
+    }
+    Ok(result)
+}
+
+/// Name of the field: The field name for named fields, `field_n` for unnamed fields.
+fn field_name(field: &Field, index: usize) -> Result<String, Error> {
+    match &field.ident {
+        Some(ident) => {
+            let name = ident.to_string();
+            let name = name.trim_start_matches("r#").trim_matches('_').to_string();
+            validate_identifier(&name, ident.span())?;
+            Ok(name)
+        }
+        None => Ok(format!("field_{}", index)),
+    }
+}
+
+/// The attributes of the type (doc, implements) are used for the newtype field.
+fn merge(field: &mut Attributes, of_type: Attributes) {
+    if of_type.doc.is_some() {
+        field.doc = of_type.doc;
+        field.explicit_doc = of_type.explicit_doc;
+    }
+    field.implements.extend(of_type.implements);
+}
+
+/// The supported (built-in) types that can have constraints and metadata.
+enum Kind<'a> {
+    UInt(u128),
+    SInt(i128, i128),
+    Bool,
+    Float32,
+    Float64,
+    Unicode,
+    Binary,
+    Uuid,
+    Option(&'a Type),
+    Seq(&'a Type),
+    Set(&'a Type),
+    Map(&'a Type, &'a Type),
+}
+
+impl<'a> Kind<'a> {
+    fn of(ty: &'a Type) -> Option<Self> {
+        match ty {
+            Type::Reference(reference) => match &*reference.elem {
+                Type::Path(path) if path.path.is_ident("str") => Some(Kind::Unicode),
+                Type::Slice(slice) => match &*slice.elem {
+                    Type::Path(path) if path.path.is_ident("u8") => Some(Kind::Binary),
+                    _ => None,
+                },
+                _ => None,
+            },
+            Type::Path(path) if path.qself.is_none() => {
+                let segment = path.path.segments.last()?;
+                let arguments: Vec<&Type> = match &segment.arguments {
+                    PathArguments::AngleBracketed(arguments) => arguments
+                        .args
+                        .iter()
+                        .filter_map(|argument| match argument {
+                            GenericArgument::Type(ty) => Some(ty),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                Some(
+                    match (segment.ident.to_string().as_str(), arguments.as_slice()) {
+                        ("u8", []) => Kind::UInt(u128::from(u8::MAX)),
+                        ("u16", []) => Kind::UInt(u128::from(u16::MAX)),
+                        ("u32", []) => Kind::UInt(u128::from(u32::MAX)),
+                        ("u64", []) => Kind::UInt(u128::from(u64::MAX)),
+                        ("u128", []) => Kind::UInt(u128::MAX),
+                        ("i8", []) => Kind::SInt(i128::from(i8::MIN), i128::from(i8::MAX)),
+                        ("i16", []) => Kind::SInt(i128::from(i16::MIN), i128::from(i16::MAX)),
+                        ("i32", []) => Kind::SInt(i128::from(i32::MIN), i128::from(i32::MAX)),
+                        ("i64", []) => Kind::SInt(i128::from(i64::MIN), i128::from(i64::MAX)),
+                        ("i128", []) => Kind::SInt(i128::MIN, i128::MAX),
+                        ("bool", []) => Kind::Bool,
+                        ("f32", []) => Kind::Float32,
+                        ("f64", []) => Kind::Float64,
+                        ("String", []) => Kind::Unicode,
+                        ("Uuid", []) => Kind::Uuid,
+                        ("Option", [present]) => Kind::Option(present),
+                        ("Vec", [element]) => Kind::Seq(element),
+                        ("BTreeSet", [element]) => Kind::Set(element),
+                        ("BTreeMap", [key, value]) => Kind::Map(key, value),
+                        _ => return None,
+                    },
+                )
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns an expression evaluating to the type reference of the field. If the field has
+/// constraints or metadata, a type for this field (with given id) is added.
+fn field_type(id: &str, ty: &Type, attributes: &Attributes) -> Result<TokenStream, Error> {
+    let own_schema = quote! {
+        <#ty as ::liquesco_schema::schema_builder::BuildsOwnSchema>::build_schema(builder)
+    };
+    let kind = Kind::of(ty);
+    let kind = match kind {
+        Some(kind) if attributes.has_constraints() || attributes.has_meta() => kind,
+        Some(_) => return Ok(own_schema),
+        None => {
+            if let Some(span) = attributes.constraint_span {
+                return Err(Error::new(
+                    span,
+                    "Constraints are only supported for integers, strings, binaries, \
+                     sequences, sets and maps.",
+                ));
+            }
+            if attributes.explicit_doc || !attributes.implements.is_empty() {
+                return Err(Error::new_spanned(
+                    ty,
+                    "Documentation and implements are not supported for this type (document \
+                     the type itself instead).",
+                ));
+            }
+            // doc comments of fields with custom types are not part of the schema
+            return Ok(own_schema);
+        }
+    };
+    validate_identifier(id, Span::call_site())?;
+
+    let constraint_error = |what: &str| {
+        Err(Error::new(
+            attributes.constraint_span.unwrap_or_else(Span::call_site),
+            format!("{} is not supported for this type.", what),
+        ))
+    };
+    if (attributes.min.is_some() || attributes.max.is_some())
+        && !matches!(kind, Kind::UInt(_) | Kind::SInt(_, _))
+    {
+        return constraint_error("A range (`min`, `max`)");
+    }
+    if (attributes.min_len.is_some() || attributes.max_len.is_some())
+        && !matches!(
+            kind,
+            Kind::Unicode | Kind::Binary | Kind::Seq(_) | Kind::Set(_) | Kind::Map(_, _)
+        )
+    {
+        return constraint_error("A length (`min_len`, `max_len`)");
+    }
+    if (attributes.sorted.is_some() || attributes.unique) && !matches!(kind, Kind::Seq(_)) {
+        return constraint_error("Sorting (`sorted`, `unique`)");
+    }
+
+    let construct = match kind {
+        Kind::UInt(natural_max) => {
+            let (min, max) = range(attributes, 0, natural_max.min(i128::MAX as u128) as i128)?;
+            let min = Literal::u128_suffixed(min as u128);
+            let max = Literal::u128_suffixed(if attributes.max.is_none() {
+                natural_max
+            } else {
+                max as u128
+            });
+            quote!(::liquesco_schema::types::uint::TUInt::try_new(#min, #max).unwrap())
+        }
+        Kind::SInt(natural_min, natural_max) => {
+            let (min, max) = range(attributes, natural_min, natural_max)?;
+            let min = Literal::i128_suffixed(min);
+            let max = Literal::i128_suffixed(max);
+            quote!(::liquesco_schema::types::sint::TSInt::try_new(#min, #max).unwrap())
+        }
+        Kind::Bool => quote!(::liquesco_schema::types::boolean::TBool::default()),
+        Kind::Float32 => float(quote!(TFloat32), quote!(F32Ext), quote!(f32)),
+        Kind::Float64 => float(quote!(TFloat64), quote!(F64Ext), quote!(f64)),
+        Kind::Unicode => {
+            let (min, max) = length(attributes, u64::MAX)?;
+            quote! {
+                ::liquesco_schema::types::unicode::TUnicode::try_new(
+                    #min,
+                    #max,
+                    ::liquesco_schema::types::unicode::LengthType::Utf8Byte,
+                )
+                .unwrap()
+            }
+        }
+        Kind::Binary => {
+            let (min, max) = length(attributes, u64::MAX)?;
+            quote!(::liquesco_schema::types::binary::TBinary::try_new(#min, #max).unwrap())
+        }
+        Kind::Uuid => quote!(::liquesco_schema::types::uuid::TUuid::default()),
+        Kind::Option(present) => quote! {
+            ::liquesco_schema::types::option::TOption::new(
+                <#present as ::liquesco_schema::schema_builder::BuildsOwnSchema>::build_schema(
+                    builder,
+                ),
+            )
+        },
+        Kind::Seq(element) | Kind::Set(element) => {
+            let (min, max) = length(attributes, u64::from(u32::MAX))?;
+            let (min, max) = (
+                Literal::u32_suffixed(min as u32),
+                Literal::u32_suffixed(max as u32),
+            );
+            let sorted = if let Kind::Set(_) = kind {
+                Some((Direction::Ascending, true))
+            } else if attributes.sorted.is_some() || attributes.unique {
+                Some((
+                    attributes.sorted.unwrap_or(Direction::Ascending),
+                    attributes.unique,
+                ))
+            } else {
+                None
+            };
+            let with_sorted = sorted.map(|(direction, unique)| {
+                let direction = match direction {
+                    Direction::Ascending => quote!(Ascending),
+                    Direction::Descending => quote!(Descending),
+                };
+                quote! {
+                    .with_sorted(::liquesco_schema::types::seq::Sorted {
+                        direction: ::liquesco_schema::types::seq::Direction::#direction,
+                        unique: #unique,
+                    })
+                }
+            });
+            quote! {
+                ::liquesco_schema::types::seq::TSeq::try_new(
+                    <#element as ::liquesco_schema::schema_builder::BuildsOwnSchema>::build_schema(
+                        builder,
+                    ),
+                    #min,
+                    #max,
+                )
+                .unwrap()
+                #with_sorted
+            }
+        }
+        Kind::Map(key, value) => {
+            let (min, max) = length(attributes, u64::from(u32::MAX))?;
+            let (min, max) = (
+                Literal::u32_suffixed(min as u32),
+                Literal::u32_suffixed(max as u32),
+            );
+            quote! {
+                ::liquesco_schema::types::map::TMap::new(
+                    <#key as ::liquesco_schema::schema_builder::BuildsOwnSchema>::build_schema(
+                        builder,
+                    ),
+                    <#value as ::liquesco_schema::schema_builder::BuildsOwnSchema>::build_schema(
+                        builder,
+                    ),
+                )
+                .with_length(
+                    ::liquesco_schema::__private::U32IneRange::try_new("Map length", #min, #max)
+                        .unwrap(),
+                )
+            }
+        }
+    };
+    let meta = meta(attributes);
+    Ok(quote! {
+        let __type = #construct;
+        #meta
+        ::liquesco_schema::schema_builder::SchemaBuilder::add_or_defer(
+            builder,
+            <::liquesco_schema::identifier::StrIdentifier
+                as ::core::convert::TryFrom<::std::borrow::Cow<'static, str>>>::try_from(
+                ::std::borrow::Cow::Borrowed(#id),
+            ),
+            ::liquesco_schema::metadata::MetadataSetter::with_meta(__type, __meta),
+        )
+    })
+}
+
+/// Any float (like the schema built by `BuildsOwnSchema` for `f32` / `f64`).
+fn float(float_type: TokenStream, ext: TokenStream, primitive: TokenStream) -> TokenStream {
+    quote! {
+        ::liquesco_schema::types::float::#float_type::try_new(
+            ::liquesco_schema::__private::#ext::from(<#primitive>::MIN),
+            ::liquesco_schema::__private::#ext::from(<#primitive>::MAX),
+        )
+        .unwrap()
+        .with_allow_positive_zero(true)
+        .with_allow_negative_zero(true)
+        .with_allow_nan(true)
+        .with_allow_positive_infinity(true)
+        .with_allow_negative_infinity(true)
+        .with_allow_subnormal(true)
+    }
+}
+
+fn range(
+    attributes: &Attributes,
+    natural_min: i128,
+    natural_max: i128,
+) -> Result<(i128, i128), Error> {
+    let min = attributes.min.unwrap_or(natural_min);
+    let max = attributes.max.unwrap_or(natural_max);
+    let span = attributes.constraint_span.unwrap_or_else(Span::call_site);
+    if min < natural_min || (attributes.max.is_some() && max > natural_max) {
+        Err(Error::new(
+            span,
+            format!(
+                "The range must be within the range of the type ({} to {}).",
+                natural_min, natural_max
+            ),
+        ))
+    } else if min > max {
+        Err(Error::new(
+            span,
+            format!("`min` ({}) must not be greater than `max` ({}).", min, max),
+        ))
+    } else {
+        Ok((min, max))
+    }
+}
+
+fn length(attributes: &Attributes, natural_max: u64) -> Result<(u64, u64), Error> {
+    let min = attributes.min_len.unwrap_or(0);
+    let max = attributes.max_len.unwrap_or(natural_max);
+    let span = attributes.constraint_span.unwrap_or_else(Span::call_site);
+    if max > natural_max {
+        Err(Error::new(
+            span,
+            format!("`max_len` must not be greater than {}.", natural_max),
+        ))
+    } else if min > max {
+        Err(Error::new(
+            span,
+            format!(
+                "`min_len` ({}) must not be greater than `max_len` ({}).",
+                min, max
+            ),
+        ))
+    } else {
+        Ok((min, max))
+    }
+}
+
+/// Declares `__meta` (the metadata).
+fn meta(attributes: &Attributes) -> TokenStream {
+    let set_doc = attributes
+        .doc
+        .as_ref()
+        .map(|doc| quote!(__meta.set_doc(#doc);));
+    let add_implements = attributes.implements.iter().map(|uuid| {
+        quote! {
+            __meta
+                .add_implements(::liquesco_schema::__private::Uuid::from([#(#uuid),*]))
+                .unwrap();
+        }
+    });
+    let mutable = if attributes.has_meta() {
+        quote!(mut)
+    } else {
+        quote!()
+    };
+    quote! {
+        let #mutable __meta = ::liquesco_schema::metadata::Meta::empty();
+        #set_doc
+        #(#add_implements)*
+    }
+}
+
+/// Converts a Rust type / variant name (`CamelCase`) to snake case.
+fn snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for (index, chr) in name.trim_start_matches("r#").chars().enumerate() {
+        if chr.is_uppercase() {
+            if index > 0 && !result.ends_with('_') {
+                result.push('_');
+            }
+            result.extend(chr.to_lowercase());
+        } else {
+            result.push(chr);
+        }
+    }
+    result
+}
+
+/// Makes sure the identifier is valid (see `liquesco_schema::identifier::Identifier`).
+fn validate_identifier(id: &str, span: Span) -> Result<(), Error> {
+    let segments: Vec<&str> = id.split('_').collect();
+    let valid_segments = segments.iter().all(|segment| {
+        !segment.is_empty()
+            && segment.len() <= SEGMENT_MAX_LEN
+            && segment
+                .bytes()
+                .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit())
+    });
+    if valid_segments && segments.len() <= MAX_NUMBER_OF_SEGMENTS {
+        Ok(())
+    } else {
+        Err(Error::new(
+            span,
+            format!(
+                "`{}` is not a valid liquesco identifier: Identifiers consist of 1 to {} \
+                 segments separated by `_`; segments consist of 1 to {} ASCII characters \
+                 (a-z, 0-9). Use `#[liquesco(name = \"...\")]` to choose another name.",
+                id, MAX_NUMBER_OF_SEGMENTS, SEGMENT_MAX_LEN
+            ),
+        ))
+    }
+}
+
+/// Identifiers of types and variants must not contain segments consisting of digits only: These
+/// are reserved for the identifiers generated for the types of fields (`{type}_0_{field}`) and
+/// for the types of `liquesco_schema::native` (e.g. `option_1_u32`); so these identifiers
+/// cannot collide.
+fn validate_no_number_segment(id: &str, span: Span) -> Result<(), Error> {
+    let number_segment = id
+        .split('_')
+        .any(|segment| segment.bytes().all(|byte| byte.is_ascii_digit()));
+    if number_segment {
+        Err(Error::new(
+            span,
+            format!(
+                "`{}` cannot be used as identifier of a type or a variant: Segments consisting \
+                 of digits only are reserved for generated identifiers. Use \
+                 `#[liquesco(name = \"...\")]` to choose another name.",
+                id
+            ),
+        ))
+    } else {
+        Ok(())
+    }
+}
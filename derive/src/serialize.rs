@@ -0,0 +1,142 @@
+use crate::common::binding;
+use crate::common::number_of_fields;
+use crate::common::variant_pattern;
+use crate::common::Shape;
+use proc_macro2::Literal;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse_quote;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Fields;
+use syn::Index;
+
+pub(crate) fn derive(input: &DeriveInput) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => serialize_struct(&data.fields),
+        Data::Enum(data) => {
+            let arms = data.variants.iter().enumerate().map(|(index, variant)| {
+                let pattern = variant_pattern(&variant.ident, &variant.fields);
+                let body = serialize_variant(index as u32, &variant.fields);
+                quote!(#pattern => { #body })
+            });
+            if data.variants.is_empty() {
+                quote!(match *item {})
+            } else {
+                quote! {
+                    match item {
+                        #(#arms)*
+                    }
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "LqSerialize cannot be derived for unions.",
+            ))
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    {
+        let where_clause = generics.make_where_clause();
+        for param in input.generics.type_params() {
+            let ident = &param.ident;
+            where_clause.predicates.push(parse_quote!(
+                #ident: ::liquesco_serialization::core::Serializer<Item = #ident>
+            ));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::liquesco_serialization::core::Serializer for #name #ty_generics
+        #where_clause
+        {
+            type Item = Self;
+
+            fn serialize<__W: ::liquesco_serialization::core::LqWriter>(
+                writer: &mut __W,
+                item: &Self::Item,
+            ) -> ::core::result::Result<(), ::liquesco_serialization::__private::LqError> {
+                #body
+            }
+        }
+    })
+}
+
+fn serialize_struct(fields: &Fields) -> TokenStream {
+    let values: Vec<TokenStream> = match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .map(|field| {
+                let ident = &field.ident;
+                quote!(&item.#ident)
+            })
+            .collect(),
+        _ => (0..fields.iter().count())
+            .map(|index| {
+                let index = Index::from(index);
+                quote!(&item.#index)
+            })
+            .collect(),
+    };
+    match Shape::of(fields) {
+        Shape::Unit => quote! {
+            <::liquesco_serialization::types::option::Presence
+                as ::liquesco_serialization::core::Serializer>::serialize(
+                writer,
+                &::liquesco_serialization::types::option::Presence::Absent,
+            )
+        },
+        Shape::Newtype => serialize_value(&fields.iter().next().unwrap().ty, &values[0]),
+        Shape::Fields => {
+            let number_of_fields = Literal::u32_unsuffixed(number_of_fields(fields));
+            let writes = fields
+                .iter()
+                .zip(values.iter())
+                .map(|(field, value)| serialize_value(&field.ty, value));
+            quote! {
+                <::liquesco_serialization::types::seq::SeqHeader
+                    as ::liquesco_serialization::core::Serializer>::serialize(
+                    writer,
+                    &::liquesco_serialization::types::seq::SeqHeader::new(#number_of_fields),
+                )?;
+                #(#writes?;)*
+                ::core::result::Result::Ok(())
+            }
+        }
+    }
+}
+
+/// Fields of the variant are bound by the pattern (see `variant_pattern`).
+fn serialize_variant(ordinal: u32, fields: &Fields) -> TokenStream {
+    let ordinal = Literal::u32_unsuffixed(ordinal);
+    let number_of_values = Literal::u32_unsuffixed(number_of_fields(fields));
+    let writes = fields.iter().enumerate().map(|(index, field)| {
+        let binding = binding(index);
+        serialize_value(&field.ty, &quote!(#binding))
+    });
+    quote! {
+        <::liquesco_serialization::types::enumeration::EnumHeader
+            as ::liquesco_serialization::core::Serializer>::serialize(
+            writer,
+            &::liquesco_serialization::types::enumeration::EnumHeader::new(
+                #ordinal,
+                #number_of_values,
+            ),
+        )?;
+        #(#writes?;)*
+        ::core::result::Result::Ok(())
+    }
+}
+
+fn serialize_value(ty: &syn::Type, value: &TokenStream) -> TokenStream {
+    quote! {
+        <#ty as ::liquesco_serialization::core::Serializer>::serialize(writer, #value)
+    }
+}
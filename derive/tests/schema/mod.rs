@@ -0,0 +1,457 @@
+use liquesco_common::float::F32Ext;
+use liquesco_common::ine_range::U32IneRange;
+use liquesco_derive::LqSchema;
+use liquesco_schema::core::{Config, Schema, TypeRef};
+use liquesco_schema::identifier::{Format, Identifier, StrIdentifier};
+use liquesco_schema::metadata::{Meta, MetadataSetter};
+use liquesco_schema::schema::DefaultSchema;
+use liquesco_schema::schema_builder::{BuildsOwnSchema, DefaultSchemaBuilder, SchemaBuilder};
+use liquesco_schema::type_container::DefaultTypeContainer;
+use liquesco_schema::types::boolean::TBool;
+use liquesco_schema::types::enumeration::{TEnum, Variant};
+use liquesco_schema::types::float::TFloat32;
+use liquesco_schema::types::map::TMap;
+use liquesco_schema::types::option::TOption;
+use liquesco_schema::types::seq::{Direction, Sorted, TSeq};
+use liquesco_schema::types::sint::TSInt;
+use liquesco_schema::types::structure::{Field, TStruct};
+use liquesco_schema::types::uint::TUInt;
+use liquesco_schema::types::unicode::{LengthType, TUnicode};
+use liquesco_serialization::serde::serialize_to_vec;
+use liquesco_serialization::slice_reader::SliceReader;
+use liquesco_serialization::types::uuid::Uuid;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+
+fn container<T: BuildsOwnSchema>() -> DefaultTypeContainer<'static> {
+    let mut builder = DefaultSchemaBuilder::default();
+    let root = T::build_schema(&mut builder);
+    builder.finish(root).unwrap()
+}
+
+fn hand_written<F>(build: F) -> DefaultTypeContainer<'static>
+where
+    F: FnOnce(&mut DefaultSchemaBuilder<'static>) -> TypeRef,
+{
+    let mut builder = DefaultSchemaBuilder::default();
+    let root = build(&mut builder);
+    builder.finish(root).unwrap()
+}
+
+fn is_valid<T: BuildsOwnSchema, S: Serialize>(item: S) -> bool {
+    let schema: DefaultSchema<DefaultTypeContainer> = container::<T>().into();
+    let data = serialize_to_vec(&item).unwrap();
+    let mut reader = SliceReader::from(&data);
+    schema.validate(Config::strict(), &mut reader).is_ok()
+}
+
+fn id(string: &'static str) -> Identifier<'static> {
+    Identifier::try_from(string).unwrap()
+}
+
+/// A person.
+#[derive(LqSchema, Serialize)]
+struct Person {
+    /// The name of the person.
+    #[liquesco(min_len = 1, max_len = 50)]
+    name: String,
+    #[liquesco(max = 150)]
+    age: u8,
+    temperature: i16,
+    nick_names: Vec<String>,
+    #[liquesco(name = "email")]
+    maybe_email: Option<String>,
+}
+
+#[test]
+fn struct_equals_hand_written() {
+    let expected = hand_written(|builder| {
+        let name = builder.add_unwrap(
+            "person_0_name",
+            TUnicode::try_new(1, 50, LengthType::Utf8Byte)
+                .unwrap()
+                .with_doc("The name of the person."),
+        );
+        let age = builder.add_unwrap("person_0_age", TUInt::try_new(0u8, 150u8).unwrap());
+        let temperature = builder.add_unwrap("i16", TSInt::try_new(i16::MIN, i16::MAX).unwrap());
+        let unicode = builder.add_unwrap(
+            "unicode",
+            TUnicode::try_new(0, u64::MAX, LengthType::Utf8Byte).unwrap(),
+        );
+        let nick_names = builder.add_unwrap(
+            "seq_1_unicode",
+            TSeq::try_new(unicode.clone(), 0, u32::MAX).unwrap(),
+        );
+        let email = builder.add_unwrap("option_1_unicode", TOption::new(unicode));
+        builder.add_unwrap(
+            "person",
+            TStruct::default()
+                .add(Field::new(id("name"), name))
+                .add(Field::new(id("age"), age))
+                .add(Field::new(id("temperature"), temperature))
+                .add(Field::new(id("nick_names"), nick_names))
+                .add(Field::new(id("email"), email))
+                .with_doc("A person."),
+        )
+    });
+    assert_eq!(expected, container::<Person>());
+}
+
+#[test]
+fn struct_validation() {
+    let person = Person {
+        name: "Demo".to_string(),
+        age: 150,
+        temperature: -20,
+        nick_names: vec!["Nick".to_string()],
+        maybe_email: None,
+    };
+    assert!(is_valid::<Person, _>(&person));
+    assert!(!is_valid::<Person, _>(Person { age: 151, ..person }));
+    assert!(!is_valid::<Person, _>(Person {
+        name: "".to_string(),
+        age: 20,
+        temperature: 0,
+        nick_names: vec![],
+        maybe_email: Some("demo@demo.com".to_string()),
+    }));
+}
+
+#[derive(LqSchema, Serialize)]
+#[liquesco(id = "shape", implements = "b6a9ab86-e4c8-4d1b-a6cc-4cd5c43a4a9f")]
+enum Shape {
+    Empty,
+    Circle(u32),
+    Rectangle { width: u32, height: u32 },
+}
+
+#[test]
+fn enum_equals_hand_written() {
+    let expected = hand_written(|builder| {
+        let u32_ref = builder.add_unwrap("u32", TUInt::try_new(0u32, u32::MAX).unwrap());
+        let mut meta = Meta::empty();
+        meta.add_implements(Uuid::from([
+            0xb6, 0xa9, 0xab, 0x86, 0xe4, 0xc8, 0x4d, 0x1b, 0xa6, 0xcc, 0x4c, 0xd5, 0xc4, 0x3a,
+            0x4a, 0x9f,
+        ]))
+        .unwrap();
+        builder.add_unwrap(
+            "shape",
+            TEnum::default()
+                .add_variant(Variant::new(id("empty")))
+                .add_variant(Variant::new(id("circle")).add_value(u32_ref.clone()))
+                .add_variant(
+                    Variant::new(id("rectangle"))
+                        .add_value(u32_ref.clone())
+                        .add_value(u32_ref),
+                )
+                .with_meta(meta),
+        )
+    });
+    assert_eq!(expected, container::<Shape>());
+
+    assert!(is_valid::<Shape, _>(Shape::Empty));
+    assert!(is_valid::<Shape, _>(Shape::Circle(5)));
+    assert!(is_valid::<Shape, _>(Shape::Rectangle {
+        width: 1,
+        height: 2
+    }));
+}
+
+/// Sorted sets and maps.
+#[derive(LqSchema, Serialize)]
+struct Collections {
+    #[liquesco(sorted, unique, max_len = 3)]
+    sorted: Vec<u8>,
+    #[liquesco(sorted = "descending")]
+    descending: Vec<i64>,
+    set: BTreeSet<u16>,
+    #[liquesco(min_len = 1)]
+    map: BTreeMap<u16, bool>,
+}
+
+#[test]
+fn collections() {
+    let schema = container::<Collections>();
+    let expected = hand_written(|builder| {
+        let u8_ref = builder.add_unwrap("u8", TUInt::try_new(0u8, u8::MAX).unwrap());
+        let sorted = builder.add_unwrap(
+            "collections_0_sorted",
+            TSeq::try_new(u8_ref, 0, 3).unwrap().with_sorted(Sorted {
+                direction: Direction::Ascending,
+                unique: true,
+            }),
+        );
+        let i64_ref = builder.add_unwrap("i64", TSInt::try_new(i64::MIN, i64::MAX).unwrap());
+        let descending = builder.add_unwrap(
+            "collections_0_descending",
+            TSeq::try_new(i64_ref, 0, u32::MAX)
+                .unwrap()
+                .with_sorted(Sorted {
+                    direction: Direction::Descending,
+                    unique: false,
+                }),
+        );
+        let u16_ref = builder.add_unwrap("u16", TUInt::try_new(0u16, u16::MAX).unwrap());
+        let set = builder.add_unwrap(
+            "set_1_u16",
+            TSeq::try_new(u16_ref.clone(), 0, u32::MAX)
+                .unwrap()
+                .with_sorted(Sorted {
+                    direction: Direction::Ascending,
+                    unique: true,
+                }),
+        );
+        let bool_ref =
+            builder.add_unwrap("bool", liquesco_schema::types::boolean::TBool::default());
+        let map = builder.add_unwrap(
+            "collections_0_map",
+            TMap::new(u16_ref, bool_ref)
+                .with_length(U32IneRange::try_new("", 1, u32::MAX).unwrap()),
+        );
+        builder.add_unwrap(
+            "collections",
+            TStruct::default()
+                .add(Field::new(id("sorted"), sorted))
+                .add(Field::new(id("descending"), descending))
+                .add(Field::new(id("set"), set))
+                .add(Field::new(id("map"), map))
+                .with_doc("Sorted sets and maps."),
+        )
+    });
+    assert_eq!(expected, schema);
+
+    let mut map = BTreeMap::new();
+    map.insert(7u16, true);
+    let mut set = BTreeSet::new();
+    set.insert(2u16);
+    set.insert(1u16);
+    let valid = Collections {
+        sorted: vec![1, 2, 3],
+        descending: vec![5, 5, -1],
+        set,
+        map,
+    };
+    assert!(is_valid::<Collections, _>(&valid));
+    assert!(!is_valid::<Collections, _>(Collections {
+        sorted: vec![2, 1],
+        descending: vec![],
+        set: BTreeSet::new(),
+        map: valid.map.clone(),
+    }));
+    assert!(!is_valid::<Collections, _>(Collections {
+        sorted: vec![],
+        descending: vec![],
+        set: BTreeSet::new(),
+        map: BTreeMap::new(),
+    }));
+}
+
+/// A distance in meters.
+#[derive(LqSchema, Serialize)]
+struct Meters(#[liquesco(min = -10, max = 10)] i32);
+
+#[test]
+fn newtype() {
+    let expected = hand_written(|builder| {
+        builder.add_unwrap(
+            "meters",
+            TSInt::try_new(-10, 10)
+                .unwrap()
+                .with_doc("A distance in meters."),
+        )
+    });
+    assert_eq!(expected, container::<Meters>());
+    assert!(is_valid::<Meters, _>(Meters(-10)));
+    assert!(!is_valid::<Meters, _>(Meters(11)));
+}
+
+#[derive(LqSchema, Serialize)]
+struct Measurement {
+    /// The measured value.
+    value: f32,
+    /// Whether the measurement is valid.
+    valid: bool,
+    /// The measured distance (if any).
+    distance: Option<Meters>,
+}
+
+#[test]
+fn documented_fields() {
+    let expected = hand_written(|builder| {
+        let value = builder.add_unwrap(
+            "measurement_0_value",
+            TFloat32::try_new(F32Ext::from(f32::MIN), F32Ext::from(f32::MAX))
+                .unwrap()
+                .with_allow_positive_zero(true)
+                .with_allow_negative_zero(true)
+                .with_allow_nan(true)
+                .with_allow_positive_infinity(true)
+                .with_allow_negative_infinity(true)
+                .with_allow_subnormal(true)
+                .with_doc("The measured value."),
+        );
+        let valid = builder.add_unwrap(
+            "measurement_0_valid",
+            TBool::default().with_doc("Whether the measurement is valid."),
+        );
+        let meters = builder.add_unwrap(
+            "meters",
+            TSInt::try_new(-10, 10)
+                .unwrap()
+                .with_doc("A distance in meters."),
+        );
+        // the doc is applied to the option (the type of the field)
+        let distance = builder.add_unwrap(
+            "measurement_0_distance",
+            TOption::new(meters).with_doc("The measured distance (if any)."),
+        );
+        builder.add_unwrap(
+            "measurement",
+            TStruct::default()
+                .add(Field::new(id("value"), value))
+                .add(Field::new(id("valid"), valid))
+                .add(Field::new(id("distance"), distance)),
+        )
+    });
+    assert_eq!(expected, container::<Measurement>());
+
+    let item = Measurement {
+        value: f32::NAN,
+        valid: true,
+        distance: Some(Meters(2)),
+    };
+    assert!(is_valid::<Measurement, _>(&item));
+}
+
+/// A linked list.
+#[derive(LqSchema, Serialize)]
+struct Node {
+    #[liquesco(max = 100)]
+    value: u8,
+    next: Option<Box<Node>>,
+}
+
+#[derive(LqSchema, Serialize)]
+enum Expression {
+    Number(u8),
+    Negate(Box<Expression>),
+}
+
+#[test]
+fn recursive_types() {
+    let expected = hand_written(|builder| {
+        let value = builder.add_unwrap("node_0_value", TUInt::try_new(0u8, 100u8).unwrap());
+        let node = TypeRef::Identifier(StrIdentifier::try_from(Cow::Borrowed("node")).unwrap());
+        let next = builder.add_unwrap("option_1_node", TOption::new(node));
+        builder.add_unwrap(
+            "node",
+            TStruct::default()
+                .add(Field::new(id("value"), value))
+                .add(Field::new(id("next"), next))
+                .with_doc("A linked list."),
+        )
+    });
+    assert_eq!(expected, container::<Node>());
+
+    let list = Node {
+        value: 1,
+        next: Some(Box::new(Node {
+            value: 2,
+            next: None,
+        })),
+    };
+    assert!(is_valid::<Node, _>(&list));
+    assert!(!is_valid::<Node, _>(&Node {
+        value: 1,
+        next: Some(Box::new(Node {
+            value: 101,
+            next: None,
+        })),
+    }));
+
+    let expression = Expression::Negate(Box::new(Expression::Negate(Box::new(
+        Expression::Number(5),
+    ))));
+    assert!(is_valid::<Expression, _>(&expression));
+}
+
+#[derive(LqSchema, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+struct Foo(#[liquesco(max = 1)] u8);
+
+#[derive(LqSchema, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+struct FooBar(#[liquesco(max = 2)] u8);
+
+#[derive(LqSchema, Serialize)]
+struct Bar(#[liquesco(max = 3)] u8);
+
+#[derive(LqSchema, Serialize)]
+struct BarBaz(#[liquesco(max = 4)] u8);
+
+#[derive(LqSchema, Serialize)]
+struct Baz(#[liquesco(max = 5)] u8);
+
+/// An order.
+#[derive(LqSchema, Serialize)]
+struct Order {
+    /// The identifier of the order.
+    id: u32,
+    number: OrderId,
+    first: BTreeMap<FooBar, Baz>,
+    second: BTreeMap<Foo, BarBaz>,
+    third: BTreeMap<Foo, Bar>,
+}
+
+/// The identifier of an order.
+#[derive(LqSchema, Serialize)]
+struct OrderId(#[liquesco(max = 7)] u8);
+
+#[test]
+fn identifiers_do_not_collide() {
+    let container = container::<Order>();
+    let ids: Vec<String> = container
+        .types()
+        .map(|(_, identifier, _)| identifier.to_string(Format::SnakeCase))
+        .collect();
+    for expected in &[
+        "order",
+        "order_id",
+        "order_0_id",
+        "map_2_foo_bar_1_baz",
+        "map_1_foo_2_bar_baz",
+        "map_1_foo_1_bar",
+    ] {
+        assert!(ids.contains(&expected.to_string()), "{} missing", expected);
+    }
+}
+
+#[derive(LqSchema, Serialize)]
+#[liquesco(name = "duplicate")]
+struct First(#[liquesco(max = 1)] u8);
+
+#[derive(LqSchema, Serialize)]
+#[liquesco(name = "duplicate")]
+struct Second(#[liquesco(max = 2)] u8);
+
+#[derive(LqSchema, Serialize)]
+struct Duplicates {
+    first: First,
+    second: Second,
+}
+
+#[test]
+fn duplicate_identifiers_are_an_error() {
+    let mut builder = DefaultSchemaBuilder::default();
+    let root = Duplicates::build_schema(&mut builder);
+    assert!(builder.finish(root).is_err());
+}
+
+#[test]
+fn too_long_identifiers_are_an_error() {
+    let mut builder = DefaultSchemaBuilder::default();
+    let root =
+        <Option<Option<Option<Option<Option<Option<Option<u8>>>>>>>>::build_schema(&mut builder);
+    assert!(builder.finish(root).is_err());
+}
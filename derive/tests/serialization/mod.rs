@@ -0,0 +1,228 @@
+use liquesco_derive::{LqDeserialize, LqSerialize};
+use liquesco_serialization::core::{DeSerializer, Serializer, ToVecLqWriter};
+use liquesco_serialization::serde::serialize_to_vec;
+use liquesco_serialization::slice_reader::SliceReader;
+use liquesco_serialization::vec_writer::VecWriter;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+fn to_vec<T: Serializer<Item = T>>(item: &T) -> Vec<u8> {
+    let mut writer = VecWriter::default();
+    T::serialize(&mut writer, item).expect("Unable to serialize value");
+    writer.into_vec()
+}
+
+fn from_slice<'a, T: DeSerializer<'a, Item = T>>(data: &'a [u8]) -> T {
+    let mut reader = SliceReader::from(data);
+    let value = T::de_serialize(&mut reader).expect("Unable to de-serialize");
+    reader.finish().expect("Not all data has been consumed");
+    value
+}
+
+/// Serializes the value using the derived serializer and using serde: Both must produce the
+/// same data; de-serializing the data must return the value.
+fn assert_same_as_serde<T>(item: T)
+where
+    T: Serializer<Item = T> + for<'a> DeSerializer<'a, Item = T> + Serialize + PartialEq + Debug,
+{
+    let data = to_vec(&item);
+    assert_eq!(serialize_to_vec(&item).expect("serde failed"), data);
+    assert_eq!(item, from_slice::<T>(&data));
+}
+
+#[derive(LqSerialize, LqDeserialize, Serialize, Deserialize, PartialEq, Debug)]
+struct Person {
+    name: String,
+    age: u8,
+    nick_names: Vec<String>,
+    height: Option<f32>,
+    account: Account,
+}
+
+#[derive(LqSerialize, LqDeserialize, Serialize, Deserialize, PartialEq, Debug)]
+struct Account(u64, i32);
+
+#[derive(LqSerialize, LqDeserialize, Serialize, Deserialize, PartialEq, Debug)]
+struct Meters(f64);
+
+#[derive(LqSerialize, LqDeserialize, Serialize, Deserialize, PartialEq, Debug)]
+struct Unit;
+
+#[derive(LqSerialize, LqDeserialize, Serialize, Deserialize, PartialEq, Debug)]
+enum Shape {
+    Empty,
+    Circle(Meters),
+    Rectangle(Meters, Meters),
+    Polygon { points: Vec<Point>, closed: bool },
+}
+
+#[derive(LqSerialize, LqDeserialize, Serialize, Deserialize, PartialEq, Debug)]
+struct Point {
+    x: i64,
+    y: i64,
+}
+
+#[derive(LqSerialize, LqDeserialize, Serialize, Deserialize, PartialEq, Debug)]
+struct Generic<T> {
+    values: Vec<T>,
+    lookup: BTreeMap<u32, T>,
+}
+
+#[test]
+fn structs() {
+    assert_same_as_serde(Person {
+        name: "Demo".to_string(),
+        age: 42,
+        nick_names: vec!["Nick".to_string(), "Name".to_string()],
+        height: Some(1.8),
+        account: Account(u64::MAX, -12),
+    });
+    assert_same_as_serde(Meters(12.5));
+    assert_same_as_serde(Unit);
+}
+
+#[test]
+fn enums() {
+    assert_same_as_serde(Shape::Empty);
+    assert_same_as_serde(Shape::Circle(Meters(2.0)));
+    assert_same_as_serde(Shape::Rectangle(Meters(2.0), Meters(4.0)));
+    assert_same_as_serde(Shape::Polygon {
+        points: vec![Point { x: 1, y: 2 }, Point { x: -1, y: 5 }],
+        closed: true,
+    });
+}
+
+#[test]
+fn generics() {
+    let mut lookup = BTreeMap::new();
+    lookup.insert(1, "one".to_string());
+    lookup.insert(2, "two".to_string());
+    assert_same_as_serde(Generic {
+        values: vec!["a".to_string(), "b".to_string()],
+        lookup,
+    });
+}
+
+#[derive(LqSerialize, LqDeserialize, PartialEq, Debug)]
+struct Borrowed<'a> {
+    text: &'a str,
+    binary: &'a [u8],
+    more_text: Option<&'a str>,
+}
+
+#[derive(LqSerialize, LqDeserialize, PartialEq, Debug)]
+enum BorrowedEnum<'a, 'b> {
+    Text(&'a str),
+    Binary { binary: &'b [u8] },
+}
+
+#[test]
+fn borrows_from_reader() {
+    let item = Borrowed {
+        text: "Hello",
+        binary: &[1, 2, 3],
+        more_text: Some("World"),
+    };
+    let data = to_vec(&item);
+    let value = from_slice::<Borrowed>(&data);
+    assert_eq!(item, value);
+    // the text points into the data (has not been copied)
+    let data_range = data.as_ptr_range();
+    assert!(data_range.contains(&value.text.as_ptr()));
+    assert!(data_range.contains(&value.binary.as_ptr()));
+
+    let item = BorrowedEnum::Binary { binary: &[4, 5] };
+    assert_eq!(item, from_slice::<BorrowedEnum>(&to_vec(&item)));
+    let item = BorrowedEnum::Text("text");
+    assert_eq!(item, from_slice::<BorrowedEnum>(&to_vec(&item)));
+}
+
+#[derive(LqSerialize, LqDeserialize, PartialEq, Debug)]
+struct PointV2 {
+    x: i64,
+    y: i64,
+    z: Option<i64>,
+    label: String,
+}
+
+#[derive(LqSerialize, LqDeserialize, PartialEq, Debug)]
+enum ShapeV2 {
+    Empty(bool),
+    Circle(Meters, Point),
+    Rectangle(Meters, Meters, String),
+    Polygon {
+        points: Vec<PointV2>,
+        closed: bool,
+        filled: bool,
+    },
+    Triangle,
+}
+
+/// Data written by a newer version of the type (with additional fields) can be read; the
+/// additional fields are skipped.
+#[test]
+fn tolerates_trailing_fields() {
+    let item = vec![
+        PointV2 {
+            x: 1,
+            y: 2,
+            z: Some(3),
+            label: "first".to_string(),
+        },
+        PointV2 {
+            x: 4,
+            y: 5,
+            z: None,
+            label: "second".to_string(),
+        },
+    ];
+    assert_eq!(
+        vec![Point { x: 1, y: 2 }, Point { x: 4, y: 5 }],
+        from_slice::<Vec<Point>>(&to_vec(&item))
+    );
+
+    let items = vec![
+        (ShapeV2::Empty(true), Shape::Empty),
+        (
+            ShapeV2::Circle(Meters(1.0), Point { x: 1, y: 1 }),
+            Shape::Circle(Meters(1.0)),
+        ),
+        (
+            ShapeV2::Rectangle(Meters(1.0), Meters(2.0), "label".to_string()),
+            Shape::Rectangle(Meters(1.0), Meters(2.0)),
+        ),
+    ];
+    for (new, old) in items {
+        assert_eq!(old, from_slice::<Shape>(&to_vec(&new)));
+    }
+}
+
+#[test]
+fn missing_fields_are_an_error() {
+    let data = to_vec(&Point { x: 1, y: 2 });
+    assert!(PointV2::de_serialize(&mut SliceReader::from(&data)).is_err());
+
+    let data = to_vec(&Shape::Circle(Meters(1.0)));
+    assert!(ShapeV2::de_serialize(&mut SliceReader::from(&data)).is_err());
+}
+
+#[test]
+fn unknown_ordinal_is_an_error() {
+    let data = to_vec(&ShapeV2::Triangle);
+    assert!(Shape::de_serialize(&mut SliceReader::from(&data)).is_err());
+}
+
+#[test]
+fn invalid_maps_are_an_error() {
+    let data = serialize_to_vec(vec![(1u32, 1u32), (2, 2)]).unwrap();
+    assert_eq!(2, from_slice::<BTreeMap<u32, u32>>(&data).len());
+
+    let data = serialize_to_vec(vec![(1u32, 1u32), (1, 2)]).unwrap();
+    let err = BTreeMap::<u32, u32>::de_serialize(&mut SliceReader::from(&data)).unwrap_err();
+    assert!(err.msg().contains("duplicate key"), "{}", err.msg());
+
+    let data = serialize_to_vec(vec![(1u32, 1u32, 1u32)]).unwrap();
+    let err = BTreeMap::<u32, u32>::de_serialize(&mut SliceReader::from(&data)).unwrap_err();
+    assert!(err.msg().contains("has 3 items"), "{}", err.msg());
+}
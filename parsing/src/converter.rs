@@ -261,6 +261,11 @@ pub trait Converter {
         maybe_vec.map(|vec| Cow::Owned(vec))
     }
 
+    /// Converts binary data to a string; this is the inverse of `to_binary`.
+    fn binary_to_string(value: &[u8]) -> String {
+        BASE64_NOPAD.encode(value)
+    }
+
     fn require_binary<'a>(value: &'a Value<'a>) -> Result<Cow<'a, [u8]>, LqError> {
         require(Self::to_binary(value), || {
             format!(
@@ -1,10 +1,13 @@
 use crate::converter::Converter;
+use crate::value::SrcPosition;
 use crate::value::TextValue;
+use liquesco_common::error::ErrData;
 use liquesco_common::error::LqError;
 use liquesco_schema::core::Schema;
 use liquesco_schema::core::Type;
 use liquesco_schema::core::TypeRef;
 use liquesco_serialization::core::LqWriter;
+use std::any::Any;
 use std::collections::HashMap;
 
 pub trait Context<'a> {
@@ -70,3 +73,18 @@ impl AnchorInfo {
         &self.anchors
     }
 }
+
+/// Information attached to errors raised when parsing a value (see `LqError::data`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseErrorInfo {
+    /// Position of the value in the source text (if known).
+    pub position: Option<SrcPosition>,
+    /// Identifiers of the types; from the root type to the type of the value.
+    pub type_path: Vec<String>,
+}
+
+impl ErrData for ParseErrorInfo {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,324 @@
+use crate::value::SrcPosition;
+use crate::value::TextValue;
+use crate::value::Value;
+use liquesco_common::error::LqError;
+use std::borrow::Cow;
+use std::cell::Cell;
+
+/// Converts JSON to a text value. Objects are converted to sequences of key-value sequences
+/// (like YAML maps). Integers beyond 64 bit and numbers with a fraction or an exponent are
+/// kept as text (so no precision is lost). Arrays and objects nested deeper than
+/// `max_depth` are rejected.
+pub(crate) fn deserialize(src: &str, max_depth: usize) -> Result<TextValue<'static>, LqError> {
+    let line_starts = std::iter::once(0)
+        .chain(src.match_indices('\n').map(|(index, _)| index + 1))
+        .collect();
+    let mut deserializer = Deserializer {
+        src,
+        offset: 0,
+        line_starts,
+        last_position: Cell::new((0, 0, 1)),
+        depth: 0,
+        max_depth,
+    };
+    let value = deserializer.value()?;
+    deserializer.skip_whitespace();
+    if deserializer.offset < src.len() {
+        deserializer.err("Expecting the end of the JSON document")
+    } else {
+        Result::Ok(value)
+    }
+}
+
+struct Deserializer<'a> {
+    src: &'a str,
+    offset: usize,
+    /// Offsets of the lines (used to compute the positions).
+    line_starts: Vec<usize>,
+    /// The last position computed (offset; line index; column). Positions are mostly
+    /// requested in ascending order; so the column can be computed from the last position
+    /// (instead of counting the characters from the start of the line each time).
+    last_position: Cell<(usize, usize, usize)>,
+    /// Current nesting depth (arrays and objects).
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<'a> Deserializer<'a> {
+    fn value(&mut self) -> Result<TextValue<'static>, LqError> {
+        self.skip_whitespace();
+        let position = self.position(self.offset);
+        let value = match self.peek() {
+            Some('{') => self.nested(Self::object)?,
+            Some('[') => self.nested(Self::array)?,
+            Some('"') => Value::Text(Cow::Owned(self.string()?)),
+            Some('-') | Some('0'..='9') => self.number()?,
+            Some(_) => {
+                if self.keyword("true") {
+                    Value::Bool(true)
+                } else if self.keyword("false") {
+                    Value::Bool(false)
+                } else if self.keyword("null") {
+                    Value::Nothing
+                } else {
+                    return self.err("Expecting a JSON value");
+                }
+            }
+            None => return self.err("Expecting a JSON value; got the end of the document"),
+        };
+        Result::Ok(TextValue::with_position(value, position))
+    }
+
+    /// Reads an array or an object (checks the nesting depth).
+    fn nested<F>(&mut self, read: F) -> Result<Value<'static>, LqError>
+    where
+        F: FnOnce(&mut Self) -> Result<Value<'static>, LqError>,
+    {
+        if self.depth >= self.max_depth {
+            return self.err(&format!(
+                "Arrays and objects are nested too deeply (maximum depth is {})",
+                self.max_depth
+            ));
+        }
+        self.depth += 1;
+        let result = read(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn object(&mut self) -> Result<Value<'static>, LqError> {
+        self.offset += 1;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.consume('}') {
+            return Result::Ok(Value::Seq(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return self.err("Expecting an object key (string)");
+            }
+            // the position of the entry is the position of the key
+            let position = self.position(self.offset);
+            let key =
+                TextValue::with_position(Value::Text(Cow::Owned(self.string()?)), position.clone());
+            self.skip_whitespace();
+            if !self.consume(':') {
+                return self.err("Expecting `:`");
+            }
+            let value = self.value()?;
+            entries.push(TextValue::with_position(
+                Value::Seq(vec![key, value]),
+                position,
+            ));
+
+            self.skip_whitespace();
+            if self.consume('}') {
+                return Result::Ok(Value::Seq(entries));
+            } else if !self.consume(',') {
+                return self.err("Expecting `,` or `}`");
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value<'static>, LqError> {
+        self.offset += 1;
+        let mut elements = Vec::new();
+        self.skip_whitespace();
+        if self.consume(']') {
+            return Result::Ok(Value::Seq(elements));
+        }
+        loop {
+            elements.push(self.value()?);
+            self.skip_whitespace();
+            if self.consume(']') {
+                return Result::Ok(Value::Seq(elements));
+            } else if !self.consume(',') {
+                return self.err("Expecting `,` or `]`");
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value<'static>, LqError> {
+        let start = self.offset;
+        self.consume('-');
+        let int_digits = self.digits();
+        let int_part = &self.src[start..self.offset];
+        if int_digits == 0 || (int_digits > 1 && int_part.trim_start_matches('-').starts_with('0'))
+        {
+            return self.err_at(start, "Invalid number");
+        }
+        let mut integer = true;
+        if self.consume('.') {
+            integer = false;
+            if self.digits() == 0 {
+                return self.err("Invalid number (expecting digits after `.`)");
+            }
+        }
+        if self.consume('e') || self.consume('E') {
+            integer = false;
+            if !self.consume('+') {
+                self.consume('-');
+            }
+            if self.digits() == 0 {
+                return self.err("Invalid number (expecting digits in the exponent)");
+            }
+        }
+
+        let text = &self.src[start..self.offset];
+        Result::Ok(if integer {
+            if let Ok(value) = text.parse::<i64>() {
+                Value::I64(value)
+            } else if let Ok(value) = text.parse::<u64>() {
+                Value::U64(value)
+            } else {
+                Value::Text(Cow::Owned(text.to_string()))
+            }
+        } else {
+            Value::Text(Cow::Owned(text.to_string()))
+        })
+    }
+
+    /// Reads 0-n ascii digits; returns the number of digits.
+    fn digits(&mut self) -> usize {
+        let len = self.src[self.offset..]
+            .bytes()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        self.offset += len;
+        len
+    }
+
+    fn string(&mut self) -> Result<String, LqError> {
+        self.offset += 1;
+        let mut result = String::new();
+        loop {
+            let chr = match self.next() {
+                Some(chr) => chr,
+                None => return self.err("Unterminated string"),
+            };
+            match chr {
+                '"' => return Result::Ok(result),
+                '\\' => {
+                    let escaped = match self.next() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.unicode_escape()?,
+                        _ => return self.err("Invalid escape sequence"),
+                    };
+                    result.push(escaped);
+                }
+                chr if (chr as u32) < 0x20 => {
+                    return self.err("Control characters must be escaped in strings");
+                }
+                chr => result.push(chr),
+            }
+        }
+    }
+
+    /// `\uXXXX` (the `\u` has already been read); also handles surrogate pairs.
+    fn unicode_escape(&mut self) -> Result<char, LqError> {
+        let first = self.hex4()?;
+        let code = if (0xd800..0xdc00).contains(&first) {
+            if !self.src[self.offset..].starts_with("\\u") {
+                return self.err("Expecting a low surrogate (`\\uXXXX`)");
+            }
+            self.offset += 2;
+            let second = self.hex4()?;
+            if !(0xdc00..0xe000).contains(&second) {
+                return self.err("Invalid low surrogate");
+            }
+            0x10000 + ((first - 0xd800) << 10) + (second - 0xdc00)
+        } else {
+            first
+        };
+        match std::char::from_u32(code) {
+            Some(chr) => Result::Ok(chr),
+            None => self.err("Invalid unicode escape sequence"),
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, LqError> {
+        // `from_str_radix` would also accept a sign (`+`)
+        let hex = self
+            .src
+            .get(self.offset..self.offset + 4)
+            .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()));
+        match hex.and_then(|hex| u32::from_str_radix(hex, 16).ok()) {
+            Some(value) => {
+                self.offset += 4;
+                Result::Ok(value)
+            }
+            None => self.err("Expecting 4 hex digits"),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        if self.src[self.offset..].starts_with(keyword) {
+            self.offset += keyword.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let len = self.src[self.offset..]
+            .bytes()
+            .take_while(|byte| [b' ', b'\t', b'\n', b'\r'].contains(byte))
+            .count();
+        self.offset += len;
+    }
+
+    fn consume(&mut self, chr: char) -> bool {
+        if self.peek() == Some(chr) {
+            self.offset += chr.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.offset..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let chr = self.peek()?;
+        self.offset += chr.len_utf8();
+        Some(chr)
+    }
+
+    fn err<T>(&self, msg: &str) -> Result<T, LqError> {
+        self.err_at(self.offset, msg)
+    }
+
+    fn err_at<T>(&self, offset: usize, msg: &str) -> Result<T, LqError> {
+        LqError::err_new(format!(
+            "Invalid JSON at {}: {}.",
+            self.position(offset),
+            msg
+        ))
+    }
+
+    fn position(&self, offset: usize) -> SrcPosition {
+        let line = match self.line_starts.binary_search(&offset) {
+            Result::Ok(line) => line,
+            Result::Err(line) => line - 1,
+        };
+        let (last_offset, last_line, last_column) = self.last_position.get();
+        let column = if last_line == line && last_offset <= offset {
+            last_column + self.src[last_offset..offset].chars().count()
+        } else {
+            self.src[self.line_starts[line]..offset].chars().count() + 1
+        };
+        self.last_position.set((offset, line, column));
+        SrcPosition::new(line + 1, column)
+    }
+}
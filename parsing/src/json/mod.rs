@@ -0,0 +1,48 @@
+use crate::json::deserializer::deserialize;
+use crate::json::serializer::serialize;
+use crate::parser::parse;
+use crate::writer::write;
+use liquesco_common::error::LqError;
+use liquesco_schema::core::Schema;
+
+pub mod deserializer;
+pub mod serializer;
+
+/// Default maximum nesting depth of JSON arrays and objects (see
+/// `parse_from_json_str_with_max_depth`).
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Parses JSON (given a schema) to binary data. The data is validated against the schema.
+/// Arrays and objects can be nested up to `DEFAULT_MAX_DEPTH` levels.
+pub fn parse_from_json_str<S>(schema: &S, src: &str) -> Result<Vec<u8>, LqError>
+where
+    S: Schema,
+{
+    parse_from_json_str_with_max_depth(schema, src, DEFAULT_MAX_DEPTH)
+}
+
+/// Like `parse_from_json_str` but arrays and objects can be nested up to `max_depth`
+/// levels (deeper JSON is rejected with an error instead of overflowing the stack).
+pub fn parse_from_json_str_with_max_depth<S>(
+    schema: &S,
+    src: &str,
+    max_depth: usize,
+) -> Result<Vec<u8>, LqError>
+where
+    S: Schema,
+{
+    let value = deserialize(src, max_depth)?;
+    parse(schema, &value)
+}
+
+/// Writes the data as JSON (the data is validated against the schema). The result can be
+/// parsed again using `parse_from_json_str`.
+pub fn write_to_json_str<S>(schema: &S, data: &[u8]) -> Result<String, LqError>
+where
+    S: Schema,
+{
+    let node = write(schema, data)?;
+    let mut result = String::new();
+    serialize(&mut result, &node);
+    Result::Ok(result)
+}
@@ -0,0 +1,124 @@
+use crate::writer::Node;
+use std::fmt::Write;
+
+/// Number of spaces per indentation level.
+const INDENT: usize = 2;
+
+/// Writes the node as (pretty printed) JSON.
+pub(crate) fn serialize(target: &mut String, node: &Node) {
+    serialize_node(target, node, 0)
+}
+
+fn serialize_node(target: &mut String, node: &Node, level: usize) {
+    // writing to a string never fails
+    match node {
+        Node::Nothing => target.push_str("null"),
+        Node::Bool(value) => write!(target, "{}", value).unwrap(),
+        // JSON numbers have no size limit
+        Node::UInt(value) => write!(target, "{}", value).unwrap(),
+        Node::SInt(value) => write!(target, "{}", value).unwrap(),
+        Node::Float(value) => target.push_str(value),
+        Node::Text(value) => serialize_string(target, value),
+        Node::Seq(seq) => {
+            serialize_container(target, '[', ']', seq, level, |target, node, level| {
+                serialize_node(target, node, level)
+            })
+        }
+        Node::Map(entries) => {
+            if entries.iter().all(|(key, _)| key.is_scalar()) {
+                serialize_container(
+                    target,
+                    '{',
+                    '}',
+                    entries,
+                    level,
+                    |target, (key, value), level| {
+                        serialize_string(target, &key_string(key));
+                        target.push_str(": ");
+                        serialize_node(target, value, level);
+                    },
+                )
+            } else {
+                // complex keys: [[key1, value1], [key2, value2], ...]
+                serialize_container(
+                    target,
+                    '[',
+                    ']',
+                    entries,
+                    level,
+                    |target, (key, value), level| {
+                        serialize_container(
+                            target,
+                            '[',
+                            ']',
+                            &[key, value],
+                            level,
+                            |target, node, level| serialize_node(target, node, level),
+                        )
+                    },
+                )
+            }
+        }
+    }
+}
+
+fn serialize_container<T, F>(
+    target: &mut String,
+    start: char,
+    end: char,
+    items: &[T],
+    level: usize,
+    serialize_item: F,
+) where
+    F: Fn(&mut String, &T, usize),
+{
+    target.push(start);
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            target.push(',');
+        }
+        new_line(target, level + 1);
+        serialize_item(target, item, level + 1);
+    }
+    if !items.is_empty() {
+        new_line(target, level);
+    }
+    target.push(end);
+}
+
+fn new_line(target: &mut String, level: usize) {
+    target.push('\n');
+    for _ in 0..level * INDENT {
+        target.push(' ');
+    }
+}
+
+/// JSON object keys are always strings.
+fn key_string(key: &Node) -> String {
+    match key {
+        Node::Text(value) => value.clone(),
+        Node::Float(value) => value.clone(),
+        Node::Bool(value) => value.to_string(),
+        Node::UInt(value) => value.to_string(),
+        Node::SInt(value) => value.to_string(),
+        Node::Nothing | Node::Seq(_) | Node::Map(_) => {
+            unreachable!("Only scalars can be used as keys")
+        }
+    }
+}
+
+fn serialize_string(target: &mut String, value: &str) {
+    target.push('"');
+    for chr in value.chars() {
+        match chr {
+            '"' => target.push_str("\\\""),
+            '\\' => target.push_str("\\\\"),
+            '\n' => target.push_str("\\n"),
+            '\r' => target.push_str("\\r"),
+            '\t' => target.push_str("\\t"),
+            chr if (chr as u32) < 0x20 => write!(target, "\\u{:04x}", chr as u32).unwrap(),
+            chr => target.push(chr),
+        }
+    }
+    target.push('"');
+}
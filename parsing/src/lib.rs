@@ -6,4 +6,7 @@ pub mod any_parser;
 
 pub mod parser;
 pub mod types;
+pub mod writer;
+
+pub mod json;
 pub mod yaml;
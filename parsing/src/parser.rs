@@ -2,14 +2,43 @@ use crate::any_parser::parse_any;
 use crate::converter::Converter;
 use crate::core::AnchorInfo;
 use crate::core::Context;
+use crate::core::ParseErrorInfo;
+use crate::value::SrcPosition;
 use crate::value::TextValue;
 use liquesco_common::error::LqError;
-use liquesco_schema::core::{Schema, TypeRef};
+use liquesco_schema::core::{Config, Schema, TypeRef};
+use liquesco_schema::identifier::Format;
 use liquesco_serialization::core::ToVecLqWriter;
+use liquesco_serialization::slice_reader::SliceReader;
 use liquesco_serialization::vec_writer::VecWriter;
 use std::convert::TryFrom;
 use std::marker::PhantomData;
 
+/// Parses the value (the root type of the schema) and validates the result (strict).
+pub(crate) fn parse<S>(schema: &S, value: &TextValue) -> Result<Vec<u8>, LqError>
+where
+    S: Schema,
+{
+    let context = ParserContext {
+        schema,
+        parent: None,
+        anchor_info: vec![],
+        r#type: None,
+        position: None,
+        _phantom: &PhantomData,
+    };
+
+    let mut writer = VecWriter::default();
+    context.parse(&mut writer, schema.root(), value)?;
+    let data = writer.into_vec();
+
+    // Now validate the result
+    let mut reader: SliceReader = (&data).into();
+    schema.validate(Config::strict(), &mut reader)?;
+
+    Result::Ok(data)
+}
+
 pub(crate) struct ParserContext<'se, 's, TSchema>
 where
     TSchema: Schema,
@@ -17,9 +46,53 @@ where
     pub(crate) schema: &'se TSchema,
     pub(crate) parent: Option<&'se ParserContext<'se, 's, TSchema>>,
     pub(crate) anchor_info: Vec<AnchorInfo>, // TODO: Try to use smallVec (since in 99% of the cases this is empty or has 1 element)
+    /// The type of the value parsed by this context (none for the root context).
+    pub(crate) r#type: Option<&'se TypeRef>,
+    /// The position of the value parsed by this context.
+    pub(crate) position: Option<&'se SrcPosition>,
     pub(crate) _phantom: &'s PhantomData<()>,
 }
 
+impl<'se, 's, TSchema> ParserContext<'se, 's, TSchema>
+where
+    TSchema: Schema,
+{
+    /// Adds the position and the type path to errors raised by the type parsers (errors from
+    /// nested values already have that information).
+    fn annotate(&self, err: LqError) -> LqError {
+        if err.data::<ParseErrorInfo>().is_some() {
+            return err;
+        }
+        let mut type_path = Vec::new();
+        let mut context = Some(self);
+        while let Some(current) = context {
+            if let Some(r#type) = current.r#type {
+                type_path.push(match self.schema.identifier(r#type) {
+                    Ok(identifier) => (*identifier).to_string(Format::SnakeCase),
+                    Err(_) => format!("{:?}", r#type),
+                });
+            }
+            context = current.parent;
+        }
+        type_path.reverse();
+
+        let position = self.position.cloned();
+        let msg = format!(
+            "{} (at {}; type path: {})",
+            err.msg(),
+            position
+                .as_ref()
+                .map(|position| position.to_string())
+                .unwrap_or_else(|| "unknown position".to_string()),
+            type_path.join(" > ")
+        );
+        err.with_msg(msg).with_data(ParseErrorInfo {
+            position,
+            type_path,
+        })
+    }
+}
+
 pub struct DefaultConverter;
 
 impl Converter for DefaultConverter {}
@@ -49,13 +122,13 @@ where
             schema: self.schema,
             parent: Some(self),
             anchor_info: vec![],
+            r#type: Some(r#type),
+            // values without position (e.g. absent struct fields) use the parent's position
+            position: value.position.as_ref().or(self.position),
             _phantom: &PhantomData,
         };
 
-        // TODO: Add position if position is missing
-        let result = parse_any(&mut context, any_type, value, writer);
-
-        result
+        parse_any(&mut context, any_type, value, writer).map_err(|err| context.annotate(err))
     }
 
     fn parse_to_vec(&self, r#type: &TypeRef, value: &TextValue) -> Result<Vec<u8>, LqError> {
@@ -9,7 +9,7 @@ use liquesco_common::error::LqError;
 use liquesco_schema::core::Schema;
 use liquesco_schema::core::TypeRef;
 use liquesco_schema::types::map::Sorting;
-use std::io::Write;
+use liquesco_serialization::core::LqWriter;
 
 use liquesco_serialization::core::Serializer;
 use liquesco_serialization::slice_reader::SliceReader;
@@ -76,7 +76,7 @@ where
     for entry in map {
         let (key, value) = entry;
         SeqHeader::serialize(writer, &SeqHeader::new(2))?;
-        writer.write_all(key.as_slice())?;
+        writer.write_slice(key.as_slice())?;
         // parse & write value
         context.parse(writer, value_type, value)?;
     }
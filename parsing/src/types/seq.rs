@@ -7,12 +7,12 @@ use liquesco_common::error::LqError;
 use liquesco_schema::core::Schema;
 use liquesco_schema::core::TypeRef;
 use liquesco_schema::types::seq::{Direction, Ordering, TSeq};
+use liquesco_serialization::core::LqWriter;
 use liquesco_serialization::core::Serializer;
 use liquesco_serialization::slice_reader::SliceReader;
 use liquesco_serialization::types::seq::SeqHeader;
 use std::cmp;
 use std::convert::TryFrom;
-use std::io::Write;
 
 pub struct PSeq;
 
@@ -60,7 +60,7 @@ impl<'a> Parser<'a> for PSeq {
             }
             sort(context, r#type.element(), ascending, &mut elements)?;
             for element in &elements {
-                writer.write_all(element)?;
+                writer.write_slice(element)?;
             }
         }
         Ok(())
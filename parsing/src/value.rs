@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::fmt::{Display, Error, Formatter};
 
 pub type Text<'a> = Cow<'a, str>;
 pub type MaybeName<'a> = Option<Text<'a>>;
@@ -16,8 +17,26 @@ impl<'a> AsRef<Value<'a>> for TextValue<'a> {
     }
 }
 
+/// Position in the source text.
 #[derive(Clone, Debug, PartialEq)]
-pub struct SrcPosition(pub usize);
+pub struct SrcPosition {
+    /// Line (starts at 1).
+    pub line: usize,
+    /// Column (starts at 1; counts characters).
+    pub column: usize,
+}
+
+impl SrcPosition {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+impl Display for SrcPosition {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value<'a> {
@@ -47,3 +66,13 @@ impl<'a> From<Value<'a>> for TextValue<'a> {
         }
     }
 }
+
+impl<'a> TextValue<'a> {
+    /// Returns a text value with given position.
+    pub fn with_position(value: Value<'a>, position: SrcPosition) -> Self {
+        Self {
+            value,
+            position: Some(position),
+        }
+    }
+}
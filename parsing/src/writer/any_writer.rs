@@ -0,0 +1,298 @@
+use crate::converter::Converter;
+use crate::converter::IdentifierType;
+use crate::writer::Node;
+use crate::writer::WriterContext;
+use liquesco_common::error::LqError;
+use liquesco_schema::any_type::AnyType;
+use liquesco_schema::core::Schema;
+use liquesco_schema::core::TypeRef;
+use liquesco_schema::types::ascii::TAscii;
+use liquesco_schema::types::decimal::DecimalSerialization;
+use liquesco_schema::types::enumeration::TEnum;
+use liquesco_schema::types::range::Inclusion;
+use liquesco_schema::types::range::TRange;
+use liquesco_schema::types::seq::TSeq;
+use liquesco_schema::types::structure::TStruct;
+use liquesco_serialization::core::DeSerializer;
+use liquesco_serialization::core::LqReader;
+use liquesco_serialization::types::binary::Binary;
+use liquesco_serialization::types::boolean::Bool;
+use liquesco_serialization::types::enumeration::EnumHeader;
+use liquesco_serialization::types::float::Float32;
+use liquesco_serialization::types::float::Float64;
+use liquesco_serialization::types::option::Presence;
+use liquesco_serialization::types::seq::SeqHeader;
+use liquesco_serialization::types::sint::SInt128;
+use liquesco_serialization::types::uint::UInt128;
+use liquesco_serialization::types::uint::UInt32;
+use liquesco_serialization::types::unicode::Unicode;
+use std::convert::TryFrom;
+use std::fmt::Debug;
+
+/// Separators used to write sequences of ascii (like identifiers) as single text (the
+/// first one not contained in the ascii codes is used). See `PIdentifier`.
+const ASCII_SEPARATORS: &[char] = &['_', '-', '.', ' ', ':', '/'];
+
+pub(crate) fn write_any<'r, S, C, R>(
+    context: &mut WriterContext<S, C>,
+    any_type: &AnyType,
+    reader: &mut R,
+) -> Result<Node, LqError>
+where
+    S: Schema,
+    C: Converter,
+    R: LqReader<'r>,
+{
+    Ok(match any_type {
+        AnyType::Option(value) => match Presence::de_serialize(reader)? {
+            Presence::Absent => Node::Nothing,
+            Presence::Present => {
+                let present = context.write(value.r#type(), reader)?;
+                if present == Node::Nothing {
+                    return LqError::err_new(
+                        "An option containing an absent option (present absent) cannot \
+                         be written as text (it would be parsed as absent).",
+                    );
+                }
+                present
+            }
+        },
+        AnyType::UInt(_) => Node::UInt(UInt128::de_serialize(reader)?),
+        AnyType::SInt(_) => Node::SInt(SInt128::de_serialize(reader)?),
+        AnyType::Struct(value) => write_struct(context, value, reader)?,
+        AnyType::Seq(value) => write_seq(context, value, reader)?,
+        AnyType::Binary(_) => Node::Text(C::binary_to_string(Binary::de_serialize(reader)?)),
+        AnyType::Ascii(_) => Node::Text(Unicode::de_serialize(reader)?.to_string()),
+        AnyType::Enum(value) => write_enum(context, value, reader)?,
+        AnyType::Bool(_) => Node::Bool(Bool::de_serialize(reader)?),
+        AnyType::Unicode(_) => Node::Text(Unicode::de_serialize(reader)?.to_string()),
+        AnyType::Float32(_) => {
+            let value = Float32::de_serialize(reader)?;
+            float(value.is_finite(), value)
+        }
+        AnyType::Float64(_) => {
+            let value = Float64::de_serialize(reader)?;
+            float(value.is_finite(), value)
+        }
+        AnyType::Uuid(_) => {
+            let value = liquesco_serialization::types::uuid::Uuid::de_serialize(reader)?;
+            match uuid::Uuid::from_slice(value.as_slice()) {
+                Result::Ok(uuid) => Node::Text(uuid.to_string()),
+                Result::Err(err) => {
+                    return LqError::err_new(format!("Invalid UUID: {:?}", err));
+                }
+            }
+        }
+        AnyType::Range(value) => write_range(context, value, reader)?,
+        AnyType::Map(value) => write_map(
+            context,
+            reader,
+            value.key(),
+            value.value(),
+            value.anchors(),
+            true,
+        )?,
+        AnyType::RootMap(value) => {
+            // the root map is [map, root]; the text is [root, map] (see `PRootMap`).
+            SeqHeader::de_serialize(reader)?;
+            let map = write_map(context, reader, value.key(), value.value(), true, false)?;
+            // the root can reference the keys of the map
+            let root = context.write(value.root(), reader)?;
+            context.pop_anchors()?;
+            Node::Seq(vec![root, map])
+        }
+        AnyType::KeyRef(value) => {
+            let index = UInt32::de_serialize(reader)?;
+            context.anchor(value.level(), index)?.clone()
+        }
+        AnyType::Decimal(_) => Node::Text(DecimalSerialization::de_serialize(reader)?.to_string()),
+    })
+}
+
+fn write_struct<'r, S, C, R>(
+    context: &mut WriterContext<S, C>,
+    r#type: &TStruct,
+    reader: &mut R,
+) -> Result<Node, LqError>
+where
+    S: Schema,
+    C: Converter,
+    R: LqReader<'r>,
+{
+    let header = SeqHeader::de_serialize(reader)?;
+    let fields = r#type.fields();
+    let mut entries = Vec::with_capacity(fields.len());
+    for field in fields {
+        let name = C::identifier_to_string(field.name(), IdentifierType::StructField);
+        entries.push((Node::Text(name), context.write(field.r#type(), reader)?));
+    }
+    // skip the fields not defined in the schema (extension)
+    reader.skip_n_values_u32(header.length() - u32::try_from(fields.len())?)?;
+    Ok(Node::Map(entries))
+}
+
+fn write_enum<'r, S, C, R>(
+    context: &mut WriterContext<S, C>,
+    r#type: &TEnum,
+    reader: &mut R,
+) -> Result<Node, LqError>
+where
+    S: Schema,
+    C: Converter,
+    R: LqReader<'r>,
+{
+    let header = EnumHeader::de_serialize(reader)?;
+    let maybe_variant = usize::try_from(header.ordinal())
+        .ok()
+        .and_then(|ordinal| r#type.variants().get(ordinal));
+    let variant = if let Some(variant) = maybe_variant {
+        variant
+    } else {
+        return LqError::err_new(format!(
+            "There's no enum variant with ordinal {}.",
+            header.ordinal()
+        ));
+    };
+
+    let name = C::identifier_to_string(variant.name(), IdentifierType::EnumIdentifier);
+    let values = variant.values();
+    let result = if values.is_empty() {
+        Node::Text(name)
+    } else {
+        let mut seq = Vec::with_capacity(values.len() + 1);
+        seq.push(Node::Text(name));
+        for value in values {
+            seq.push(context.write(value, reader)?);
+        }
+        Node::Seq(seq)
+    };
+    // skip the values not defined in the schema (extension)
+    reader.skip_n_values_u32(header.number_of_values() - u32::try_from(values.len())?)?;
+    Ok(result)
+}
+
+fn write_seq<'r, S, C, R>(
+    context: &mut WriterContext<S, C>,
+    r#type: &TSeq,
+    reader: &mut R,
+) -> Result<Node, LqError>
+where
+    S: Schema,
+    C: Converter,
+    R: LqReader<'r>,
+{
+    if let AnyType::Ascii(ascii) = context.schema().require_type(r#type.element())? {
+        return write_ascii_seq(ascii, reader);
+    }
+
+    let header = SeqHeader::de_serialize(reader)?;
+    let mut seq = Vec::with_capacity(usize::try_from(header.length())?);
+    for _ in 0..header.length() {
+        seq.push(context.write(r#type.element(), reader)?);
+    }
+    Ok(Node::Seq(seq))
+}
+
+/// Sequences of ascii (like identifiers) are written as single text if possible.
+fn write_ascii_seq<'r, R>(ascii: &TAscii, reader: &mut R) -> Result<Node, LqError>
+where
+    R: LqReader<'r>,
+{
+    let header = SeqHeader::de_serialize(reader)?;
+    let mut segments = Vec::with_capacity(usize::try_from(header.length())?);
+    for _ in 0..header.length() {
+        segments.push(Unicode::de_serialize(reader)?);
+    }
+
+    let separator = ASCII_SEPARATORS
+        .iter()
+        .find(|separator| !ascii.codes().contains_chr(**separator));
+    match separator {
+        // an empty text would be parsed as one empty segment
+        Some(separator) if !segments.is_empty() => {
+            Ok(Node::Text(segments.join(&separator.to_string())))
+        }
+        _ => Ok(Node::Seq(
+            segments
+                .into_iter()
+                .map(|segment| Node::Text(segment.to_string()))
+                .collect(),
+        )),
+    }
+}
+
+fn write_range<'r, S, C, R>(
+    context: &mut WriterContext<S, C>,
+    r#type: &TRange,
+    reader: &mut R,
+) -> Result<Node, LqError>
+where
+    S: Schema,
+    C: Converter,
+    R: LqReader<'r>,
+{
+    SeqHeader::de_serialize(reader)?;
+    let mut seq = vec![
+        context.write(r#type.element(), reader)?,
+        context.write(r#type.element(), reader)?,
+    ];
+    if r#type.inclusion() == Inclusion::Supplied {
+        seq.push(Node::Bool(Bool::de_serialize(reader)?));
+        seq.push(Node::Bool(Bool::de_serialize(reader)?));
+    }
+    Ok(Node::Seq(seq))
+}
+
+/// Writes a map; when `anchors` is true, the keys are pushed as anchors (and popped when
+/// `pop_anchors` is true).
+fn write_map<'r, S, C, R>(
+    context: &mut WriterContext<S, C>,
+    reader: &mut R,
+    key_type: &TypeRef,
+    value_type: &TypeRef,
+    anchors: bool,
+    pop_anchors: bool,
+) -> Result<Node, LqError>
+where
+    S: Schema,
+    C: Converter,
+    R: LqReader<'r>,
+{
+    let header = SeqHeader::de_serialize(reader)?;
+    let number_of_entries = usize::try_from(header.length())?;
+
+    // first the keys: the values can reference the keys (but keys can't reference keys of
+    // the same map).
+    let mut keys_reader = reader.clone();
+    let mut keys = Vec::with_capacity(number_of_entries);
+    for _ in 0..number_of_entries {
+        SeqHeader::de_serialize(&mut keys_reader)?;
+        keys.push(context.write(key_type, &mut keys_reader)?);
+        keys_reader.skip()?;
+    }
+
+    if anchors {
+        context.push_anchors(keys.clone());
+    }
+    let mut entries = Vec::with_capacity(number_of_entries);
+    for key in keys {
+        SeqHeader::de_serialize(reader)?;
+        reader.skip()?;
+        entries.push((key, context.write(value_type, reader)?));
+    }
+    if anchors && pop_anchors {
+        context.pop_anchors()?;
+    }
+    Ok(Node::Map(entries))
+}
+
+/// The debug representation is the shortest one that can be parsed again without loss.
+fn float<T: Debug>(finite: bool, value: T) -> Node {
+    let text = format!("{:?}", value);
+    if finite {
+        Node::Float(text)
+    } else {
+        // "NaN", "inf", "-inf"
+        Node::Text(text)
+    }
+}
@@ -0,0 +1,126 @@
+//! Writes data (binary, given a schema) as text. This is the reverse direction of the
+//! parser: The written text can be parsed again (e.g. using `parse_from_yaml`) and results
+//! in the same data.
+//!
+//! The writer produces a `Node`; the text formats (see `yaml` and `json`) then write that
+//! node.
+
+use crate::converter::Converter;
+use crate::parser::DefaultConverter;
+use crate::writer::any_writer::write_any;
+use liquesco_common::error::LqError;
+use liquesco_schema::core::Config;
+use liquesco_schema::core::Schema;
+use liquesco_schema::core::TypeRef;
+use liquesco_serialization::core::LqReader;
+use liquesco_serialization::slice_reader::SliceReader;
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
+mod any_writer;
+
+/// The text representation of data (independent of the text format). This is what the
+/// parser takes as input (`TextValue`) - with the difference that the node distinguishes
+/// between maps and sequences (so text formats can write maps as maps).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+    Nothing,
+    Bool(bool),
+    UInt(u128),
+    SInt(i128),
+    /// A finite float (as text; non-finite floats are written as `Node::Text`).
+    Float(String),
+    Text(String),
+    Seq(Vec<Node>),
+    /// Structures and maps (the entries are in the order of the data).
+    Map(Vec<(Node, Node)>),
+}
+
+impl Node {
+    /// Returns true if this is a single value (not a container and not nothing).
+    pub fn is_scalar(&self) -> bool {
+        !matches!(self, Node::Nothing | Node::Seq(_) | Node::Map(_))
+    }
+}
+
+/// Writes the given data; the data must be valid (according to the schema). Note: Data
+/// constructed for a later schema version (having more fields in structures or more values
+/// in enum variants) is accepted; those additional fields / values are not written.
+pub fn write<S>(schema: &S, data: &[u8]) -> Result<Node, LqError>
+where
+    S: Schema,
+{
+    let mut reader = SliceReader::from(data);
+    schema.validate(Config::new(), &mut reader)?;
+    reader.finish()?;
+
+    let mut context = WriterContext::<S, DefaultConverter> {
+        schema,
+        anchors: vec![],
+        _phantom: PhantomData,
+    };
+    context.write(schema.root(), &mut SliceReader::from(data))
+}
+
+pub(crate) struct WriterContext<'s, TSchema, TConverter>
+where
+    TSchema: Schema,
+    TConverter: Converter,
+{
+    schema: &'s TSchema,
+    /// The keys of the maps with anchors (innermost is last).
+    anchors: Vec<Vec<Node>>,
+    _phantom: PhantomData<TConverter>,
+}
+
+impl<'s, TSchema, TConverter> WriterContext<'s, TSchema, TConverter>
+where
+    TSchema: Schema,
+    TConverter: Converter,
+{
+    pub(crate) fn schema(&self) -> &'s TSchema {
+        self.schema
+    }
+
+    pub(crate) fn write<'r, R>(&mut self, r#type: &TypeRef, reader: &mut R) -> Result<Node, LqError>
+    where
+        R: LqReader<'r>,
+    {
+        let any_type = self.schema.require_type(r#type)?;
+        write_any(self, any_type, reader)
+    }
+
+    pub(crate) fn push_anchors(&mut self, keys: Vec<Node>) {
+        self.anchors.push(keys);
+    }
+
+    pub(crate) fn pop_anchors(&mut self) -> Result<(), LqError> {
+        if self.anchors.pop().is_some() {
+            Ok(())
+        } else {
+            LqError::err_new("There's a problem with anchor info: The anchors stack is empty.")
+        }
+    }
+
+    /// The key with given index of the map at given level (0 is the innermost map).
+    pub(crate) fn anchor(&self, level: u32, index: u32) -> Result<&Node, LqError> {
+        let maybe_keys = usize::try_from(level)
+            .ok()
+            .filter(|level| *level < self.anchors.len())
+            .map(|level| &self.anchors[self.anchors.len() - level - 1]);
+        let maybe_key = maybe_keys.and_then(|keys| {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| keys.get(index))
+        });
+        if let Some(key) = maybe_key {
+            Ok(key)
+        } else {
+            LqError::err_new(format!(
+                "Key reference (level {}, index {}) not found; there's no such key in the \
+                 outer map.",
+                level, index
+            ))
+        }
+    }
+}
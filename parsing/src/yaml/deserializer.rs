@@ -1,13 +1,157 @@
+use crate::value::SrcPosition;
 use crate::value::TextValue;
 use crate::value::Value;
 use liquesco_common::error::LqError;
 use std::borrow::Cow;
+use std::collections::HashMap;
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::{Marker, TScalarStyle, TokenType};
 use yaml_rust::Yaml;
 
 pub(crate) fn deserialize(yaml: Yaml) -> Result<TextValue<'static>, LqError> {
     deserialize_single(yaml)
 }
 
+/// Deserializes the first YAML document of given source. Unlike `deserialize` the text
+/// values have positions.
+pub(crate) fn deserialize_str(src: &str) -> Result<TextValue<'static>, LqError> {
+    let mut loader = Loader::default();
+    Parser::new(src.chars())
+        .load(&mut loader, false)
+        .map_err(|err| LqError::new(format!("Unable to parse yaml: {}", err)))?;
+    if let Some(err) = loader.error {
+        return Result::Err(err);
+    }
+    loader
+        .document
+        .ok_or_else(|| LqError::new("Unable to parse yaml: The yaml source has no document."))
+}
+
+/// Like yaml_rust's `YamlLoader`; but keeps the positions.
+#[derive(Default)]
+struct Loader {
+    /// Sequences and maps (maps contain key-value sequences) not yet complete.
+    stack: Vec<(Vec<TextValue<'static>>, usize, SrcPosition, bool)>,
+    /// Keys (of maps) waiting for the value.
+    keys: Vec<Option<TextValue<'static>>>,
+    anchors: HashMap<usize, TextValue<'static>>,
+    document: Option<TextValue<'static>>,
+    error: Option<LqError>,
+}
+
+impl Loader {
+    fn insert(&mut self, value: TextValue<'static>, anchor_id: usize) {
+        if anchor_id > 0 {
+            self.anchors.insert(anchor_id, value.clone());
+        }
+        match self.stack.last_mut() {
+            Some((entries, _, _, true)) => {
+                let key = self.keys.last_mut().expect("Map without key");
+                if let Some(key) = key.take() {
+                    // the position of the entry is the position of the key
+                    let position = key.position.clone();
+                    entries.push(TextValue {
+                        value: Value::Seq(vec![key, value]),
+                        position,
+                    });
+                } else {
+                    *key = Some(value);
+                }
+            }
+            Some((elements, _, _, false)) => elements.push(value),
+            None => {
+                if self.document.is_none() {
+                    self.document = Some(value)
+                }
+            }
+        }
+    }
+
+    fn end_container(&mut self) {
+        if let Some((entries, anchor_id, mut position, is_map)) = self.stack.pop() {
+            if is_map {
+                self.keys.pop();
+                // the mapping start marker is at the first `:`; the first key is more useful
+                if let Some(first_position) = entries.first().and_then(|e| e.position.clone()) {
+                    position = first_position;
+                }
+            }
+            self.insert(
+                TextValue::with_position(Value::Seq(entries), position),
+                anchor_id,
+            );
+        }
+    }
+}
+
+impl MarkedEventReceiver for Loader {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        let position = SrcPosition::new(mark.line(), mark.col() + 1);
+        match event {
+            Event::SequenceStart(anchor_id) => {
+                self.stack.push((Vec::new(), anchor_id, position, false));
+            }
+            Event::MappingStart(anchor_id) => {
+                self.stack.push((Vec::new(), anchor_id, position, true));
+                self.keys.push(None);
+            }
+            Event::SequenceEnd | Event::MappingEnd => self.end_container(),
+            Event::Scalar(string, style, anchor_id, tag) => {
+                match deserialize_single(resolve_scalar(string, style, tag)) {
+                    Result::Ok(value) => {
+                        self.insert(TextValue::with_position(value.value, position), anchor_id)
+                    }
+                    Result::Err(err) => {
+                        if self.error.is_none() {
+                            let msg = format!("{} (at {})", err.msg(), position);
+                            self.error = Some(err.with_msg(msg));
+                        }
+                        self.insert(TextValue::with_position(Value::Nothing, position), 0);
+                    }
+                }
+            }
+            Event::Alias(id) => {
+                let value = match self.anchors.get(&id) {
+                    Some(value) => value.value.clone(),
+                    None => Value::Nothing,
+                };
+                self.insert(TextValue::with_position(value, position), 0);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Same as in yaml_rust's `YamlLoader`.
+fn resolve_scalar(string: String, style: TScalarStyle, tag: Option<TokenType>) -> Yaml {
+    if style != TScalarStyle::Plain {
+        return Yaml::String(string);
+    }
+    match tag {
+        Some(TokenType::Tag(ref handle, ref suffix)) if handle == "!!" => match suffix.as_ref() {
+            "bool" => string
+                .parse::<bool>()
+                .map(Yaml::Boolean)
+                .unwrap_or(Yaml::BadValue),
+            "int" => string
+                .parse::<i64>()
+                .map(Yaml::Integer)
+                .unwrap_or(Yaml::BadValue),
+            "float" => match string.parse::<f64>() {
+                Result::Ok(_) => Yaml::Real(string),
+                Result::Err(_) => Yaml::BadValue,
+            },
+            "null" => match string.as_ref() {
+                "~" | "null" => Yaml::Null,
+                _ => Yaml::BadValue,
+            },
+            _ => Yaml::String(string),
+        },
+        Some(TokenType::Tag(_, _)) => Yaml::String(string),
+        _ => Yaml::from_str(&string),
+    }
+}
+
 fn deserialize_single(yaml: Yaml) -> Result<TextValue<'static>, LqError> {
     Result::Ok(
         match yaml {
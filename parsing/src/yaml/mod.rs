@@ -1,23 +1,21 @@
-use crate::core::Context;
-use crate::parser::ParserContext;
+use crate::parser::parse;
+use crate::writer::write;
 use crate::yaml::deserializer::deserialize;
+use crate::yaml::deserializer::deserialize_str;
+use crate::yaml::serializer::serialize;
 use liquesco_common::error::LqError;
-use liquesco_schema::core::{Config, Schema};
-use liquesco_serialization::core::ToVecLqWriter;
-use liquesco_serialization::slice_reader::SliceReader;
-use liquesco_serialization::vec_writer::VecWriter;
-use std::marker::PhantomData;
-use yaml_rust::{Yaml, YamlLoader};
+use liquesco_schema::core::Schema;
+use yaml_rust::{Yaml, YamlEmitter};
 
 pub mod deserializer;
+pub mod serializer;
 
 pub fn parse_from_yaml_str<'s, S>(schema: &S, src: &str) -> Result<Vec<u8>, LqError>
 where
     S: Schema,
 {
-    let mut docs = YamlLoader::load_from_str(src).unwrap(); // TODO: "Unwrap"
-    let yaml = docs.remove(0);
-    parse_from_yaml(schema, yaml)
+    let value = deserialize_str(src)?;
+    parse(schema, &value)
 }
 
 pub fn parse_from_yaml<'s, S>(schema: &S, yaml: Yaml) -> Result<Vec<u8>, LqError>
@@ -25,21 +23,26 @@ where
     S: Schema,
 {
     let value = deserialize(yaml)?;
+    parse(schema, &value)
+}
 
-    let context = ParserContext {
-        schema,
-        parent: None,
-        anchor_info: vec![],
-        _phantom: &PhantomData,
-    };
-
-    let mut writer = VecWriter::default();
-    context.parse(&mut writer, schema.root(), &value)?;
-    let data = writer.into_vec();
-
-    // Now validate the result
-    let mut reader: SliceReader = (&data).into();
-    schema.validate(Config::strict(), &mut reader)?;
+/// Writes the data as YAML (the data is validated against the schema). The result can be
+/// parsed again using `parse_from_yaml_str`.
+pub fn write_to_yaml_str<S>(schema: &S, data: &[u8]) -> Result<String, LqError>
+where
+    S: Schema,
+{
+    let yaml = write_to_yaml(schema, data)?;
+    let mut result = String::new();
+    YamlEmitter::new(&mut result)
+        .dump(&yaml)
+        .map_err(|err| LqError::new(format!("Unable to write yaml: {:?}", err)))?;
+    Result::Ok(result)
+}
 
-    Result::Ok(data)
+pub fn write_to_yaml<S>(schema: &S, data: &[u8]) -> Result<Yaml, LqError>
+where
+    S: Schema,
+{
+    Result::Ok(serialize(&write(schema, data)?))
 }
@@ -0,0 +1,38 @@
+use crate::writer::Node;
+use std::convert::TryFrom;
+use yaml_rust::Yaml;
+
+pub(crate) fn serialize(node: &Node) -> Yaml {
+    match node {
+        Node::Nothing => Yaml::Null,
+        Node::Bool(value) => Yaml::Boolean(*value),
+        // yaml integers are 64 bit; larger numbers are written as text
+        Node::UInt(value) => i64::try_from(*value)
+            .map(Yaml::Integer)
+            .unwrap_or_else(|_| Yaml::String(value.to_string())),
+        Node::SInt(value) => i64::try_from(*value)
+            .map(Yaml::Integer)
+            .unwrap_or_else(|_| Yaml::String(value.to_string())),
+        Node::Float(value) => Yaml::Real(value.clone()),
+        Node::Text(value) => Yaml::String(value.clone()),
+        Node::Seq(seq) => Yaml::Array(seq.iter().map(serialize).collect()),
+        Node::Map(entries) => {
+            if entries.iter().all(|(key, _)| key.is_scalar()) {
+                Yaml::Hash(
+                    entries
+                        .iter()
+                        .map(|(key, value)| (serialize(key), serialize(value)))
+                        .collect(),
+                )
+            } else {
+                // complex keys: [[key1, value1], [key2, value2], ...]
+                Yaml::Array(
+                    entries
+                        .iter()
+                        .map(|(key, value)| Yaml::Array(vec![serialize(key), serialize(value)]))
+                        .collect(),
+                )
+            }
+        }
+    }
+}
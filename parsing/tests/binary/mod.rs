@@ -7,7 +7,7 @@ use liquesco_schema::type_container::DefaultTypeContainer;
 use liquesco_schema::types::binary::TBinary;
 use liquesco_schema::types::seq::TSeq;
 
-fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+pub(crate) fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
     let mut builder = builder();
     let binary = TBinary::try_new(0, 20).unwrap();
     let binary = builder.add_unwrap("binary", AnyType::Binary(binary));
@@ -8,7 +8,7 @@ use liquesco_schema::type_container::DefaultTypeContainer;
 use liquesco_schema::types::decimal::TDecimal;
 use liquesco_schema::types::seq::TSeq;
 
-fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+pub(crate) fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
     let mut builder = builder();
 
     let decimal = builder.add_unwrap(
@@ -11,7 +11,7 @@ use liquesco_schema::types::seq::TSeq;
 use liquesco_schema::types::sint::TSInt;
 
 /// Creates an enum
-fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+pub(crate) fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
     let mut builder = builder();
 
     let variant1 = builder.add_unwrap(
@@ -10,7 +10,7 @@ use liquesco_schema::types::seq::TSeq;
 use liquesco_schema::types::structure::Field;
 use liquesco_schema::types::structure::TStruct;
 
-fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+pub(crate) fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
     let mut builder = builder();
 
     let field1 = builder.add_unwrap(
@@ -9,7 +9,7 @@ use liquesco_schema::types::ascii::CodeRange;
 use liquesco_schema::types::ascii::TAscii;
 use liquesco_schema::types::seq::TSeq;
 
-fn create_identifier_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+pub(crate) fn create_identifier_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
     let mut builder = builder();
     let ascii = builder.add_unwrap(
         "ascii",
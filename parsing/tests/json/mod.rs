@@ -0,0 +1,149 @@
+use crate::utils::{assert_err, builder, finish, id};
+use liquesco_common::decimal::Decimal;
+use liquesco_parsing::json::parse_from_json_str;
+use liquesco_parsing::json::parse_from_json_str_with_max_depth;
+use liquesco_parsing::json::write_to_json_str;
+use liquesco_parsing::yaml::parse_from_yaml_str;
+use liquesco_schema::any_type::AnyType;
+use liquesco_schema::schema::{schema_schema, DefaultSchema};
+use liquesco_schema::schema_builder::{DefaultSchemaBuilder, SchemaBuilder};
+use liquesco_schema::type_container::DefaultTypeContainer;
+use liquesco_schema::types::decimal::TDecimal;
+use liquesco_schema::types::sint::TSInt;
+use liquesco_schema::types::structure::{Field, TStruct};
+use liquesco_schema::types::uint::TUInt;
+use liquesco_schema::types::unicode::{LengthType, TUnicode};
+
+const STRUCTURE_JSON: &str = r#"[
+  {"first_name": "Albert", "last_name": "Einstein", "year_born": 1879},
+  {
+    "first_name": "Max",
+    "last_name": "Powers",
+    "year_born": 1976,
+    "email": "max_pwers@gmail.com"
+  },
+  {"first_name": "Email", "last_name": "Can also be null", "year_born": 1976, "email": null},
+  {"first_name": "J\u00f8hn", "last_name": "\u00c6egson", "year_born": 1964, "email": "j.a@gmail.com"}
+]"#;
+
+#[test]
+fn same_as_yaml() {
+    let schema = crate::structure::create_schema();
+    assert_eq!(
+        parse_from_yaml_str(&schema, include_str!("../structure/working1.yaml")).unwrap(),
+        parse_from_json_str(&schema, STRUCTURE_JSON).unwrap()
+    );
+}
+
+fn create_numbers_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+    let mut builder = builder();
+    let uint = builder.add_unwrap("uint", TUInt::try_new(0u128, u128::MAX).unwrap());
+    let sint = builder.add_unwrap("sint", TSInt::try_new(i128::MIN, i128::MAX).unwrap());
+    let decimal = builder.add_unwrap(
+        "decimal",
+        TDecimal::try_new(
+            Decimal::from_parts(i128::MIN + 1, i8::MAX),
+            Decimal::from_parts(i128::MAX - 1, i8::MAX),
+        )
+        .unwrap(),
+    );
+    let text = builder.add_unwrap(
+        "text",
+        AnyType::Unicode(TUnicode::try_new(0, 100, LengthType::Byte).unwrap()),
+    );
+    let root = builder.add_unwrap(
+        "root",
+        TStruct::default()
+            .add(Field::new(id("uint"), uint))
+            .add(Field::new(id("sint"), sint))
+            .add(Field::new(id("decimal"), decimal))
+            .add(Field::new(id("text"), text)),
+    );
+    finish(builder, root)
+}
+
+#[test]
+fn numbers_beyond_64_bit_and_exact_decimals() {
+    let schema = create_numbers_schema();
+    let data = parse_from_json_str(
+        &schema,
+        r#"{
+            "uint": 340282366920938463463374607431768211455,
+            "sint": -170141183460469231731687303715884105728,
+            "decimal": 0.1000000000000000000001,
+            "text": "quote \" slash \/ tab \t emoji 😀"
+        }"#,
+    )
+    .unwrap();
+
+    let expected = parse_from_yaml_str(
+        &schema,
+        r#"
+uint: "340282366920938463463374607431768211455"
+sint: "-170141183460469231731687303715884105728"
+decimal: "0.1000000000000000000001"
+text: "quote \" slash / tab \t emoji 😀""#,
+    )
+    .unwrap();
+    assert_eq!(expected, data);
+
+    // the decimal has not been converted to a float
+    let rounded =
+        parse_from_yaml_str(&schema, r#"{uint: 0, sint: 0, decimal: "0.1", text: ""}"#).unwrap();
+    let not_rounded = parse_from_json_str(
+        &schema,
+        r#"{"uint": 0, "sint": 0, "decimal": 0.1000000000000000000001, "text": ""}"#,
+    )
+    .unwrap();
+    assert_ne!(rounded, not_rounded);
+}
+
+#[test]
+fn round_trip_with_writer() {
+    let lq_schema: DefaultSchema<'static, DefaultTypeContainer<'static>> =
+        schema_schema(DefaultSchemaBuilder::default())
+            .unwrap()
+            .into();
+    let data =
+        parse_from_yaml_str(&lq_schema, include_str!("../schemas/simple_schema.yaml")).unwrap();
+    let json = write_to_json_str(&lq_schema, &data).unwrap();
+    assert_eq!(data, parse_from_json_str(&lq_schema, &json).unwrap());
+
+    let schema = crate::key_ref::create_schema();
+    let data = parse_from_yaml_str(&schema, include_str!("../key_ref/ok1.yaml")).unwrap();
+    let json = write_to_json_str(&schema, &data).unwrap();
+    assert_eq!(data, parse_from_json_str(&schema, &json).unwrap());
+}
+
+#[test]
+fn invalid_json() {
+    let schema = crate::structure::create_schema();
+    let err = |json: &str| format!("{:?}", parse_from_json_str(&schema, json).unwrap_err());
+    assert!(err("[\n  {\"first_name\" \"Albert\"}]").contains("line 2, column 17"));
+    assert!(err("[{}, ]").contains("line 1, column 6"));
+    assert!(err("[01]").contains("line 1, column 2"));
+    assert!(err("[] []").contains("line 1, column 4"));
+    assert!(err("[\"unterminated").contains("Unterminated string"));
+    assert!(err("[{\"first_name\": \"\\u+041\"}]").contains("Expecting 4 hex digits"));
+    assert_err(parse_from_json_str(
+        &schema,
+        "[{\"first_name\": \"\\ud83d\"}]",
+    ));
+}
+
+#[test]
+fn max_depth() {
+    let schema = crate::structure::create_schema();
+    let json = r#"[{"first_name": "Albert", "last_name": "Einstein", "year_born": 1879}]"#;
+    parse_from_json_str_with_max_depth(&schema, json, 2).unwrap();
+    let err = parse_from_json_str_with_max_depth(&schema, json, 1).unwrap_err();
+    assert!(
+        err.msg().contains("line 1, column 2") && err.msg().contains("nested too deeply"),
+        "{}",
+        err.msg()
+    );
+    // fails instead of overflowing the stack
+    let deep = "[".repeat(100_000);
+    let err = parse_from_json_str(&schema, &deep).unwrap_err();
+    assert!(err.msg().contains("nested too deeply"), "{}", err.msg());
+}
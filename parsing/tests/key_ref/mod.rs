@@ -14,7 +14,7 @@ use liquesco_schema::types::uint::TUInt;
 use std::convert::TryFrom;
 
 /// We have two nested maps: A root map (int -> inner_map) and an inner map: (identifier -> value).
-fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+pub(crate) fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
     let mut builder = builder();
 
     let outer_ref = builder.add_unwrap("outer_ref", TKeyRef::default().with_level(1));
@@ -11,7 +11,7 @@ use liquesco_schema::types::unicode::LengthType;
 use liquesco_schema::types::unicode::TUnicode;
 
 /// It's a map of "identifier" -> "string"
-fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+pub(crate) fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
     let mut builder = builder();
     let key = Identifier::build_schema(&mut builder);
     let value = builder.add_unwrap(
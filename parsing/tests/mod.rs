@@ -6,9 +6,12 @@ pub mod decimal;
 pub mod enumeration;
 pub mod float_num;
 pub mod identifier;
+pub mod json;
 pub mod key_ref;
 pub mod map;
+pub mod positions;
 pub mod range;
 pub mod structure;
+pub mod writer;
 
 pub mod schemas;
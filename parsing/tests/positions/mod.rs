@@ -0,0 +1,77 @@
+use crate::utils::{builder, finish};
+use liquesco_parsing::core::ParseErrorInfo;
+use liquesco_parsing::json::parse_from_json_str;
+use liquesco_parsing::value::SrcPosition;
+use liquesco_parsing::yaml::parse_from_yaml_str;
+use liquesco_schema::schema_builder::SchemaBuilder;
+use liquesco_schema::types::seq::TSeq;
+use liquesco_schema::types::uint::TUInt;
+
+const INVALID_YEAR_YAML: &str = "- first_name: Albert
+  last_name: Einstein
+  year_born: 1879
+
+- first_name: Max
+  last_name: Powers
+  year_born: unknown
+";
+
+const INVALID_YEAR_JSON: &str = r#"[
+  {"first_name": "Albert", "last_name": "Einstein", "year_born": 1879},
+  {"first_name": "Max", "last_name": "Powers", "year_born": "unknown"}
+]"#;
+
+#[test]
+fn yaml_error_has_position() {
+    let schema = crate::structure::create_schema();
+    let err = parse_from_yaml_str(&schema, INVALID_YEAR_YAML).unwrap_err();
+    let info = err.data::<ParseErrorInfo>().unwrap();
+    assert_eq!(Some(SrcPosition::new(7, 14)), info.position);
+    assert_eq!(vec!["root", "structure", "year_born"], info.type_path);
+    assert!(err.msg().contains("line 7, column 14"));
+    assert!(err.msg().contains("root > structure > year_born"));
+}
+
+#[test]
+fn json_error_has_position() {
+    let schema = crate::structure::create_schema();
+    let err = parse_from_json_str(&schema, INVALID_YEAR_JSON).unwrap_err();
+    let info = err.data::<ParseErrorInfo>().unwrap();
+    assert_eq!(Some(SrcPosition::new(3, 61)), info.position);
+    assert_eq!(vec!["root", "structure", "year_born"], info.type_path);
+}
+
+#[test]
+fn missing_value_uses_parent_position() {
+    let schema = crate::structure::create_schema();
+    // last name is missing: there's no value; so the position of the structure is used
+    let err =
+        parse_from_yaml_str(&schema, "- first_name: Albert\n  year_born: 1879\n").unwrap_err();
+    let info = err.data::<ParseErrorInfo>().unwrap();
+    assert_eq!(Some(SrcPosition::new(1, 3)), info.position);
+    assert_eq!(vec!["root", "structure", "last_name"], info.type_path);
+}
+
+#[test]
+fn yaml_syntax_error_has_position() {
+    let schema = crate::structure::create_schema();
+    let err = parse_from_yaml_str(&schema, "- first_name: [Albert\n").unwrap_err();
+    assert!(err.msg().contains("line"));
+}
+
+#[test]
+fn single_line_json_is_linear() {
+    let mut builder = builder();
+    let number = builder.add_unwrap("number", TUInt::try_new(0u32, 100u32).unwrap());
+    let root = builder.add_unwrap("root", TSeq::try_new(number, 0, 1_000_000).unwrap());
+    let schema = finish(builder, root);
+
+    // minified JSON: one line with lots of values (the last value is a text); computing
+    // the position of every value must not count the characters from the start of the line
+    let mut json = "[".to_string();
+    json.push_str(&"1,".repeat(500_000));
+    json.push_str("\"x\"]");
+    let err = parse_from_json_str(&schema, &json).unwrap_err();
+    let info = err.data::<ParseErrorInfo>().unwrap();
+    assert_eq!(Some(SrcPosition::new(1, json.len() - 3)), info.position);
+}
@@ -8,7 +8,8 @@ use liquesco_schema::types::range::{Inclusion, TRange};
 use liquesco_schema::types::seq::TSeq;
 use liquesco_schema::types::uint::TUInt;
 
-fn create_schema_given_inclusion() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+pub(crate) fn create_schema_given_inclusion(
+) -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
     let mut builder = builder();
 
     let range_element = builder.add_unwrap(
@@ -22,7 +23,8 @@ fn create_schema_given_inclusion() -> DefaultSchema<'static, DefaultTypeContaine
     builder.finish(root).unwrap().into()
 }
 
-fn create_schema_supplied_inclusion() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+pub(crate) fn create_schema_supplied_inclusion(
+) -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
     let mut builder = builder();
 
     let range_element = builder.add_unwrap(
@@ -13,7 +13,7 @@ use liquesco_schema::types::uint::TUInt;
 use liquesco_schema::types::unicode::LengthType;
 use liquesco_schema::types::unicode::TUnicode;
 
-fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+pub(crate) fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
     let mut builder = builder();
 
     // a structure: a person
@@ -0,0 +1,172 @@
+use crate::utils::{builder, finish, id};
+use liquesco_parsing::json::write_to_json_str;
+use liquesco_parsing::yaml::parse_from_yaml_str;
+use liquesco_parsing::yaml::write_to_yaml_str;
+use liquesco_schema::any_type::AnyType;
+use liquesco_schema::core::Schema;
+use liquesco_schema::schema::{schema_schema, DefaultSchema};
+use liquesco_schema::schema_builder::{DefaultSchemaBuilder, SchemaBuilder};
+use liquesco_schema::type_container::DefaultTypeContainer;
+use liquesco_schema::types::binary::TBinary;
+use liquesco_schema::types::enumeration::{TEnum, Variant};
+use liquesco_schema::types::float::TFloat32;
+use liquesco_schema::types::option::TOption;
+use liquesco_schema::types::structure::{Field, TStruct};
+use liquesco_schema::types::uint::TUInt;
+use liquesco_schema::types::unicode::{LengthType, TUnicode};
+use liquesco_serialization::serde::de_serialize_from_slice;
+
+/// Parses the yaml, writes the data as yaml and parses that again: Must result in the
+/// same data.
+fn assert_round_trip<S: Schema>(schema: &S, yaml: &str) {
+    let data = parse_from_yaml_str(schema, yaml).unwrap();
+    let written = write_to_yaml_str(schema, &data).unwrap();
+    let parsed_again = parse_from_yaml_str(schema, &written).unwrap();
+    assert_eq!(data, parsed_again, "Written yaml: {}", written);
+}
+
+#[test]
+fn round_trip_types() {
+    assert_round_trip(
+        &crate::structure::create_schema(),
+        include_str!("../structure/working1.yaml"),
+    );
+    assert_round_trip(
+        &crate::enumeration::create_schema(),
+        include_str!("../enumeration/working1.yaml"),
+    );
+    assert_round_trip(
+        &crate::float_num::create_schema(),
+        include_str!("../float_num/working1.yaml"),
+    );
+    assert_round_trip(
+        &crate::binary::create_schema(),
+        include_str!("../binary/working1.yaml"),
+    );
+    assert_round_trip(
+        &crate::decimal::create_schema(),
+        include_str!("../decimal/working1.yaml"),
+    );
+    assert_round_trip(
+        &crate::identifier::create_identifier_schema(),
+        include_str!("../identifier/ok_identifier.yaml"),
+    );
+    assert_round_trip(
+        &crate::map::create_schema(),
+        include_str!("../map/ok_simple.yaml"),
+    );
+    assert_round_trip(
+        &crate::range::create_schema_given_inclusion(),
+        include_str!("../range/range_ok.yaml"),
+    );
+    assert_round_trip(
+        &crate::range::create_schema_supplied_inclusion(),
+        include_str!("../range/range_supplied_inclusion_ok.yaml"),
+    );
+}
+
+#[test]
+fn round_trip_key_refs() {
+    assert_round_trip(
+        &crate::key_ref::create_schema(),
+        include_str!("../key_ref/ok1.yaml"),
+    );
+}
+
+#[test]
+fn round_trip_schema_and_data() {
+    let lq_schema: DefaultSchema<'static, DefaultTypeContainer<'static>> =
+        schema_schema(DefaultSchemaBuilder::default())
+            .unwrap()
+            .into();
+    let schema_yaml = include_str!("../schemas/simple_schema.yaml");
+    assert_round_trip(&lq_schema, schema_yaml);
+
+    let schema_data = parse_from_yaml_str(&lq_schema, schema_yaml).unwrap();
+    let own_schema: DefaultSchema<'static, DefaultTypeContainer<'static>> =
+        de_serialize_from_slice::<DefaultTypeContainer>(&schema_data)
+            .unwrap()
+            .into();
+    assert_round_trip(
+        &own_schema,
+        include_str!("../schemas/simple_schema_data.yaml"),
+    );
+}
+
+fn create_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+    let mut builder = builder();
+    let name = builder.add_unwrap(
+        "name",
+        AnyType::Unicode(TUnicode::try_new(0, 100, LengthType::Byte).unwrap()),
+    );
+    let number = builder.add_unwrap("number", TUInt::try_new(0u64, u64::MAX).unwrap());
+    let maybe_number = builder.add_unwrap("maybe_number", TOption::new(number.clone()));
+    let payload = builder.add_unwrap("payload", TBinary::try_new(0, 100).unwrap());
+    let float = builder.add_unwrap(
+        "float",
+        TFloat32::try_new(f32::MIN.into(), f32::MAX.into()).unwrap(),
+    );
+    let shape = builder.add_unwrap(
+        "shape",
+        TEnum::default()
+            .add_variant(Variant::new(id("point")))
+            .add_variant(Variant::new(id("circle")).add_value(float.clone())),
+    );
+    let root = builder.add_unwrap(
+        "root",
+        TStruct::default()
+            .add(Field::new(id("name"), name))
+            .add(Field::new(id("big_number"), number))
+            .add(Field::new(id("maybe_number"), maybe_number))
+            .add(Field::new(id("payload"), payload))
+            .add(Field::new(id("float"), float))
+            .add(Field::new(id("first_shape"), shape.clone()))
+            .add(Field::new(id("second_shape"), shape)),
+    );
+    finish(builder, root)
+}
+
+const YAML: &str = r#"---
+name: "true"
+big_number: "18446744073709551615"
+maybe_number: ~
+payload: aGVsbG8
+float: 0.1
+first_shape: point
+second_shape:
+  - circle
+  - 2.5"#;
+
+#[test]
+fn write_yaml() {
+    let schema = create_schema();
+    let data = parse_from_yaml_str(&schema, YAML).unwrap();
+    assert_eq!(YAML, write_to_yaml_str(&schema, &data).unwrap());
+}
+
+#[test]
+fn write_json() {
+    let schema = create_schema();
+    let data = parse_from_yaml_str(&schema, YAML).unwrap();
+    assert_eq!(
+        r#"{
+  "name": "true",
+  "big_number": 18446744073709551615,
+  "maybe_number": null,
+  "payload": "aGVsbG8",
+  "float": 0.1,
+  "first_shape": "point",
+  "second_shape": [
+    "circle",
+    2.5
+  ]
+}"#,
+        write_to_json_str(&schema, &data).unwrap()
+    );
+}
+
+#[test]
+fn write_invalid_data() {
+    let schema = create_schema();
+    assert!(write_to_yaml_str(&schema, &[1, 2, 3]).is_err());
+}
@@ -0,0 +1,876 @@
+use crate::any_type::AnyType;
+use crate::core::{Config, TypeContainer, TypeRef};
+use crate::identifier::{Format, Identifier};
+use crate::types::ascii::CodeRange;
+use crate::types::float::TFloat;
+use crate::types::map::Sorting;
+use crate::types::seq::{Direction, Ordering};
+use crate::types::tint::TInt;
+use crate::types::unicode::LengthType;
+use liquesco_common::error::LqError;
+use liquesco_common::ine_range::IneRange;
+use liquesco_common::range::Range;
+use std::cmp::min;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+
+/// Checks whether the old and the new version of a schema are compatible.
+///
+/// Starts at the root types and compares every pair of types (old version; new version)
+/// reachable from there. Types are compared by position (that's how the data is
+/// serialized): Fields of structures and variants of enums are matched by index, names are
+/// only used to detect inserted, removed or moved fields and variants. Those are always
+/// reported as incompatible in both directions: even if the data happens to validate, values
+/// would end up in the wrong fields (or variants).
+///
+/// The result depends on the configuration used for validation (see `Config::no_extension`):
+/// Appending a field to a structure is forward compatible unless extensions are disallowed.
+pub fn check<Old, New>(
+    old: &Old,
+    new: &New,
+    config: &Config,
+) -> Result<CompatibilityReport, LqError>
+where
+    Old: TypeContainer,
+    New: TypeContainer,
+{
+    let mut checker = Checker {
+        old,
+        new,
+        no_extension: config.no_extension(),
+        pairs: Vec::new(),
+        indexes: HashMap::new(),
+        incompatibilities: Vec::new(),
+        children: Vec::new(),
+    };
+    checker.child(old.root(), new.root());
+    checker.children.clear();
+
+    let mut checked = Vec::new();
+    while checked.len() < checker.pairs.len() {
+        let (old_ref, new_ref) = checker.pairs[checked.len()].clone();
+        checker.check_pair(&old_ref, &new_ref)?;
+        checked.push((
+            old_ref,
+            new_ref,
+            std::mem::take(&mut checker.incompatibilities),
+            std::mem::take(&mut checker.children),
+        ));
+    }
+
+    // the compatibility of a type also depends on the types it references
+    let local: Vec<(bool, bool)> = checked
+        .iter()
+        .map(|(_, _, incompatibilities, _)| {
+            (
+                incompatibilities.iter().any(|item| item.breaks_backward),
+                incompatibilities.iter().any(|item| item.breaks_forward),
+            )
+        })
+        .collect();
+    let mut types = Vec::with_capacity(checked.len());
+    for (index, (old_ref, new_ref, incompatibilities, _)) in checked.iter().enumerate() {
+        let mut breaks_backward = false;
+        let mut breaks_forward = false;
+        let mut visited = vec![false; checked.len()];
+        let mut stack = vec![index];
+        while let Some(current) = stack.pop() {
+            if visited[current] {
+                continue;
+            }
+            visited[current] = true;
+            breaks_backward |= local[current].0;
+            breaks_forward |= local[current].1;
+            stack.extend(checked[current].3.iter().cloned());
+        }
+        types.push(TypeCompatibility {
+            old_type: old_ref.clone(),
+            new_type: new_ref.clone(),
+            old_identifier: old.identifier(old_ref)?.into_owned().into_owned(),
+            new_identifier: new.identifier(new_ref)?.into_owned().into_owned(),
+            compatibility: Compatibility::from_flags(!breaks_backward, !breaks_forward),
+            incompatibilities: incompatibilities.clone(),
+        });
+    }
+    Result::Ok(CompatibilityReport { types })
+}
+
+/// Result of `check`: Contains an entry for every pair of types (old version; new version)
+/// reachable from the root.
+#[derive(Clone, Debug)]
+pub struct CompatibilityReport {
+    types: Vec<TypeCompatibility>,
+}
+
+/// The compatibility of the old and the new version of a single type.
+#[derive(Clone, Debug)]
+pub struct TypeCompatibility {
+    old_type: TypeRef,
+    new_type: TypeRef,
+    old_identifier: Identifier<'static>,
+    new_identifier: Identifier<'static>,
+    compatibility: Compatibility,
+    incompatibilities: Vec<Incompatibility>,
+}
+
+/// Whether data written with one version of the schema validates under the other version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Compatibility {
+    /// Backward and forward compatible.
+    Full,
+    /// Data written with the old schema validates under the new schema (readers using the
+    /// new schema can read old data).
+    Backward,
+    /// Data written with the new schema validates under the old schema (readers using the
+    /// old schema can read new data).
+    Forward,
+    /// Neither backward nor forward compatible.
+    None,
+}
+
+/// A change that breaks compatibility (in one or both directions).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Incompatibility {
+    kind: IncompatibilityKind,
+    breaks_backward: bool,
+    breaks_forward: bool,
+    description: String,
+}
+
+/// What kind of change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IncompatibilityKind {
+    /// The type has been replaced by another kind of type (e.g. an unsigned integer by a
+    /// signed integer).
+    TypeChanged,
+    /// A field has been appended to a structure.
+    FieldAdded,
+    /// A field has been inserted in the middle of a structure.
+    FieldInserted,
+    /// A field has been removed from a structure (at the end or in the middle).
+    FieldRemoved,
+    /// The fields of a structure have been reordered.
+    FieldMoved,
+    /// A variant has been appended to an enum.
+    VariantAdded,
+    /// A variant has been inserted in the middle of an enum.
+    VariantInserted,
+    /// A variant has been removed from an enum (at the end or in the middle).
+    VariantRemoved,
+    /// The variants of an enum have been reordered.
+    VariantMoved,
+    /// A value has been appended to an enum variant.
+    VariantValueAdded,
+    /// A value has been removed from an enum variant.
+    VariantValueRemoved,
+    /// A constraint (range, length, allowed values) is more restrictive than before.
+    ConstraintNarrowed,
+    /// A constraint (range, length, allowed values) is less restrictive than before.
+    ConstraintWidened,
+    /// A constraint has changed (neither narrowed nor widened).
+    ConstraintChanged,
+    /// The sorting of a map or the ordering of a sequence has changed.
+    SortingChanged,
+    /// The anchors of a map or the level of a key reference has changed.
+    KeyRefChanged,
+}
+
+impl CompatibilityReport {
+    /// All types reachable from the root; the first entry is the root type.
+    pub fn types(&self) -> &[TypeCompatibility] {
+        &self.types
+    }
+
+    /// The compatibility of the root types (that's the compatibility of the schema).
+    pub fn compatibility(&self) -> Compatibility {
+        self.types[0].compatibility
+    }
+
+    /// All incompatibilities (of all types).
+    pub fn incompatibilities(&self) -> impl Iterator<Item = &Incompatibility> {
+        self.types
+            .iter()
+            .flat_map(|item| item.incompatibilities.iter())
+    }
+}
+
+impl TypeCompatibility {
+    /// Reference to the old version of the type.
+    pub fn old_type(&self) -> &TypeRef {
+        &self.old_type
+    }
+
+    /// Reference to the new version of the type.
+    pub fn new_type(&self) -> &TypeRef {
+        &self.new_type
+    }
+
+    pub fn old_identifier(&self) -> &Identifier<'static> {
+        &self.old_identifier
+    }
+
+    pub fn new_identifier(&self) -> &Identifier<'static> {
+        &self.new_identifier
+    }
+
+    /// The compatibility of this type; this includes the types referenced by this type.
+    pub fn compatibility(&self) -> Compatibility {
+        self.compatibility
+    }
+
+    /// Changes of this type that break compatibility (does not include the changes of
+    /// referenced types; those have their own entry).
+    pub fn incompatibilities(&self) -> &[Incompatibility] {
+        &self.incompatibilities
+    }
+}
+
+impl Compatibility {
+    fn from_flags(backward: bool, forward: bool) -> Self {
+        match (backward, forward) {
+            (true, true) => Compatibility::Full,
+            (true, false) => Compatibility::Backward,
+            (false, true) => Compatibility::Forward,
+            (false, false) => Compatibility::None,
+        }
+    }
+
+    /// True if data written with the old schema validates under the new schema.
+    pub fn is_backward(self) -> bool {
+        matches!(self, Compatibility::Full | Compatibility::Backward)
+    }
+
+    /// True if data written with the new schema validates under the old schema.
+    pub fn is_forward(self) -> bool {
+        matches!(self, Compatibility::Full | Compatibility::Forward)
+    }
+
+    pub fn is_full(self) -> bool {
+        self == Compatibility::Full
+    }
+}
+
+impl Incompatibility {
+    pub fn kind(&self) -> IncompatibilityKind {
+        self.kind
+    }
+
+    /// True if data written with the old schema might not validate under the new schema.
+    pub fn breaks_backward(&self) -> bool {
+        self.breaks_backward
+    }
+
+    /// True if data written with the new schema might not validate under the old schema.
+    pub fn breaks_forward(&self) -> bool {
+        self.breaks_forward
+    }
+
+    /// Human readable description; e.g. "uint range narrowed from [0, 100] to [0, 50]".
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+struct Checker<'o, 'n, Old, New> {
+    old: &'o Old,
+    new: &'n New,
+    no_extension: bool,
+    /// Pairs of types to check (old; new) and their index.
+    pairs: Vec<(TypeRef, TypeRef)>,
+    indexes: HashMap<(TypeRef, TypeRef), usize>,
+    /// Incompatibilities and referenced pairs of the pair currently checked.
+    incompatibilities: Vec<Incompatibility>,
+    children: Vec<usize>,
+}
+
+impl<'o, 'n, Old, New> Checker<'o, 'n, Old, New>
+where
+    Old: TypeContainer,
+    New: TypeContainer,
+{
+    fn child(&mut self, old: &TypeRef, new: &TypeRef) {
+        let key = (old.clone(), new.clone());
+        let index = if let Some(index) = self.indexes.get(&key) {
+            *index
+        } else {
+            let index = self.pairs.len();
+            self.pairs.push(key.clone());
+            self.indexes.insert(key, index);
+            index
+        };
+        if !self.children.contains(&index) {
+            self.children.push(index);
+        }
+    }
+
+    fn push(
+        &mut self,
+        kind: IncompatibilityKind,
+        breaks_backward: bool,
+        breaks_forward: bool,
+        description: String,
+    ) {
+        if breaks_backward || breaks_forward {
+            self.incompatibilities.push(Incompatibility {
+                kind,
+                breaks_backward,
+                breaks_forward,
+                description,
+            });
+        }
+    }
+
+    /// A constraint; `old_accepts_new`: everything allowed by the new constraint is also
+    /// allowed by the old constraint (and vice versa for `new_accepts_old`).
+    fn constraint<D: Display>(
+        &mut self,
+        what: &str,
+        old_accepts_new: bool,
+        new_accepts_old: bool,
+        old: D,
+        new: D,
+    ) {
+        let (kind, change) = match (old_accepts_new, new_accepts_old) {
+            (true, true) => return,
+            (true, false) => (IncompatibilityKind::ConstraintNarrowed, "narrowed"),
+            (false, true) => (IncompatibilityKind::ConstraintWidened, "widened"),
+            (false, false) => (IncompatibilityKind::ConstraintChanged, "changed"),
+        };
+        self.push(
+            kind,
+            !new_accepts_old,
+            !old_accepts_new,
+            format!("{} {} from {} to {}", what, change, old, new),
+        );
+    }
+
+    fn ine_range<T: PartialOrd + Display>(
+        &mut self,
+        what: &str,
+        old: &IneRange<T>,
+        new: &IneRange<T>,
+    ) {
+        self.constraint(
+            what,
+            ine_range_contains(old, new),
+            ine_range_contains(new, old),
+            ine_range_to_string(old),
+            ine_range_to_string(new),
+        );
+    }
+
+    fn range<T: PartialOrd + Display>(&mut self, what: &str, old: &Range<T>, new: &Range<T>) {
+        self.constraint(
+            what,
+            range_contains(old, new),
+            range_contains(new, old),
+            range_to_string(old),
+            range_to_string(new),
+        );
+    }
+
+    fn flag(&mut self, what: &str, old: bool, new: bool) {
+        self.constraint(what, old || !new, new || !old, old, new);
+    }
+
+    fn both<D: Debug>(&mut self, kind: IncompatibilityKind, what: &str, old: D, new: D) {
+        self.push(
+            kind,
+            true,
+            true,
+            format!("{} changed from {:?} to {:?}", what, old, new),
+        );
+    }
+
+    fn check_pair(&mut self, old_ref: &TypeRef, new_ref: &TypeRef) -> Result<(), LqError> {
+        let old = self.old.require_type(old_ref)?;
+        let new = self.new.require_type(new_ref)?;
+        match (old, new) {
+            (AnyType::Bool(_), AnyType::Bool(_)) | (AnyType::Uuid(_), AnyType::Uuid(_)) => {}
+            (AnyType::Option(old), AnyType::Option(new)) => self.child(old.r#type(), new.r#type()),
+            (AnyType::Seq(old), AnyType::Seq(new)) => {
+                self.child(old.element(), new.element());
+                self.constraint(
+                    "seq length",
+                    ine_range_contains(old.length(), new.length())
+                        && multiple_contains(old.multiple_of(), new.multiple_of()),
+                    ine_range_contains(new.length(), old.length())
+                        && multiple_contains(new.multiple_of(), old.multiple_of()),
+                    seq_length_to_string(old.length(), old.multiple_of()),
+                    seq_length_to_string(new.length(), new.multiple_of()),
+                );
+                let old_accepts_new = ordering_contains(old.ordering(), new.ordering());
+                let new_accepts_old = ordering_contains(new.ordering(), old.ordering());
+                self.push(
+                    IncompatibilityKind::SortingChanged,
+                    !new_accepts_old,
+                    !old_accepts_new,
+                    format!(
+                        "seq ordering changed from {} to {}",
+                        ordering_to_string(old.ordering()),
+                        ordering_to_string(new.ordering())
+                    ),
+                );
+            }
+            (AnyType::Binary(old), AnyType::Binary(new)) => {
+                self.ine_range("binary length", old.length(), new.length())
+            }
+            (AnyType::Unicode(old), AnyType::Unicode(new)) => {
+                let byte_length = |length_type| match length_type {
+                    LengthType::Byte | LengthType::Utf8Byte => true,
+                    LengthType::ScalarValue => false,
+                };
+                let unrestricted =
+                    |length: &IneRange<u64>| *length.start() == 0 && *length.end() == u64::MAX;
+                if byte_length(old.length_type()) == byte_length(new.length_type()) {
+                    self.ine_range("unicode length", old.length(), new.length());
+                } else if !unrestricted(old.length()) || !unrestricted(new.length()) {
+                    self.both(
+                        IncompatibilityKind::ConstraintChanged,
+                        "unicode length type",
+                        old.length_type(),
+                        new.length_type(),
+                    );
+                }
+            }
+            (AnyType::UInt(old), AnyType::UInt(new)) => {
+                self.ine_range("uint range", old.range(), new.range())
+            }
+            (AnyType::SInt(old), AnyType::SInt(new)) => {
+                self.ine_range("sint range", old.range(), new.range())
+            }
+            (AnyType::Float32(old), AnyType::Float32(new)) => {
+                self.range("f32 range", old.range(), new.range());
+                self.float_flags("f32", float_flags(old), float_flags(new));
+            }
+            (AnyType::Float64(old), AnyType::Float64(new)) => {
+                self.range("f64 range", old.range(), new.range());
+                self.float_flags("f64", float_flags(old), float_flags(new));
+            }
+            (AnyType::Enum(old), AnyType::Enum(new)) => {
+                let old_names: Vec<_> = old.variants().iter().map(|item| item.name()).collect();
+                let new_names: Vec<_> = new.variants().iter().map(|item| item.name()).collect();
+                let members = Members::match_names(&old_names, &new_names);
+                for (old_index, new_index) in &members.pairs {
+                    let old_variant = &old.variants()[*old_index];
+                    let new_variant = &new.variants()[*new_index];
+                    let old_values = old_variant.values();
+                    let new_values = new_variant.values();
+                    for index in 0..min(old_values.len(), new_values.len()) {
+                        self.child(&old_values[index], &new_values[index]);
+                    }
+                    let name = name_to_string(new_variant.name());
+                    for index in old_values.len()..new_values.len() {
+                        self.push(
+                            IncompatibilityKind::VariantValueAdded,
+                            true,
+                            self.no_extension,
+                            format!("value #{} added to enum variant `{}`", index, name),
+                        );
+                    }
+                    for index in new_values.len()..old_values.len() {
+                        self.push(
+                            IncompatibilityKind::VariantValueRemoved,
+                            self.no_extension,
+                            true,
+                            format!("value #{} removed from enum variant `{}`", index, name),
+                        );
+                    }
+                }
+                self.members(
+                    &members,
+                    &old_names,
+                    &new_names,
+                    "enum variant",
+                    [
+                        IncompatibilityKind::VariantAdded,
+                        IncompatibilityKind::VariantInserted,
+                        IncompatibilityKind::VariantRemoved,
+                        IncompatibilityKind::VariantMoved,
+                    ],
+                    // new data might use new variants; old data might use removed variants
+                    (false, true),
+                    (true, false),
+                );
+            }
+            (AnyType::Struct(old), AnyType::Struct(new)) => {
+                let old_names: Vec<_> = old.fields().iter().map(|item| item.name()).collect();
+                let new_names: Vec<_> = new.fields().iter().map(|item| item.name()).collect();
+                let members = Members::match_names(&old_names, &new_names);
+                for (old_index, new_index) in &members.pairs {
+                    self.child(
+                        old.fields()[*old_index].r#type(),
+                        new.fields()[*new_index].r#type(),
+                    );
+                }
+                let no_extension = self.no_extension;
+                self.members(
+                    &members,
+                    &old_names,
+                    &new_names,
+                    "field",
+                    [
+                        IncompatibilityKind::FieldAdded,
+                        IncompatibilityKind::FieldInserted,
+                        IncompatibilityKind::FieldRemoved,
+                        IncompatibilityKind::FieldMoved,
+                    ],
+                    // old data lacks new fields; new data has more fields than the old schema
+                    (true, no_extension),
+                    (no_extension, true),
+                );
+            }
+            (AnyType::Map(old), AnyType::Map(new)) => {
+                self.child(old.key(), new.key());
+                self.child(old.value(), new.value());
+                self.ine_range("map length", old.length(), new.length());
+                self.sorting("map", old.sorting(), new.sorting());
+                if old.anchors() != new.anchors() {
+                    self.both(
+                        IncompatibilityKind::KeyRefChanged,
+                        "map anchors",
+                        old.anchors(),
+                        new.anchors(),
+                    );
+                }
+            }
+            (AnyType::RootMap(old), AnyType::RootMap(new)) => {
+                self.child(old.root(), new.root());
+                self.child(old.key(), new.key());
+                self.child(old.value(), new.value());
+                self.ine_range("root map length", old.length(), new.length());
+                self.sorting("root map", old.sorting(), new.sorting());
+            }
+            (AnyType::KeyRef(old), AnyType::KeyRef(new)) => {
+                if old.level() != new.level() {
+                    self.both(
+                        IncompatibilityKind::KeyRefChanged,
+                        "key ref level",
+                        old.level(),
+                        new.level(),
+                    );
+                }
+            }
+            (AnyType::Ascii(old), AnyType::Ascii(new)) => {
+                self.ine_range("ascii length", old.length(), new.length());
+                self.constraint(
+                    "ascii codes",
+                    codes_contain(old.codes(), new.codes()),
+                    codes_contain(new.codes(), old.codes()),
+                    codes_to_string(old.codes()),
+                    codes_to_string(new.codes()),
+                );
+            }
+            (AnyType::Range(old), AnyType::Range(new)) => {
+                self.child(old.element(), new.element());
+                if old.inclusion() != new.inclusion() {
+                    self.both(
+                        IncompatibilityKind::ConstraintChanged,
+                        "range inclusion",
+                        old.inclusion(),
+                        new.inclusion(),
+                    );
+                }
+                self.flag("range `allow_empty`", old.allow_empty(), new.allow_empty());
+            }
+            (AnyType::Decimal(old), AnyType::Decimal(new)) => {
+                self.range("decimal range", old.range(), new.range())
+            }
+            (old, new) => self.push(
+                IncompatibilityKind::TypeChanged,
+                true,
+                true,
+                format!("type changed from {} to {}", type_name(old), type_name(new)),
+            ),
+        }
+        Result::Ok(())
+    }
+
+    fn float_flags(&mut self, what: &str, old: [(&str, bool); 6], new: [(&str, bool); 6]) {
+        for index in 0..old.len() {
+            let (flag, old_value) = old[index];
+            self.flag(&format!("{} `{}`", what, flag), old_value, new[index].1);
+        }
+    }
+
+    fn sorting(&mut self, what: &str, old: Sorting, new: Sorting) {
+        if old != new {
+            self.both(
+                IncompatibilityKind::SortingChanged,
+                &format!("{} sorting", what),
+                old,
+                new,
+            );
+        }
+    }
+
+    /// Reports added, inserted, removed and moved members (fields or variants). `added` and
+    /// `removed` are the flags (breaks backward; breaks forward) for members added at the end
+    /// or removed from the end.
+    #[allow(clippy::too_many_arguments)]
+    fn members(
+        &mut self,
+        members: &Members,
+        old_names: &[&Identifier],
+        new_names: &[&Identifier],
+        what: &str,
+        kinds: [IncompatibilityKind; 4],
+        added: (bool, bool),
+        removed: (bool, bool),
+    ) {
+        let [added_kind, inserted_kind, removed_kind, moved_kind] = kinds;
+        for index in &members.added {
+            self.push(
+                added_kind,
+                added.0,
+                added.1,
+                format!("{} `{}` added", what, name_to_string(new_names[*index])),
+            );
+        }
+        for index in &members.inserted {
+            self.push(
+                inserted_kind,
+                true,
+                true,
+                format!(
+                    "{} `{}` inserted in the middle (at index {})",
+                    what,
+                    name_to_string(new_names[*index]),
+                    index
+                ),
+            );
+        }
+        for index in &members.removed {
+            self.push(
+                removed_kind,
+                removed.0,
+                removed.1,
+                format!("{} `{}` removed", what, name_to_string(old_names[*index])),
+            );
+        }
+        for index in &members.removed_from_middle {
+            self.push(
+                removed_kind,
+                true,
+                true,
+                format!(
+                    "{} `{}` removed from the middle (at index {})",
+                    what,
+                    name_to_string(old_names[*index]),
+                    index
+                ),
+            );
+        }
+        for (old_index, new_index) in &members.moved {
+            self.push(
+                moved_kind,
+                true,
+                true,
+                format!(
+                    "{} `{}` moved from index {} to index {}",
+                    what,
+                    name_to_string(old_names[*old_index]),
+                    old_index,
+                    new_index
+                ),
+            );
+        }
+    }
+}
+
+/// How the members (fields of a structure; variants of an enum) of the old version map to
+/// the members of the new version.
+#[derive(Default)]
+pub(crate) struct Members {
+    /// Members (old index; new index) that are compared.
+    pub(crate) pairs: Vec<(usize, usize)>,
+    /// Members (new index) added at the end.
+    pub(crate) added: Vec<usize>,
+    /// Members (new index) inserted in the middle.
+    pub(crate) inserted: Vec<usize>,
+    /// Members (old index) removed from the end.
+    pub(crate) removed: Vec<usize>,
+    /// Members (old index) removed from the middle.
+    pub(crate) removed_from_middle: Vec<usize>,
+    /// Members (old index; new index) moved (only when nothing has been inserted or removed).
+    pub(crate) moved: Vec<(usize, usize)>,
+}
+
+impl Members {
+    pub(crate) fn match_names(old: &[&Identifier], new: &[&Identifier]) -> Self {
+        let position =
+            |names: &[&Identifier], name: &Identifier| names.iter().position(|item| *item == name);
+        let common = min(old.len(), new.len());
+        let mut members = Members::default();
+        // renamed members (name not in the other version) are fine
+        let by_position = (0..common).all(|index| {
+            old[index] == new[index]
+                || (position(new, old[index]).is_none() && position(old, new[index]).is_none())
+        });
+        if by_position {
+            members.pairs = (0..common).map(|index| (index, index)).collect();
+            members.added = (common..new.len()).collect();
+            members.removed = (common..old.len()).collect();
+            return members;
+        }
+
+        let mut moved = Vec::new();
+        for (old_index, name) in old.iter().enumerate() {
+            match position(new, name) {
+                Option::Some(new_index) => {
+                    members.pairs.push((old_index, new_index));
+                    if old_index != new_index {
+                        moved.push((old_index, new_index));
+                    }
+                }
+                Option::None if old_index < new.len() => {
+                    members.removed_from_middle.push(old_index)
+                }
+                Option::None => members.removed.push(old_index),
+            }
+        }
+        for (new_index, name) in new.iter().enumerate() {
+            if position(old, name).is_none() {
+                if new_index < old.len() {
+                    members.inserted.push(new_index);
+                } else {
+                    members.added.push(new_index);
+                }
+            }
+        }
+        if members.inserted.is_empty() && members.removed_from_middle.is_empty() {
+            members.moved = moved;
+        }
+        members
+    }
+}
+
+pub(crate) fn float_flags<F: Eq + PartialOrd + Debug>(
+    float: &TFloat<F>,
+) -> [(&'static str, bool); 6] {
+    [
+        ("allow_positive_zero", float.allow_positive_zero()),
+        ("allow_negative_zero", float.allow_negative_zero()),
+        ("allow_nan", float.allow_nan()),
+        ("allow_positive_infinity", float.allow_positive_infinity()),
+        ("allow_negative_infinity", float.allow_negative_infinity()),
+        ("allow_subnormal", float.allow_subnormal()),
+    ]
+}
+
+fn ine_range_contains<T: PartialOrd>(outer: &IneRange<T>, inner: &IneRange<T>) -> bool {
+    outer.start() <= inner.start() && inner.end() <= outer.end()
+}
+
+fn range_contains<T: PartialOrd>(outer: &Range<T>, inner: &Range<T>) -> bool {
+    let start = outer.start() < inner.start()
+        || (outer.start() == inner.start() && (outer.start_included() || !inner.start_included()));
+    let end = inner.end() < outer.end()
+        || (inner.end() == outer.end() && (outer.end_included() || !inner.end_included()));
+    start && end
+}
+
+pub(crate) fn ine_range_to_string<T: Display>(range: &IneRange<T>) -> String {
+    format!("[{}, {}]", range.start(), range.end())
+}
+
+pub(crate) fn range_to_string<T: Display>(range: &Range<T>) -> String {
+    format!(
+        "{}{}, {}{}",
+        if range.start_included() { "[" } else { "(" },
+        range.start(),
+        range.end(),
+        if range.end_included() { "]" } else { ")" }
+    )
+}
+
+/// True if every length that's a multiple of `inner` is also a multiple of `outer`.
+fn multiple_contains(outer: Option<u32>, inner: Option<u32>) -> bool {
+    match (outer, inner) {
+        (Option::None, _) => true,
+        (Option::Some(_), Option::None) => false,
+        (Option::Some(outer), Option::Some(inner)) => outer != 0 && inner % outer == 0,
+    }
+}
+
+pub(crate) fn seq_length_to_string(length: &IneRange<u32>, multiple_of: Option<u32>) -> String {
+    if let Some(multiple_of) = multiple_of {
+        format!(
+            "{} (multiple of {})",
+            ine_range_to_string(length),
+            multiple_of
+        )
+    } else {
+        ine_range_to_string(length)
+    }
+}
+
+/// True if every sequence ordered according to `inner` is also ordered according to `outer`.
+fn ordering_contains(outer: &Ordering, inner: &Ordering) -> bool {
+    match (outer, inner) {
+        (Ordering::None, _) => true,
+        (Ordering::Sorted(_), Ordering::None) => false,
+        (Ordering::Sorted(outer), Ordering::Sorted(inner)) => {
+            outer.direction == inner.direction && (!outer.unique || inner.unique)
+        }
+    }
+}
+
+pub(crate) fn ordering_to_string(ordering: &Ordering) -> String {
+    match ordering {
+        Ordering::None => "none".to_string(),
+        Ordering::Sorted(sorted) => format!(
+            "{}{}",
+            match sorted.direction {
+                Direction::Ascending => "ascending",
+                Direction::Descending => "descending",
+            },
+            if sorted.unique { " (unique)" } else { "" }
+        ),
+    }
+}
+
+fn codes_contain(outer: &CodeRange, inner: &CodeRange) -> bool {
+    (0..=u8::MAX).all(|code| !inner.contains(code) || outer.contains(code))
+}
+
+pub(crate) fn codes_to_string(codes: &CodeRange) -> String {
+    let ranges: Vec<String> = (0..codes.len() / 2)
+        .map(|index| {
+            format!(
+                "{}-{}",
+                codes[index * 2],
+                // the end is exclusive
+                codes[index * 2 + 1] - 1
+            )
+        })
+        .collect();
+    format!("[{}]", ranges.join(", "))
+}
+
+pub(crate) fn name_to_string(name: &Identifier) -> String {
+    name.to_string(Format::SnakeCase)
+}
+
+pub(crate) fn type_name(any_type: &AnyType) -> &'static str {
+    match any_type {
+        AnyType::Bool(_) => "bool",
+        AnyType::Option(_) => "option",
+        AnyType::Seq(_) => "seq",
+        AnyType::Binary(_) => "binary",
+        AnyType::Unicode(_) => "unicode",
+        AnyType::UInt(_) => "uint",
+        AnyType::SInt(_) => "sint",
+        AnyType::Float32(_) => "f32",
+        AnyType::Float64(_) => "f64",
+        AnyType::Enum(_) => "enum",
+        AnyType::Struct(_) => "struct",
+        AnyType::Map(_) => "map",
+        AnyType::RootMap(_) => "root map",
+        AnyType::KeyRef(_) => "key ref",
+        AnyType::Ascii(_) => "ascii",
+        AnyType::Uuid(_) => "uuid",
+        AnyType::Range(_) => "range",
+        AnyType::Decimal(_) => "decimal",
+    }
+}
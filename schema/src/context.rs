@@ -1,6 +1,7 @@
 use crate::any_type::AnyType;
 use crate::core::Config;
 use crate::core::TypeRef;
+use crate::validation_error::{PathSegment, ValidationResult};
 use liquesco_common::error::LqError;
 use liquesco_serialization::core::LqReader;
 use std::cmp::Ordering;
@@ -9,6 +10,16 @@ use std::cmp::Ordering;
 pub trait ValidationContext<'a>: CmpContext<'a> {
     fn validate(&mut self, reference: &TypeRef) -> Result<(), LqError>;
 
+    /// Validates a nested value (like a field of a structure). `segment` is the location of
+    /// the nested value within the current value; it's used for error reports (see
+    /// `ValidationError::path`).
+    fn validate_nested<F>(&mut self, reference: &TypeRef, segment: F) -> Result<(), LqError>
+    where
+        F: FnOnce() -> PathSegment,
+    {
+        self.validate(reference).in_path(segment)
+    }
+
     fn validate_any_type(&mut self, any_type: &AnyType) -> Result<(), LqError>;
 
     fn reader(&mut self) -> &mut Self::Reader;
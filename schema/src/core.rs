@@ -121,10 +121,21 @@ pub trait TypeContainer {
     /// Generates the hash for the given type. Technically does this:
     ///
     /// 1. Maybe reduces information of the type (depending on `information`).
-    /// 2. Converts the type to `AnyType`.
+    /// 2. Converts the type to `AnyType` and replaces all references with reference 0.
     /// 3. Serializes the `AnyType` using liquesco. Then hashes the given binary.
     /// 4. Collects all referenced types (dependencies) and does the same for those types (recursion; see step #1).
+    ///    Types that are not part of a cycle are hashed once; every reference to such a type
+    ///    hashes the hash of that type. So a shared type gives the same hash as two equal
+    ///    copies of that type. Types of a cycle (cyclic type graphs) are hashed once per
+    ///    type the cycle is entered at: Types of the cycle already hashed are not hashed
+    ///    again; instead the order they have been visited in (starting at 0) is hashed.
+    ///
+    /// The time needed is linear in the number of types and references (for types that are
+    /// not part of a cycle).
     /// 5. Then writes the the number of dependencies as u64.
+    ///
+    /// The hash does not depend on the identifiers and on the order of the types in the
+    /// container.
     fn hash_type<H: Hasher>(
         &self,
         reference: &TypeRef,
@@ -0,0 +1,721 @@
+use crate::any_type::AnyType;
+use crate::compatibility::{
+    codes_to_string, float_flags, ine_range_to_string, name_to_string, ordering_to_string,
+    range_to_string, seq_length_to_string, type_name, Members,
+};
+use crate::core::{TypeContainer, TypeRef};
+use crate::identifier::Identifier;
+use crate::metadata::{Information, WithMetadata};
+use crate::type_container::DefaultTypeContainer;
+use crate::types::tint::TInt;
+use liquesco_common::error::LqError;
+use std::collections::HashMap;
+use std::fmt::{Display, Error, Formatter};
+
+/// Compares two versions of a schema and lists what has changed.
+///
+/// Types are matched by identifier. Types that only exist in one of the versions are matched
+/// by their hash (`Information::Type`; so documentation is ignored): Those are reported as
+/// renamed. Fields of structures and variants of enums are matched by name (see
+/// `compatibility::check` to find out whether the changes are compatible).
+pub fn diff<'a>(
+    old: &DefaultTypeContainer<'a>,
+    new: &DefaultTypeContainer<'a>,
+) -> Result<SchemaDiff, LqError> {
+    let mut new_by_identifier: HashMap<&Identifier, TypeRef> = new
+        .types()
+        .map(|(reference, identifier, _)| (identifier, reference))
+        .collect();
+    let mut pairs = Vec::new();
+    let mut not_in_new = Vec::new();
+    for (old_ref, identifier, _) in old.types() {
+        if let Some(new_ref) = new_by_identifier.remove(identifier) {
+            pairs.push((old_ref, new_ref));
+        } else {
+            not_in_new.push(old_ref);
+        }
+    }
+    let mut not_in_old: Vec<TypeRef> = new
+        .types()
+        .filter(|(_, identifier, _)| new_by_identifier.contains_key(identifier))
+        .map(|(reference, _, _)| reference)
+        .collect();
+
+    let mut removed = Vec::new();
+    let mut renamed = Vec::new();
+    for old_ref in not_in_new {
+        let hash = old.type_hash(&old_ref, Information::Type)?;
+        let mut same_hash = None;
+        for (index, new_ref) in not_in_old.iter().enumerate() {
+            if new.type_hash(new_ref, Information::Type)? == hash {
+                same_hash = Some(index);
+                break;
+            }
+        }
+        if let Some(index) = same_hash {
+            let new_ref = not_in_old.remove(index);
+            renamed.push((identifier(old, &old_ref)?, identifier(new, &new_ref)?));
+            pairs.push((old_ref, new_ref));
+        } else {
+            removed.push(identifier(old, &old_ref)?);
+        }
+    }
+    let added = not_in_old
+        .iter()
+        .map(|reference| identifier(new, reference))
+        .collect::<Result<Vec<_>, LqError>>()?;
+
+    let mut comparer = Comparer {
+        old,
+        new,
+        mapping: pairs.iter().cloned().collect(),
+        changes: Vec::new(),
+    };
+    let root = if comparer.mapping.get(old.root()) == Some(new.root()) {
+        None
+    } else {
+        Some((identifier(old, old.root())?, identifier(new, new.root())?))
+    };
+    let mut changed = Vec::new();
+    for (old_ref, new_ref) in &pairs {
+        comparer.compare(old.require_type(old_ref)?, new.require_type(new_ref)?)?;
+        if !comparer.changes.is_empty() {
+            changed.push(TypeDiff {
+                old_identifier: identifier(old, old_ref)?,
+                new_identifier: identifier(new, new_ref)?,
+                changes: std::mem::take(&mut comparer.changes),
+            });
+        }
+    }
+
+    Result::Ok(SchemaDiff {
+        root,
+        added,
+        removed,
+        renamed,
+        changed,
+    })
+}
+
+/// The result of `diff`: what has changed between two versions of a schema. `Display` writes
+/// a plain text report; see also `to_markdown`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchemaDiff {
+    root: Option<(Identifier<'static>, Identifier<'static>)>,
+    added: Vec<Identifier<'static>>,
+    removed: Vec<Identifier<'static>>,
+    renamed: Vec<(Identifier<'static>, Identifier<'static>)>,
+    changed: Vec<TypeDiff>,
+}
+
+/// The changes of a single type (a type present in both versions).
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeDiff {
+    old_identifier: Identifier<'static>,
+    new_identifier: Identifier<'static>,
+    changes: Vec<Change>,
+}
+
+/// A single change of a type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    kind: ChangeKind,
+    description: String,
+}
+
+/// What kind of change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// The type has been replaced by another kind of type (e.g. an unsigned integer by a
+    /// signed integer).
+    TypeChanged,
+    FieldAdded,
+    FieldRemoved,
+    FieldRenamed,
+    FieldMoved,
+    /// The field references another type.
+    FieldTypeChanged,
+    VariantAdded,
+    VariantRemoved,
+    VariantRenamed,
+    VariantMoved,
+    /// The number of values of an enum variant or the types of those values have changed.
+    VariantValuesChanged,
+    /// Another type is referenced (e.g. by an option or as element of a sequence).
+    ReferenceChanged,
+    /// A range, a length or the allowed values have changed.
+    ConstraintChanged,
+    /// The sorting of a map or the ordering of a sequence has changed.
+    SortingChanged,
+    /// The anchors of a map or the level of a key reference has changed.
+    AnchorsChanged,
+    DocumentationChanged,
+    /// The list of implemented conformance UUIDs has changed.
+    ConformanceChanged,
+}
+
+impl SchemaDiff {
+    /// True if nothing has changed.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.changed.is_empty()
+    }
+
+    /// The root types (old; new) if the root type has changed (it's not the same type or the
+    /// renamed type).
+    pub fn root(&self) -> Option<&(Identifier<'static>, Identifier<'static>)> {
+        self.root.as_ref()
+    }
+
+    /// Types only present in the new version.
+    pub fn added(&self) -> &[Identifier<'static>] {
+        &self.added
+    }
+
+    /// Types only present in the old version.
+    pub fn removed(&self) -> &[Identifier<'static>] {
+        &self.removed
+    }
+
+    /// Renamed types (old identifier; new identifier).
+    pub fn renamed(&self) -> &[(Identifier<'static>, Identifier<'static>)] {
+        &self.renamed
+    }
+
+    /// Types present in both versions that have changed.
+    pub fn changed(&self) -> &[TypeDiff] {
+        &self.changed
+    }
+
+    /// The report as markdown.
+    pub fn to_markdown(&self) -> String {
+        let mut md = "# Schema changes\n".to_string();
+        if self.is_empty() {
+            md.push_str("\nNo changes.\n");
+            return md;
+        }
+        if let Some((old, new)) = &self.root {
+            md.push_str(&format!(
+                "\nRoot type changed from `{}` to `{}`.\n",
+                name_to_string(old),
+                name_to_string(new)
+            ));
+        }
+        let mut list = |title: &str, items: Vec<String>| {
+            if !items.is_empty() {
+                md.push_str(&format!("\n## {}\n\n", title));
+                for item in items {
+                    md.push_str(&format!("- {}\n", item));
+                }
+            }
+        };
+        list("Added types", self.added.iter().map(quoted).collect());
+        list("Removed types", self.removed.iter().map(quoted).collect());
+        list(
+            "Renamed types",
+            self.renamed
+                .iter()
+                .map(|(old, new)| format!("{} renamed to {}", quoted(old), quoted(new)))
+                .collect(),
+        );
+        if !self.changed.is_empty() {
+            md.push_str("\n## Changed types\n");
+            for type_diff in &self.changed {
+                md.push_str(&format!("\n### {}\n\n", type_diff.title()));
+                for change in &type_diff.changes {
+                    md.push_str(&format!("- {}\n", change.description));
+                }
+            }
+        }
+        md
+    }
+}
+
+impl Display for SchemaDiff {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.is_empty() {
+            return writeln!(f, "No changes.");
+        }
+        if let Some((old, new)) = &self.root {
+            writeln!(
+                f,
+                "Root type changed from {} to {}.",
+                quoted(old),
+                quoted(new)
+            )?;
+        }
+        if !self.added.is_empty() {
+            writeln!(f, "Added types:")?;
+            for identifier in &self.added {
+                writeln!(f, "  + {}", quoted(identifier))?;
+            }
+        }
+        if !self.removed.is_empty() {
+            writeln!(f, "Removed types:")?;
+            for identifier in &self.removed {
+                writeln!(f, "  - {}", quoted(identifier))?;
+            }
+        }
+        if !self.renamed.is_empty() {
+            writeln!(f, "Renamed types:")?;
+            for (old, new) in &self.renamed {
+                writeln!(f, "  * {} renamed to {}", quoted(old), quoted(new))?;
+            }
+        }
+        if !self.changed.is_empty() {
+            writeln!(f, "Changed types:")?;
+            for type_diff in &self.changed {
+                writeln!(f, "  {}:", type_diff.title())?;
+                for change in &type_diff.changes {
+                    writeln!(f, "    - {}", change.description)?;
+                }
+            }
+        }
+        Result::Ok(())
+    }
+}
+
+impl TypeDiff {
+    pub fn old_identifier(&self) -> &Identifier<'static> {
+        &self.old_identifier
+    }
+
+    pub fn new_identifier(&self) -> &Identifier<'static> {
+        &self.new_identifier
+    }
+
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// True if only the documentation has changed.
+    pub fn documentation_only(&self) -> bool {
+        self.changes
+            .iter()
+            .all(|change| change.kind == ChangeKind::DocumentationChanged)
+    }
+
+    fn title(&self) -> String {
+        let mut title = quoted(&self.old_identifier);
+        if self.old_identifier != self.new_identifier {
+            title.push_str(&format!(" (renamed to {})", quoted(&self.new_identifier)));
+        }
+        if self.documentation_only() {
+            title.push_str(" (documentation only)");
+        }
+        title
+    }
+}
+
+impl Change {
+    pub fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    /// Human readable description; e.g. "field `email` added".
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+fn identifier<C: TypeContainer>(
+    container: &C,
+    reference: &TypeRef,
+) -> Result<Identifier<'static>, LqError> {
+    Result::Ok(container.identifier(reference)?.into_owned().into_owned())
+}
+
+fn quoted(identifier: &Identifier) -> String {
+    format!("`{}`", name_to_string(identifier))
+}
+
+struct Comparer<'o, 'n, 'a> {
+    old: &'o DefaultTypeContainer<'a>,
+    new: &'n DefaultTypeContainer<'a>,
+    /// Types of the old version mapped to the types of the new version.
+    mapping: HashMap<TypeRef, TypeRef>,
+    /// Changes of the type currently compared.
+    changes: Vec<Change>,
+}
+
+impl<'o, 'n, 'a> Comparer<'o, 'n, 'a> {
+    fn push(&mut self, kind: ChangeKind, description: String) {
+        self.changes.push(Change { kind, description });
+    }
+
+    fn changed<D: PartialEq + Display>(&mut self, kind: ChangeKind, what: &str, old: D, new: D) {
+        if old != new {
+            self.push(kind, format!("{} changed from {} to {}", what, old, new));
+        }
+    }
+
+    /// The reference has changed unless it references the same type (or the renamed type).
+    fn reference(
+        &mut self,
+        kind: ChangeKind,
+        what: &str,
+        old: &TypeRef,
+        new: &TypeRef,
+    ) -> Result<(), LqError> {
+        if self.mapping.get(old) != Some(new) {
+            let description = format!(
+                "{} changed from {} to {}",
+                what,
+                quoted(&*self.old.identifier(old)?),
+                quoted(&*self.new.identifier(new)?)
+            );
+            self.push(kind, description);
+        }
+        Result::Ok(())
+    }
+
+    fn compare(&mut self, old: &AnyType, new: &AnyType) -> Result<(), LqError> {
+        if old.meta().doc() != new.meta().doc() {
+            self.push(
+                ChangeKind::DocumentationChanged,
+                "documentation changed".to_string(),
+            );
+        }
+        if old.meta().implements() != new.meta().implements() {
+            self.push(
+                ChangeKind::ConformanceChanged,
+                "conformance changed".to_string(),
+            );
+        }
+        match (old, new) {
+            (AnyType::Bool(_), AnyType::Bool(_)) | (AnyType::Uuid(_), AnyType::Uuid(_)) => {}
+            (AnyType::Option(old), AnyType::Option(new)) => {
+                self.reference(
+                    ChangeKind::ReferenceChanged,
+                    "option type",
+                    old.r#type(),
+                    new.r#type(),
+                )?;
+            }
+            (AnyType::Seq(old), AnyType::Seq(new)) => {
+                self.reference(
+                    ChangeKind::ReferenceChanged,
+                    "seq element type",
+                    old.element(),
+                    new.element(),
+                )?;
+                self.changed(
+                    ChangeKind::ConstraintChanged,
+                    "seq length",
+                    seq_length_to_string(old.length(), old.multiple_of()),
+                    seq_length_to_string(new.length(), new.multiple_of()),
+                );
+                self.changed(
+                    ChangeKind::SortingChanged,
+                    "seq ordering",
+                    ordering_to_string(old.ordering()),
+                    ordering_to_string(new.ordering()),
+                );
+            }
+            (AnyType::Binary(old), AnyType::Binary(new)) => self.changed(
+                ChangeKind::ConstraintChanged,
+                "binary length",
+                ine_range_to_string(old.length()),
+                ine_range_to_string(new.length()),
+            ),
+            (AnyType::Unicode(old), AnyType::Unicode(new)) => {
+                self.changed(
+                    ChangeKind::ConstraintChanged,
+                    "unicode length",
+                    ine_range_to_string(old.length()),
+                    ine_range_to_string(new.length()),
+                );
+                self.changed(
+                    ChangeKind::ConstraintChanged,
+                    "unicode length type",
+                    format!("{:?}", old.length_type()),
+                    format!("{:?}", new.length_type()),
+                );
+            }
+            (AnyType::UInt(old), AnyType::UInt(new)) => self.changed(
+                ChangeKind::ConstraintChanged,
+                "uint range",
+                ine_range_to_string(old.range()),
+                ine_range_to_string(new.range()),
+            ),
+            (AnyType::SInt(old), AnyType::SInt(new)) => self.changed(
+                ChangeKind::ConstraintChanged,
+                "sint range",
+                ine_range_to_string(old.range()),
+                ine_range_to_string(new.range()),
+            ),
+            (AnyType::Float32(old), AnyType::Float32(new)) => {
+                self.changed(
+                    ChangeKind::ConstraintChanged,
+                    "f32 range",
+                    range_to_string(old.range()),
+                    range_to_string(new.range()),
+                );
+                self.float_flags("f32", float_flags(old), float_flags(new));
+            }
+            (AnyType::Float64(old), AnyType::Float64(new)) => {
+                self.changed(
+                    ChangeKind::ConstraintChanged,
+                    "f64 range",
+                    range_to_string(old.range()),
+                    range_to_string(new.range()),
+                );
+                self.float_flags("f64", float_flags(old), float_flags(new));
+            }
+            (AnyType::Enum(old), AnyType::Enum(new)) => {
+                let old_names: Vec<_> = old.variants().iter().map(|item| item.name()).collect();
+                let new_names: Vec<_> = new.variants().iter().map(|item| item.name()).collect();
+                let members = Members::match_names(&old_names, &new_names);
+                for (old_index, new_index) in &members.pairs {
+                    let name = name_to_string(new_names[*new_index]);
+                    let old_values = old.variants()[*old_index].values();
+                    let new_values = new.variants()[*new_index].values();
+                    self.changed(
+                        ChangeKind::VariantValuesChanged,
+                        &format!("number of values of enum variant `{}`", name),
+                        old_values.len(),
+                        new_values.len(),
+                    );
+                    for (index, (old_value, new_value)) in
+                        old_values.iter().zip(new_values.iter()).enumerate()
+                    {
+                        self.reference(
+                            ChangeKind::VariantValuesChanged,
+                            &format!("type of value #{} of enum variant `{}`", index, name),
+                            old_value,
+                            new_value,
+                        )?;
+                    }
+                }
+                self.members(
+                    "enum variant",
+                    &old_names,
+                    &new_names,
+                    &members,
+                    [
+                        ChangeKind::VariantAdded,
+                        ChangeKind::VariantRemoved,
+                        ChangeKind::VariantRenamed,
+                        ChangeKind::VariantMoved,
+                    ],
+                );
+            }
+            (AnyType::Struct(old), AnyType::Struct(new)) => {
+                let old_names: Vec<_> = old.fields().iter().map(|item| item.name()).collect();
+                let new_names: Vec<_> = new.fields().iter().map(|item| item.name()).collect();
+                let members = Members::match_names(&old_names, &new_names);
+                for (old_index, new_index) in &members.pairs {
+                    self.reference(
+                        ChangeKind::FieldTypeChanged,
+                        &format!("type of field `{}`", name_to_string(new_names[*new_index])),
+                        old.fields()[*old_index].r#type(),
+                        new.fields()[*new_index].r#type(),
+                    )?;
+                }
+                self.members(
+                    "field",
+                    &old_names,
+                    &new_names,
+                    &members,
+                    [
+                        ChangeKind::FieldAdded,
+                        ChangeKind::FieldRemoved,
+                        ChangeKind::FieldRenamed,
+                        ChangeKind::FieldMoved,
+                    ],
+                );
+            }
+            (AnyType::Map(old), AnyType::Map(new)) => {
+                self.reference(
+                    ChangeKind::ReferenceChanged,
+                    "map key type",
+                    old.key(),
+                    new.key(),
+                )?;
+                self.reference(
+                    ChangeKind::ReferenceChanged,
+                    "map value type",
+                    old.value(),
+                    new.value(),
+                )?;
+                self.changed(
+                    ChangeKind::ConstraintChanged,
+                    "map length",
+                    ine_range_to_string(old.length()),
+                    ine_range_to_string(new.length()),
+                );
+                self.changed(
+                    ChangeKind::SortingChanged,
+                    "map sorting",
+                    format!("{:?}", old.sorting()),
+                    format!("{:?}", new.sorting()),
+                );
+                self.changed(
+                    ChangeKind::AnchorsChanged,
+                    "map anchors",
+                    old.anchors(),
+                    new.anchors(),
+                );
+            }
+            (AnyType::RootMap(old), AnyType::RootMap(new)) => {
+                self.reference(
+                    ChangeKind::ReferenceChanged,
+                    "root map root type",
+                    old.root(),
+                    new.root(),
+                )?;
+                self.reference(
+                    ChangeKind::ReferenceChanged,
+                    "root map key type",
+                    old.key(),
+                    new.key(),
+                )?;
+                self.reference(
+                    ChangeKind::ReferenceChanged,
+                    "root map value type",
+                    old.value(),
+                    new.value(),
+                )?;
+                self.changed(
+                    ChangeKind::ConstraintChanged,
+                    "root map length",
+                    ine_range_to_string(old.length()),
+                    ine_range_to_string(new.length()),
+                );
+                self.changed(
+                    ChangeKind::SortingChanged,
+                    "root map sorting",
+                    format!("{:?}", old.sorting()),
+                    format!("{:?}", new.sorting()),
+                );
+            }
+            (AnyType::KeyRef(old), AnyType::KeyRef(new)) => self.changed(
+                ChangeKind::AnchorsChanged,
+                "key ref level",
+                old.level(),
+                new.level(),
+            ),
+            (AnyType::Ascii(old), AnyType::Ascii(new)) => {
+                self.changed(
+                    ChangeKind::ConstraintChanged,
+                    "ascii length",
+                    ine_range_to_string(old.length()),
+                    ine_range_to_string(new.length()),
+                );
+                self.changed(
+                    ChangeKind::ConstraintChanged,
+                    "ascii codes",
+                    codes_to_string(old.codes()),
+                    codes_to_string(new.codes()),
+                );
+            }
+            (AnyType::Range(old), AnyType::Range(new)) => {
+                self.reference(
+                    ChangeKind::ReferenceChanged,
+                    "range element type",
+                    old.element(),
+                    new.element(),
+                )?;
+                self.changed(
+                    ChangeKind::ConstraintChanged,
+                    "range inclusion",
+                    format!("{:?}", old.inclusion()),
+                    format!("{:?}", new.inclusion()),
+                );
+                self.changed(
+                    ChangeKind::ConstraintChanged,
+                    "range `allow_empty`",
+                    old.allow_empty(),
+                    new.allow_empty(),
+                );
+            }
+            (AnyType::Decimal(old), AnyType::Decimal(new)) => self.changed(
+                ChangeKind::ConstraintChanged,
+                "decimal range",
+                range_to_string(old.range()),
+                range_to_string(new.range()),
+            ),
+            (old, new) => self.changed(
+                ChangeKind::TypeChanged,
+                "type",
+                type_name(old),
+                type_name(new),
+            ),
+        }
+        Result::Ok(())
+    }
+
+    fn float_flags(&mut self, what: &str, old: [(&str, bool); 6], new: [(&str, bool); 6]) {
+        for index in 0..old.len() {
+            let (flag, old_value) = old[index];
+            self.changed(
+                ChangeKind::ConstraintChanged,
+                &format!("{} `{}`", what, flag),
+                old_value,
+                new[index].1,
+            );
+        }
+    }
+
+    /// Reports added, removed, renamed and moved members (fields or variants). `kinds`: The
+    /// change kinds for added, removed, renamed and moved members.
+    fn members(
+        &mut self,
+        what: &str,
+        old_names: &[&Identifier],
+        new_names: &[&Identifier],
+        members: &Members,
+        kinds: [ChangeKind; 4],
+    ) {
+        let [added_kind, removed_kind, renamed_kind, moved_kind] = kinds;
+        for (old_index, new_index) in &members.pairs {
+            let (old_name, new_name) = (old_names[*old_index], new_names[*new_index]);
+            if old_name != new_name {
+                self.push(
+                    renamed_kind,
+                    format!(
+                        "{} `{}` renamed to `{}`",
+                        what,
+                        name_to_string(old_name),
+                        name_to_string(new_name)
+                    ),
+                );
+            }
+        }
+        for index in &members.added {
+            let name = name_to_string(new_names[*index]);
+            self.push(added_kind, format!("{} `{}` added", what, name));
+        }
+        for index in &members.inserted {
+            let name = name_to_string(new_names[*index]);
+            self.push(
+                added_kind,
+                format!("{} `{}` inserted at index {}", what, name, index),
+            );
+        }
+        for index in &members.removed {
+            let name = name_to_string(old_names[*index]);
+            self.push(removed_kind, format!("{} `{}` removed", what, name));
+        }
+        for index in &members.removed_from_middle {
+            let name = name_to_string(old_names[*index]);
+            self.push(
+                removed_kind,
+                format!("{} `{}` removed from index {}", what, name, index),
+            );
+        }
+        for (old_index, new_index) in &members.moved {
+            let name = name_to_string(old_names[*old_index]);
+            self.push(
+                moved_kind,
+                format!(
+                    "{} `{}` moved from index {} to index {}",
+                    what, name, old_index, new_index
+                ),
+            );
+        }
+    }
+}
@@ -2,12 +2,29 @@
 extern crate derive_new;
 
 pub mod any_type;
+pub mod compatibility;
 pub mod context;
 pub mod core;
+pub mod diff;
 pub mod identifier;
+pub mod lint;
 pub mod metadata;
+pub mod migration;
+pub mod native;
 pub mod schema;
 pub mod schema_builder;
 pub mod type_container;
 pub mod type_hash;
 pub mod types;
+pub mod validation_error;
+
+#[cfg(feature = "derive")]
+pub use liquesco_derive::LqSchema;
+
+/// Not public API: Used by the code generated by the derive macros (`liquesco-derive`).
+#[doc(hidden)]
+pub mod __private {
+    pub use liquesco_common::float::{F32Ext, F64Ext};
+    pub use liquesco_common::ine_range::U32IneRange;
+    pub use liquesco_serialization::types::uuid::Uuid;
+}
@@ -0,0 +1,471 @@
+use crate::any_type::AnyType;
+use crate::compatibility::{ine_range_to_string, name_to_string, range_to_string, type_name};
+use crate::core::{Type, TypeContainer, TypeRef};
+use crate::identifier::Identifier;
+use crate::metadata::WithMetadata;
+use crate::types::tint::TInt;
+use liquesco_common::error::LqError;
+use liquesco_common::ine_range::IneRange;
+use liquesco_common::range::Range;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Error, Formatter};
+
+/// Checks a schema for problems `SchemaBuilder::finish` does not detect (it only makes sure
+/// all referenced types exist): Types not reachable from the root, key refs that can never
+/// reference a key, maps with anchors whose keys are never referenced, impossible constraints,
+/// types that cannot be instantiated with finite data and missing documentation.
+///
+/// The types are enumerated using numerical references (see `TypeRef::new_numerical`),
+/// starting at 0 until there's no type.
+pub fn lint<C: TypeContainer>(container: &C) -> Result<LintReport, LqError> {
+    let mut types = Vec::new();
+    for index in 0.. {
+        let reference = TypeRef::new_numerical(index);
+        if let Some(any_type) = container.maybe_type(&reference) {
+            types.push((reference, any_type));
+        } else {
+            break;
+        }
+    }
+
+    let mut issues: HashMap<TypeRef, Vec<(LintKind, String)>> = HashMap::new();
+    let mut push = |reference: &TypeRef, kind: LintKind, description: String| {
+        issues
+            .entry(reference.clone())
+            .or_default()
+            .push((kind, description));
+    };
+
+    // reachable types
+    let mut reachable = HashSet::new();
+    let mut stack = vec![container.root().clone()];
+    while let Some(reference) = stack.pop() {
+        if reachable.insert(reference.clone()) {
+            stack.extend(references(container.require_type(&reference)?).cloned());
+        }
+    }
+    for (reference, _) in &types {
+        if !reachable.contains(reference) {
+            push(
+                reference,
+                LintKind::UnreachableType,
+                "type is not reachable from the root type".to_string(),
+            );
+        }
+    }
+
+    // key refs and anchors (only for types reachable from the root)
+    let max_level = types
+        .iter()
+        .filter(|(reference, _)| reachable.contains(reference))
+        .filter_map(|(_, any_type)| match any_type {
+            AnyType::KeyRef(key_ref) => Some(key_ref.level()),
+            _ => None,
+        })
+        .max();
+    if let Some(max_level) = max_level {
+        let max_depth = max_level.saturating_add(1);
+        let from_root = anchor_depths(container, vec![(container.root().clone(), 0)], max_depth)?;
+        for (reference, any_type) in &types {
+            if let AnyType::KeyRef(key_ref) = any_type {
+                let reached = from_root
+                    .iter()
+                    .any(|(item, depth)| item == reference && *depth > key_ref.level());
+                if reachable.contains(reference) && !reached {
+                    push(
+                        reference,
+                        LintKind::DeadKeyRef,
+                        format!(
+                            "key ref can never reference a key: there's no map with anchors \
+                             at level {}",
+                            key_ref.level()
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    for (reference, any_type) in &types {
+        if let AnyType::Map(map) = any_type {
+            if map.anchors() && reachable.contains(reference) {
+                let max_depth = max_level.map_or(0, |level| level.saturating_add(1));
+                let referenced =
+                    anchor_depths(container, vec![(map.value().clone(), 0)], max_depth)?
+                        .iter()
+                        .any(|(item, depth)| match container.maybe_type(item) {
+                            Some(AnyType::KeyRef(key_ref)) => key_ref.level() == *depth,
+                            _ => false,
+                        });
+                if !referenced {
+                    push(
+                        reference,
+                        LintKind::UnusedAnchors,
+                        "map has anchors but there's no key ref referencing its keys".to_string(),
+                    );
+                }
+            }
+        }
+    }
+
+    // constraints
+    let mut constraint_problems = HashSet::new();
+    for (reference, any_type) in &types {
+        for (kind, description) in constraints(any_type) {
+            constraint_problems.insert(reference.clone());
+            push(reference, kind, description);
+        }
+    }
+
+    // types that can be instantiated (least fixed point: a type that contains itself is never
+    // added unless there's a way out, such as an option or an empty sequence)
+    let mut instantiable = HashSet::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (reference, any_type) in &types {
+            if !instantiable.contains(reference)
+                && !constraint_problems.contains(reference)
+                && can_instantiate(any_type, &instantiable)
+            {
+                instantiable.insert(reference.clone());
+                changed = true;
+            }
+        }
+    }
+    for (reference, _) in &types {
+        if !instantiable.contains(reference) && !constraint_problems.contains(reference) {
+            push(
+                reference,
+                LintKind::NotInstantiable,
+                "type can never be instantiated with finite data (e.g. a structure that \
+                 contains itself)"
+                    .to_string(),
+            );
+        }
+    }
+
+    for (reference, any_type) in &types {
+        let documented = any_type
+            .meta()
+            .doc()
+            .is_some_and(|doc| !doc.trim().is_empty());
+        if !documented {
+            push(
+                reference,
+                LintKind::MissingDocumentation,
+                "type has no documentation".to_string(),
+            );
+        }
+    }
+
+    let mut result = Vec::new();
+    for (reference, _) in &types {
+        if let Some(type_issues) = issues.remove(reference) {
+            let identifier = container.identifier(reference)?.into_owned().into_owned();
+            for (kind, description) in type_issues {
+                result.push(LintIssue {
+                    reference: reference.clone(),
+                    identifier: identifier.clone(),
+                    kind,
+                    description,
+                });
+            }
+        }
+    }
+    Result::Ok(LintReport { issues: result })
+}
+
+/// Result of `lint`: All issues found; ordered by type.
+#[derive(Clone, Debug)]
+pub struct LintReport {
+    issues: Vec<LintIssue>,
+}
+
+/// A problem found in a type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LintIssue {
+    reference: TypeRef,
+    identifier: Identifier<'static>,
+    kind: LintKind,
+    description: String,
+}
+
+/// What kind of problem.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LintKind {
+    /// The type cannot be reached from the root type.
+    UnreachableType,
+    /// A key ref that's never within a map with anchors at its level (data validation always
+    /// fails).
+    DeadKeyRef,
+    /// A map with anchors but there's no key ref referencing its keys.
+    UnusedAnchors,
+    /// A range (or length) that does not contain a single value.
+    EmptyRange,
+    /// The length of a sequence must be a multiple of some number but no length within the
+    /// length range is.
+    ImpossibleMultipleOf,
+    /// The type can never be instantiated with finite data (e.g. a structure that contains
+    /// itself; or a structure that contains such a structure).
+    NotInstantiable,
+    /// The type has no documentation.
+    MissingDocumentation,
+}
+
+impl LintReport {
+    /// All issues found.
+    pub fn issues(&self) -> &[LintIssue] {
+        &self.issues
+    }
+
+    /// All issues of the given kind.
+    pub fn issues_of(&self, kind: LintKind) -> impl Iterator<Item = &LintIssue> {
+        self.issues.iter().filter(move |item| item.kind == kind)
+    }
+
+    /// True if no issues have been found.
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl LintIssue {
+    /// The type with the problem.
+    pub fn reference(&self) -> &TypeRef {
+        &self.reference
+    }
+
+    pub fn identifier(&self) -> &Identifier<'static> {
+        &self.identifier
+    }
+
+    pub fn kind(&self) -> LintKind {
+        self.kind
+    }
+
+    /// Human readable description.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl Display for LintReport {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.issues.is_empty() {
+            return writeln!(f, "No issues.");
+        }
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Result::Ok(())
+    }
+}
+
+impl Display for LintIssue {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(
+            f,
+            "`{}`: {}",
+            name_to_string(&self.identifier),
+            self.description
+        )
+    }
+}
+
+fn references<'t>(any_type: &'t AnyType) -> impl Iterator<Item = &'t TypeRef> {
+    (0..)
+        .map(move |index| any_type.reference(index))
+        .take_while(Option::is_some)
+        .map(Option::unwrap)
+}
+
+/// Walks the types starting at `start` and returns all types reached (type; number of maps
+/// with anchors around the type). The number of maps is limited to `max_depth`.
+fn anchor_depths<C: TypeContainer>(
+    container: &C,
+    start: Vec<(TypeRef, u32)>,
+    max_depth: u32,
+) -> Result<HashSet<(TypeRef, u32)>, LqError> {
+    let mut reached = HashSet::new();
+    let mut stack = start;
+    while let Some((reference, depth)) = stack.pop() {
+        if !reached.insert((reference.clone(), depth)) {
+            continue;
+        }
+        // keys cannot reference their own map; values (and the root of a root map) can.
+        let inner = depth.saturating_add(1).min(max_depth);
+        match container.require_type(&reference)? {
+            AnyType::Map(map) if map.anchors() => {
+                stack.push((map.key().clone(), depth));
+                stack.push((map.value().clone(), inner));
+            }
+            AnyType::RootMap(map) => {
+                stack.push((map.key().clone(), depth));
+                stack.push((map.value().clone(), inner));
+                stack.push((map.root().clone(), inner));
+            }
+            any_type => stack.extend(references(any_type).map(|item| (item.clone(), depth))),
+        }
+    }
+    Result::Ok(reached)
+}
+
+/// Constraints that cannot be satisfied.
+fn constraints(any_type: &AnyType) -> Vec<(LintKind, String)> {
+    let mut problems = Vec::new();
+    let name = type_name(any_type);
+    let mut length = |length_is_empty: bool, length: String| {
+        if length_is_empty {
+            problems.push((
+                LintKind::EmptyRange,
+                format!("{} length {} is empty", name, length),
+            ));
+        }
+    };
+    match any_type {
+        AnyType::Seq(seq) => {
+            length(
+                ine_range_is_empty(seq.length()),
+                ine_range_to_string(seq.length()),
+            );
+        }
+        AnyType::Binary(binary) => {
+            length(
+                ine_range_is_empty(binary.length()),
+                ine_range_to_string(binary.length()),
+            );
+        }
+        AnyType::Unicode(unicode) => length(
+            ine_range_is_empty(unicode.length()),
+            ine_range_to_string(unicode.length()),
+        ),
+        AnyType::Ascii(ascii) => {
+            length(
+                ine_range_is_empty(ascii.length()),
+                ine_range_to_string(ascii.length()),
+            );
+        }
+        AnyType::Map(map) => {
+            length(
+                ine_range_is_empty(map.length()),
+                ine_range_to_string(map.length()),
+            );
+        }
+        AnyType::RootMap(map) => {
+            length(
+                ine_range_is_empty(map.length()),
+                ine_range_to_string(map.length()),
+            );
+        }
+        _ => {}
+    }
+
+    let mut range = |range_is_empty: bool, range: String| {
+        if range_is_empty {
+            problems.push((
+                LintKind::EmptyRange,
+                format!("{} range {} is empty", name, range),
+            ));
+        }
+    };
+    match any_type {
+        AnyType::UInt(uint) => {
+            range(
+                ine_range_is_empty(uint.range()),
+                ine_range_to_string(uint.range()),
+            );
+        }
+        AnyType::SInt(sint) => {
+            range(
+                ine_range_is_empty(sint.range()),
+                ine_range_to_string(sint.range()),
+            );
+        }
+        AnyType::Float32(float) => {
+            range(
+                range_is_empty(float.range()),
+                range_to_string(float.range()),
+            );
+        }
+        AnyType::Float64(float) => {
+            range(
+                range_is_empty(float.range()),
+                range_to_string(float.range()),
+            );
+        }
+        AnyType::Decimal(decimal) => {
+            range(
+                range_is_empty(decimal.range()),
+                range_to_string(decimal.range()),
+            );
+        }
+        _ => {}
+    }
+
+    match any_type {
+        AnyType::Ascii(ascii) if ascii.codes().is_empty() && *ascii.length().start() > 0 => {
+            problems.push((
+                LintKind::EmptyRange,
+                format!(
+                    "ascii code range is empty but the length has to be at least {}",
+                    ascii.length().start()
+                ),
+            ));
+        }
+        AnyType::Seq(seq) => {
+            if let Some(multiple_of) = seq.multiple_of() {
+                let start = u64::from(*seq.length().start());
+                let end = u64::from(*seq.length().end());
+                let multiple_of = u64::from(multiple_of);
+                let possible = multiple_of != 0 && start.div_ceil(multiple_of) * multiple_of <= end;
+                if !possible {
+                    problems.push((
+                        LintKind::ImpossibleMultipleOf,
+                        format!(
+                            "no seq length within {} is a multiple of {}",
+                            ine_range_to_string(seq.length()),
+                            multiple_of
+                        ),
+                    ));
+                }
+            }
+        }
+        _ => {}
+    }
+    problems
+}
+
+/// True if the type can be instantiated given the types in `instantiable` can be
+/// instantiated.
+fn can_instantiate(any_type: &AnyType, instantiable: &HashSet<TypeRef>) -> bool {
+    let all = |references: &[&TypeRef]| references.iter().all(|item| instantiable.contains(item));
+    match any_type {
+        AnyType::Seq(seq) => *seq.length().start() == 0 || all(&[seq.element()]),
+        AnyType::Map(map) => *map.length().start() == 0 || all(&[map.key(), map.value()]),
+        AnyType::RootMap(map) => {
+            all(&[map.root()]) && (*map.length().start() == 0 || all(&[map.key(), map.value()]))
+        }
+        AnyType::Struct(structure) => structure
+            .fields()
+            .iter()
+            .all(|field| instantiable.contains(field.r#type())),
+        AnyType::Enum(enumeration) => enumeration.variants().iter().any(|variant| {
+            variant
+                .values()
+                .iter()
+                .all(|value| instantiable.contains(value))
+        }),
+        AnyType::Range(range) => all(&[range.element()]),
+        // options can be absent; all other types do not contain other types
+        _ => true,
+    }
+}
+
+fn ine_range_is_empty<T: PartialOrd>(range: &IneRange<T>) -> bool {
+    range.start() > range.end()
+}
+
+fn range_is_empty<T: PartialOrd>(range: &Range<T>) -> bool {
+    range.start() > range.end()
+        || (range.start() == range.end() && !(range.start_included() && range.end_included()))
+}
@@ -0,0 +1,647 @@
+use crate::any_type::AnyType;
+use crate::compatibility::type_name;
+use crate::core::{Config, Schema, TypeRef};
+use crate::identifier::{Format, Identifier};
+use crate::types::map::Sorting;
+use crate::types::range::Inclusion;
+use crate::types::seq::{Direction, Ordering as SeqOrdering};
+use liquesco_common::error::LqError;
+use liquesco_serialization::core::{DeSerializer, Serializer, ToVecLqWriter};
+use liquesco_serialization::slice_reader::SliceReader;
+use liquesco_serialization::types::float::Float;
+use liquesco_serialization::value::{Value, ValueRef, ValueSeq, ValueVariant};
+use liquesco_serialization::vec_writer::VecWriter;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// A conversion of a value (see `TypeMigration::with_conversion`).
+type Conversion<'a> = Box<dyn Fn(&Value) -> Result<Value<'static>, LqError> + 'a>;
+
+/// Declares how to migrate data from an old version of a schema to a new version (see
+/// `migrate`). Contains the transformations for the types that cannot be migrated
+/// automatically.
+#[derive(Default)]
+pub struct Migration<'a> {
+    types: HashMap<Identifier<'static>, TypeMigration<'a>>,
+}
+
+/// How to migrate the data of a single type (the type in the new version of the schema).
+///
+/// Without transformations, fields of structures and variants of enums are matched by name
+/// (so reordered fields are migrated automatically), integers can be converted to other
+/// integer types (when the value is within the new range) and 32-bit floats can be
+/// converted to 64-bit floats.
+#[derive(Default)]
+pub struct TypeMigration<'a> {
+    /// New field name -> old field name.
+    renamed_fields: HashMap<Identifier<'static>, Identifier<'static>>,
+    /// New field name -> default value (in the format of the new field type).
+    defaults: HashMap<Identifier<'static>, Value<'static>>,
+    /// Old variant name -> new variant name.
+    variants: HashMap<Identifier<'static>, Identifier<'static>>,
+    conversion: Option<Conversion<'a>>,
+}
+
+impl<'a> Migration<'a> {
+    /// Adds the transformations for the given type (identifier of the type in the new version
+    /// of the schema).
+    pub fn with_type(
+        mut self,
+        identifier: Identifier<'static>,
+        migration: TypeMigration<'a>,
+    ) -> Self {
+        self.types.insert(identifier, migration);
+        self
+    }
+}
+
+impl<'a> TypeMigration<'a> {
+    /// The field `new_name` (new version) gets its value from the field `old_name` (old
+    /// version).
+    pub fn with_renamed_field(
+        mut self,
+        new_name: Identifier<'static>,
+        old_name: Identifier<'static>,
+    ) -> Self {
+        self.renamed_fields.insert(new_name, old_name);
+        self
+    }
+
+    /// The value for the field `name` when there's no such field in the old version. Fields
+    /// with an option type do not need a default value (they are absent by default).
+    pub fn with_default(mut self, name: Identifier<'static>, value: Value<'static>) -> Self {
+        self.defaults.insert(name, value);
+        self
+    }
+
+    /// The enum variant `old_name` (old version) is now called `new_name`.
+    pub fn with_variant(
+        mut self,
+        old_name: Identifier<'static>,
+        new_name: Identifier<'static>,
+    ) -> Self {
+        self.variants.insert(old_name, new_name);
+        self
+    }
+
+    /// Converts the value of the old version to the value of the new version (e.g. for unit
+    /// conversions). The conversion replaces the automatic migration of this type: the
+    /// returned value has to be in the format of the new type.
+    pub fn with_conversion<F>(mut self, conversion: F) -> Self
+    where
+        F: Fn(&Value) -> Result<Value<'static>, LqError> + 'a,
+    {
+        self.conversion = Some(Box::new(conversion));
+        self
+    }
+}
+
+/// Migrates the data from the old version of the schema to the new version.
+///
+/// Validates the given data using the old schema, migrates the data (see `Migration`) and
+/// returns the canonical data for the new schema: Maps and sorted sequences are sorted again
+/// (keys might sort differently after the migration; key references are updated
+/// accordingly). The returned data has been validated using the new schema (strict).
+pub fn migrate<Old, New>(
+    old: &Old,
+    new: &New,
+    migration: &Migration,
+    data: &[u8],
+) -> Result<Vec<u8>, LqError>
+where
+    Old: Schema,
+    New: Schema,
+{
+    let mut reader = SliceReader::from(data);
+    old.validate(Config::new(), &mut reader)?;
+    let mut reader = SliceReader::from(data);
+    let value = Value::de_serialize(&mut reader)?;
+
+    let mut migrator = Migrator {
+        old,
+        new,
+        migration,
+        resolved: HashMap::new(),
+        key_ref_mappings: Vec::new(),
+    };
+    let migrated = migrator.migrate(old.root(), new.root(), &value)?;
+    let canonical = migrator.canonicalize(new.root(), migrated)?;
+
+    let new_data = serialize(&canonical)?;
+    let mut reader = SliceReader::from(&new_data);
+    new.validate(Config::strict(), &mut reader).map_err(|err| {
+        let msg = format!("The migrated data is invalid: {}", err.msg());
+        err.with_msg(msg)
+    })?;
+    Result::Ok(new_data)
+}
+
+struct Migrator<'s, 'm, Old, New> {
+    old: &'s Old,
+    new: &'s New,
+    migration: &'m Migration<'m>,
+    /// The type migrations found for the new types.
+    resolved: HashMap<TypeRef, Option<&'m TypeMigration<'m>>>,
+    /// For every map with anchors currently canonicalized (innermost last): the new index of
+    /// the entries (by old index).
+    key_ref_mappings: Vec<Vec<u32>>,
+}
+
+impl<'s, 'm, Old, New> Migrator<'s, 'm, Old, New>
+where
+    Old: Schema,
+    New: Schema,
+{
+    fn type_migration(
+        &mut self,
+        new_ref: &TypeRef,
+    ) -> Result<Option<&'m TypeMigration<'m>>, LqError> {
+        if let Some(resolved) = self.resolved.get(new_ref) {
+            return Result::Ok(*resolved);
+        }
+        let identifier = self.new.identifier(new_ref)?.into_owned().into_owned();
+        let resolved = self.migration.types.get(&identifier);
+        self.resolved.insert(new_ref.clone(), resolved);
+        Result::Ok(resolved)
+    }
+
+    fn type_identifier(&self, new_ref: &TypeRef) -> String {
+        self.new
+            .identifier(new_ref)
+            .map(|identifier| (*identifier).to_string(Format::SnakeCase))
+            .unwrap_or_else(|_| format!("{}", new_ref))
+    }
+
+    /// Migrates the value (of the old type) to a value of the new type.
+    fn migrate<'v>(
+        &mut self,
+        old_ref: &TypeRef,
+        new_ref: &TypeRef,
+        value: &Value<'v>,
+    ) -> Result<Value<'v>, LqError> {
+        let type_migration = self.type_migration(new_ref)?;
+        if let Some(conversion) = type_migration.and_then(|item| item.conversion.as_ref()) {
+            return conversion(value);
+        }
+        let old_type = self.old.require_type(old_ref)?;
+        let new_type = self.new.require_type(new_ref)?;
+        let migrated = match (old_type, new_type, value) {
+            (AnyType::Option(old), AnyType::Option(new), Value::Option(present)) => {
+                Value::Option(match present {
+                    Option::Some(present) => {
+                        Option::Some(self.migrate(old.r#type(), new.r#type(), present)?.into())
+                    }
+                    Option::None => Option::None,
+                })
+            }
+            // the value is now optional
+            (_, AnyType::Option(new), value) => Value::Option(Option::Some(
+                self.migrate(old_ref, new.r#type(), value)?.into(),
+            )),
+            (AnyType::Struct(old), AnyType::Struct(new), Value::Seq(values)) => {
+                let mut new_values = Vec::with_capacity(new.fields().len());
+                for field in new.fields() {
+                    let renamed =
+                        type_migration.and_then(|item| item.renamed_fields.get(field.name()));
+                    let old_name = renamed.unwrap_or_else(|| field.name());
+                    let old_index = old.fields().iter().position(|item| item.name() == old_name);
+                    let default = type_migration.and_then(|item| item.defaults.get(field.name()));
+                    let new_value = match (old_index, default) {
+                        (Option::Some(old_index), _) if old_index < values.len() => self.migrate(
+                            old.fields()[old_index].r#type(),
+                            field.r#type(),
+                            &values[old_index],
+                        )?,
+                        (_, Option::Some(default)) => default.clone(),
+                        _ if renamed.is_some() => {
+                            return LqError::err_new(format!(
+                                "Field `{}` of type `{}` has been declared as renamed field \
+                                 (old name `{}`) but there's no such field in the old version.",
+                                field.name().to_string(Format::SnakeCase),
+                                self.type_identifier(new_ref),
+                                old_name.to_string(Format::SnakeCase)
+                            ))
+                        }
+                        _ => {
+                            if let AnyType::Option(_) = self.new.require_type(field.r#type())? {
+                                Value::Option(Option::None)
+                            } else {
+                                return LqError::err_new(format!(
+                                    "There's no value for field `{}` of type `{}`: There's no \
+                                     such field in the old version and no default value has \
+                                     been declared.",
+                                    field.name().to_string(Format::SnakeCase),
+                                    self.type_identifier(new_ref)
+                                ));
+                            }
+                        }
+                    };
+                    new_values.push(new_value);
+                }
+                Value::Seq(ValueSeq::Owned(new_values))
+            }
+            (AnyType::Enum(old), AnyType::Enum(new), Value::Enum(variant)) => {
+                let old_variant = old.variants().get(variant.ordinal() as usize);
+                let old_variant = if let Some(old_variant) = old_variant {
+                    old_variant
+                } else {
+                    return LqError::err_new(format!(
+                        "There's no enum variant with ordinal {} in the old version.",
+                        variant.ordinal()
+                    ));
+                };
+                let new_name = type_migration
+                    .and_then(|item| item.variants.get(old_variant.name()))
+                    .unwrap_or_else(|| old_variant.name());
+                let (ordinal, new_variant) = if let Some(found) = new.variant_by_id(new_name) {
+                    found
+                } else {
+                    return LqError::err_new(format!(
+                        "Enum variant `{}` (old version) has no counterpart in type `{}`: Declare \
+                         to what variant it should be mapped.",
+                        old_variant.name().to_string(Format::SnakeCase),
+                        self.type_identifier(new_ref)
+                    ));
+                };
+                let values = variant.values();
+                if values.len() < new_variant.values().len() {
+                    return LqError::err_new(format!(
+                        "Enum variant `{}` of type `{}` needs {} values, have {} values.",
+                        new_name.to_string(Format::SnakeCase),
+                        self.type_identifier(new_ref),
+                        new_variant.values().len(),
+                        values.len()
+                    ));
+                }
+                let mut new_values = Vec::with_capacity(new_variant.values().len());
+                for (index, new_value_type) in new_variant.values().iter().enumerate() {
+                    // the data might contain more values than declared (extension values)
+                    let old_value_type =
+                        if let Some(old_value_type) = old_variant.values().get(index) {
+                            old_value_type
+                        } else {
+                            return LqError::err_new(format!(
+                                "Enum variant `{}` of type `{}` needs {} values, the old version \
+                             only declares {} values: Declare a conversion.",
+                                new_name.to_string(Format::SnakeCase),
+                                self.type_identifier(new_ref),
+                                new_variant.values().len(),
+                                old_variant.values().len()
+                            ));
+                        };
+                    new_values.push(self.migrate(
+                        old_value_type,
+                        new_value_type,
+                        &values[index],
+                    )?);
+                }
+                Value::Enum(ValueVariant::new_values(
+                    ordinal,
+                    ValueSeq::Owned(new_values),
+                ))
+            }
+            (AnyType::Seq(old), AnyType::Seq(new), Value::Seq(elements)) => {
+                let mut new_elements = Vec::with_capacity(elements.len());
+                for element in elements.iter() {
+                    new_elements.push(self.migrate(old.element(), new.element(), element)?);
+                }
+                Value::Seq(ValueSeq::Owned(new_elements))
+            }
+            (AnyType::Map(old), AnyType::Map(new), Value::Seq(entries)) => {
+                self.migrate_entries((old.key(), old.value()), (new.key(), new.value()), entries)?
+            }
+            (AnyType::RootMap(old), AnyType::RootMap(new), Value::Seq(outer))
+                if outer.len() == 2 =>
+            {
+                let entries = if let Value::Seq(entries) = &outer[0] {
+                    entries
+                } else {
+                    return LqError::err_new("Invalid root map (entries are not a sequence).");
+                };
+                let entries = self.migrate_entries(
+                    (old.key(), old.value()),
+                    (new.key(), new.value()),
+                    entries,
+                )?;
+                let root = self.migrate(old.root(), new.root(), &outer[1])?;
+                Value::Seq(ValueSeq::Owned(vec![entries, root]))
+            }
+            (AnyType::Range(old), AnyType::Range(new), Value::Seq(items)) if items.len() >= 2 => {
+                let start = self.migrate(old.element(), new.element(), &items[0])?;
+                let end = self.migrate(old.element(), new.element(), &items[1])?;
+                let inclusion = if old.inclusion() == Inclusion::Supplied {
+                    match (&items[2], &items[3]) {
+                        (Value::Bool(start), Value::Bool(end)) => (*start, *end),
+                        _ => return LqError::err_new("Invalid range (inclusion is not a bool)."),
+                    }
+                } else {
+                    fixed_inclusion(old.inclusion())
+                };
+                let mut new_items = vec![start, end];
+                if new.inclusion() == Inclusion::Supplied {
+                    new_items.push(Value::Bool(inclusion.0));
+                    new_items.push(Value::Bool(inclusion.1));
+                } else if fixed_inclusion(new.inclusion()) != inclusion {
+                    return LqError::err_new(format!(
+                        "Unable to migrate range of type `{}`: The new type has another \
+                         inclusion ({:?}).",
+                        self.type_identifier(new_ref),
+                        new.inclusion()
+                    ));
+                }
+                Value::Seq(ValueSeq::Owned(new_items))
+            }
+            // widening integers (or conversion between signed and unsigned)
+            (AnyType::UInt(_), AnyType::UInt(_), Value::UInt(_))
+            | (AnyType::SInt(_), AnyType::SInt(_), Value::SInt(_)) => value.clone(),
+            (AnyType::UInt(_), AnyType::SInt(_), Value::UInt(int)) => {
+                Value::SInt(i128::try_from(*int)?)
+            }
+            (AnyType::SInt(_), AnyType::UInt(_), Value::SInt(int)) => {
+                Value::UInt(u128::try_from(*int)?)
+            }
+            (AnyType::Float32(_), AnyType::Float64(_), Value::Float(Float::F32(float))) => {
+                Value::Float(Float::F64(f64::from(*float)))
+            }
+            // same data
+            (AnyType::Bool(_), AnyType::Bool(_), _)
+            | (AnyType::Binary(_), AnyType::Binary(_), _)
+            | (AnyType::Unicode(_), AnyType::Unicode(_), _)
+            | (AnyType::Ascii(_), AnyType::Ascii(_), _)
+            | (AnyType::Ascii(_), AnyType::Unicode(_), _)
+            | (AnyType::Unicode(_), AnyType::Ascii(_), _)
+            | (AnyType::Float32(_), AnyType::Float32(_), _)
+            | (AnyType::Float64(_), AnyType::Float64(_), _)
+            | (AnyType::KeyRef(_), AnyType::KeyRef(_), _)
+            | (AnyType::Uuid(_), AnyType::Uuid(_), _)
+            | (AnyType::Decimal(_), AnyType::Decimal(_), _) => value.clone(),
+            (old_type, new_type, _) => {
+                return LqError::err_new(format!(
+                    "Unable to migrate {} data to {} (type `{}`): Declare a conversion for \
+                     this type.",
+                    type_name(old_type),
+                    type_name(new_type),
+                    self.type_identifier(new_ref)
+                ))
+            }
+        };
+        Result::Ok(migrated)
+    }
+
+    fn migrate_entries<'v>(
+        &mut self,
+        old: (&TypeRef, &TypeRef),
+        new: (&TypeRef, &TypeRef),
+        entries: &ValueSeq<'v>,
+    ) -> Result<Value<'v>, LqError> {
+        let mut new_entries = Vec::with_capacity(entries.len());
+        for entry in entries.iter() {
+            if let Value::Seq(entry) = entry {
+                if entry.len() == 2 {
+                    let key = self.migrate(old.0, new.0, &entry[0])?;
+                    let value = self.migrate(old.1, new.1, &entry[1])?;
+                    new_entries.push(Value::Seq(ValueSeq::Owned(vec![key, value])));
+                    continue;
+                }
+            }
+            return LqError::err_new("Invalid map entry (must be a sequence with 2 elements).");
+        }
+        Result::Ok(Value::Seq(ValueSeq::Owned(new_entries)))
+    }
+
+    /// Sorts maps and sorted sequences according to the new schema and updates the key
+    /// references.
+    fn canonicalize<'v>(
+        &mut self,
+        new_ref: &TypeRef,
+        value: Value<'v>,
+    ) -> Result<Value<'v>, LqError> {
+        let new_type = self.new.require_type(new_ref)?;
+        let canonical = match (new_type, value) {
+            (AnyType::Option(new), Value::Option(Option::Some(present))) => Value::Option(
+                Option::Some(self.canonicalize(new.r#type(), into_value(present))?.into()),
+            ),
+            (AnyType::Struct(new), Value::Seq(values)) => {
+                let mut canonical = Vec::with_capacity(values.len());
+                for (field, value) in new.fields().iter().zip(into_vec(values)) {
+                    canonical.push(self.canonicalize(field.r#type(), value)?);
+                }
+                Value::Seq(ValueSeq::Owned(canonical))
+            }
+            (AnyType::Enum(new), Value::Enum(variant)) => {
+                let ordinal = variant.ordinal();
+                let value_types = if let Some(new_variant) = new.variants().get(ordinal as usize) {
+                    new_variant.values()
+                } else {
+                    return LqError::err_new(format!(
+                        "There's no enum variant with ordinal {} in type `{}` (migrated data).",
+                        ordinal,
+                        self.type_identifier(new_ref)
+                    ));
+                };
+                let mut canonical = Vec::with_capacity(value_types.len());
+                for (value_type, value) in value_types.iter().zip(variant.values().iter()) {
+                    canonical.push(self.canonicalize(value_type, value.clone())?);
+                }
+                Value::Enum(ValueVariant::new_values(
+                    ordinal,
+                    ValueSeq::Owned(canonical),
+                ))
+            }
+            (AnyType::Seq(new), Value::Seq(elements)) => {
+                let mut canonical = Vec::with_capacity(elements.len());
+                for element in into_vec(elements) {
+                    canonical.push(self.canonicalize(new.element(), element)?);
+                }
+                if let SeqOrdering::Sorted(sorted) = new.ordering() {
+                    let descending = sorted.direction == Direction::Descending;
+                    let order = self.sort_order(new.element(), &canonical, descending)?;
+                    canonical = reorder(canonical, &order);
+                }
+                Value::Seq(ValueSeq::Owned(canonical))
+            }
+            (AnyType::Map(new), Value::Seq(entries)) => self.canonicalize_map(
+                (new.key(), new.value()),
+                new.sorting(),
+                new.anchors(),
+                into_vec(entries),
+                None,
+            )?,
+            (AnyType::RootMap(new), Value::Seq(outer)) => {
+                let mut outer = into_vec(outer);
+                let root = outer.pop();
+                let entries = outer.pop();
+                match (entries, root) {
+                    (Some(Value::Seq(entries)), Some(root)) => self.canonicalize_map(
+                        (new.key(), new.value()),
+                        new.sorting(),
+                        true,
+                        into_vec(entries),
+                        Some((new.root(), root)),
+                    )?,
+                    _ => return LqError::err_new("Invalid root map."),
+                }
+            }
+            (AnyType::KeyRef(new), Value::UInt(index)) => {
+                let len = self.key_ref_mappings.len();
+                let level = new.level() as usize;
+                if level < len {
+                    let mapping = &self.key_ref_mappings[len - level - 1];
+                    match usize::try_from(index)
+                        .ok()
+                        .and_then(|index| mapping.get(index))
+                    {
+                        Option::Some(new_index) => Value::UInt(u128::from(*new_index)),
+                        Option::None => Value::UInt(index),
+                    }
+                } else {
+                    Value::UInt(index)
+                }
+            }
+            (AnyType::Range(new), Value::Seq(items)) => {
+                let mut items = into_vec(items);
+                for item in items.iter_mut().take(2) {
+                    let element = std::mem::replace(item, Value::Bool(false));
+                    *item = self.canonicalize(new.element(), element)?;
+                }
+                Value::Seq(ValueSeq::Owned(items))
+            }
+            (_, value) => value,
+        };
+        Result::Ok(canonical)
+    }
+
+    /// Sorts the entries of a map (and updates key references when the map has anchors). For
+    /// root maps, `root` is the root type and the root value.
+    fn canonicalize_map<'v>(
+        &mut self,
+        (key_type, value_type): (&TypeRef, &TypeRef),
+        sorting: Sorting,
+        anchors: bool,
+        entries: Vec<Value<'v>>,
+        root: Option<(&TypeRef, Value<'v>)>,
+    ) -> Result<Value<'v>, LqError> {
+        let mut keys = Vec::with_capacity(entries.len());
+        let mut values = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let mut entry = match entry {
+                Value::Seq(entry) if entry.len() == 2 => into_vec(entry),
+                _ => return LqError::err_new("Invalid map entry."),
+            };
+            values.push(entry.pop().unwrap());
+            keys.push(entry.pop().unwrap());
+        }
+        // keys cannot reference keys of the same map
+        let mut canonical_keys = Vec::with_capacity(keys.len());
+        for key in keys {
+            canonical_keys.push(self.canonicalize(key_type, key)?);
+        }
+        let order = self.sort_order(key_type, &canonical_keys, sorting == Sorting::Descending)?;
+
+        if anchors {
+            let mut mapping = vec![0u32; order.len()];
+            for (new_index, old_index) in order.iter().enumerate() {
+                mapping[*old_index] = u32::try_from(new_index)?;
+            }
+            self.key_ref_mappings.push(mapping);
+        }
+        let mut canonical_values = Vec::with_capacity(values.len());
+        for value in values {
+            canonical_values.push(self.canonicalize(value_type, value)?);
+        }
+        let root = if let Some((root_type, root_value)) = root {
+            Some(self.canonicalize(root_type, root_value)?)
+        } else {
+            None
+        };
+        if anchors {
+            self.key_ref_mappings.pop();
+        }
+
+        let entries: Vec<_> = canonical_keys
+            .into_iter()
+            .zip(canonical_values)
+            .map(|(key, value)| Value::Seq(ValueSeq::Owned(vec![key, value])))
+            .collect();
+        let entries = Value::Seq(ValueSeq::Owned(reorder(entries, &order)));
+        Result::Ok(if let Some(root) = root {
+            Value::Seq(ValueSeq::Owned(vec![entries, root]))
+        } else {
+            entries
+        })
+    }
+
+    /// Returns the indexes of the values in sorted order (sorted according to the new
+    /// schema).
+    fn sort_order(
+        &self,
+        type_ref: &TypeRef,
+        values: &[Value],
+        descending: bool,
+    ) -> Result<Vec<usize>, LqError> {
+        let serialized = values
+            .iter()
+            .map(serialize)
+            .collect::<Result<Vec<_>, LqError>>()?;
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        let mut error = None;
+        order.sort_by(|index1, index2| {
+            let mut reader1 = SliceReader::from(&serialized[*index1]);
+            let mut reader2 = SliceReader::from(&serialized[*index2]);
+            match self.new.compare(type_ref, &mut reader1, &mut reader2) {
+                Result::Ok(ordering) if descending => ordering.reverse(),
+                Result::Ok(ordering) => ordering,
+                Result::Err(err) => {
+                    error.get_or_insert(err);
+                    Ordering::Equal
+                }
+            }
+        });
+        if let Some(err) = error {
+            Result::Err(err)
+        } else {
+            Result::Ok(order)
+        }
+    }
+}
+
+/// Start and end inclusion of ranges where the inclusion is not supplied in the data.
+fn fixed_inclusion(inclusion: Inclusion) -> (bool, bool) {
+    match inclusion {
+        Inclusion::BothInclusive | Inclusion::Supplied => (true, true),
+        Inclusion::StartInclusive => (true, false),
+        Inclusion::BothExclusive => (false, false),
+        Inclusion::EndInclusive => (false, true),
+    }
+}
+
+fn serialize(value: &Value) -> Result<Vec<u8>, LqError> {
+    let mut writer = VecWriter::default();
+    Value::serialize(&mut writer, value)?;
+    Result::Ok(writer.into_vec())
+}
+
+fn into_vec(seq: ValueSeq) -> Vec<Value> {
+    match seq {
+        ValueSeq::Owned(values) => values,
+        ValueSeq::Borrowed(values) => values.to_vec(),
+        ValueSeq::Empty => Vec::new(),
+    }
+}
+
+fn into_value(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Boxed(value) => *value,
+        ValueRef::Borrowed(value) => value.clone(),
+    }
+}
+
+/// Reorders the values: `order` contains the indexes of the values in the new order.
+fn reorder<T>(values: Vec<T>, order: &[usize]) -> Vec<T> {
+    let mut values: Vec<Option<T>> = values.into_iter().map(Some).collect();
+    order
+        .iter()
+        .map(|index| values[*index].take().unwrap())
+        .collect()
+}
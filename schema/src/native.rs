@@ -0,0 +1,228 @@
+//! Schemas for Rust's own types (`BuildsOwnSchema`). Integers get their natural range, texts,
+//! binaries and sequences have no length limit. This is what the `LqSchema` derive macro uses
+//! for the fields; the encoding matches `liquesco_serialization::types::native`.
+//!
+//! Types containing other types (`Option<T>`, `Vec<T>`, ...) derive the identifier from the
+//! identifiers of the contained types: Each contained identifier is preceded by its number of
+//! segments (e.g. `option_1_u32`, `seq_3_option_1_u32`, `map_1_u8_2_foo_bar`). This makes the
+//! identifiers unambiguous; they cannot collide with each other or with identifiers without
+//! segments consisting of digits only (such as the identifiers of types deriving
+//! `LqSchema`). If such an identifier gets too long (types nested too deeply), the error is
+//! returned by `SchemaBuilder::finish` (see `SchemaBuilder::add_or_defer`).
+
+use crate::core::TypeRef;
+use crate::identifier::StrIdentifier;
+use crate::schema_builder::{BuildsOwnSchema, SchemaBuilder};
+use crate::types::binary::TBinary;
+use crate::types::boolean::TBool;
+use crate::types::float::{TFloat32, TFloat64};
+use crate::types::map::TMap;
+use crate::types::option::TOption;
+use crate::types::seq::{Direction, Sorted, TSeq};
+use crate::types::sint::TSInt;
+use crate::types::uint::TUInt;
+use crate::types::unicode::{LengthType, TUnicode};
+use crate::types::uuid::TUuid;
+use liquesco_common::error::LqError;
+use liquesco_common::float::{F32Ext, F64Ext};
+use liquesco_serialization::types::uuid::Uuid;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+
+macro_rules! uint_schema {
+    ($ty:ty, $id:expr) => {
+        impl BuildsOwnSchema for $ty {
+            fn build_schema<B>(builder: &mut B) -> TypeRef
+            where
+                B: SchemaBuilder<'static>,
+            {
+                builder.add_unwrap($id, TUInt::try_new(<$ty>::MIN, <$ty>::MAX).unwrap())
+            }
+        }
+    };
+}
+
+macro_rules! sint_schema {
+    ($ty:ty, $id:expr) => {
+        impl BuildsOwnSchema for $ty {
+            fn build_schema<B>(builder: &mut B) -> TypeRef
+            where
+                B: SchemaBuilder<'static>,
+            {
+                builder.add_unwrap($id, TSInt::try_new(<$ty>::MIN, <$ty>::MAX).unwrap())
+            }
+        }
+    };
+}
+
+uint_schema!(u8, "u8");
+uint_schema!(u16, "u16");
+uint_schema!(u32, "u32");
+uint_schema!(u64, "u64");
+uint_schema!(u128, "u128");
+sint_schema!(i8, "i8");
+sint_schema!(i16, "i16");
+sint_schema!(i32, "i32");
+sint_schema!(i64, "i64");
+sint_schema!(i128, "i128");
+
+impl BuildsOwnSchema for bool {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        builder.add_unwrap("bool", TBool::default())
+    }
+}
+
+/// Any 32 bit float (including NaN, infinity and subnormal values).
+impl BuildsOwnSchema for f32 {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        let float = TFloat32::try_new(F32Ext::from(f32::MIN), F32Ext::from(f32::MAX))
+            .unwrap()
+            .with_allow_positive_zero(true)
+            .with_allow_negative_zero(true)
+            .with_allow_nan(true)
+            .with_allow_positive_infinity(true)
+            .with_allow_negative_infinity(true)
+            .with_allow_subnormal(true);
+        builder.add_unwrap("f32", float)
+    }
+}
+
+/// Any 64 bit float (including NaN, infinity and subnormal values).
+impl BuildsOwnSchema for f64 {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        let float = TFloat64::try_new(F64Ext::from(f64::MIN), F64Ext::from(f64::MAX))
+            .unwrap()
+            .with_allow_positive_zero(true)
+            .with_allow_negative_zero(true)
+            .with_allow_nan(true)
+            .with_allow_positive_infinity(true)
+            .with_allow_negative_infinity(true)
+            .with_allow_subnormal(true);
+        builder.add_unwrap("f64", float)
+    }
+}
+
+impl BuildsOwnSchema for String {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        builder.add_unwrap(
+            "unicode",
+            TUnicode::try_new(0, u64::MAX, LengthType::Utf8Byte).unwrap(),
+        )
+    }
+}
+
+impl BuildsOwnSchema for &str {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        String::build_schema(builder)
+    }
+}
+
+impl BuildsOwnSchema for &[u8] {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        builder.add_unwrap("binary", TBinary::try_new(0, u64::MAX).unwrap())
+    }
+}
+
+impl BuildsOwnSchema for Uuid {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        builder.add_unwrap("uuid", TUuid::default())
+    }
+}
+
+impl<T: BuildsOwnSchema> BuildsOwnSchema for Box<T> {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        T::build_schema(builder)
+    }
+}
+
+impl<T: BuildsOwnSchema> BuildsOwnSchema for Option<T> {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        let present = T::build_schema(builder);
+        let id = composed_id("option", &[&present]);
+        builder.add_or_defer(id, TOption::new(present))
+    }
+}
+
+impl<T: BuildsOwnSchema> BuildsOwnSchema for Vec<T> {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        let element = T::build_schema(builder);
+        let id = composed_id("seq", &[&element]);
+        builder.add_or_defer(id, TSeq::try_new(element, 0, u32::MAX).unwrap())
+    }
+}
+
+/// A set is a sorted (ascending) sequence with unique elements.
+impl<T: BuildsOwnSchema> BuildsOwnSchema for BTreeSet<T> {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        let element = T::build_schema(builder);
+        let id = composed_id("set", &[&element]);
+        let seq = TSeq::try_new(element, 0, u32::MAX)
+            .unwrap()
+            .with_sorted(Sorted {
+                direction: Direction::Ascending,
+                unique: true,
+            });
+        builder.add_or_defer(id, seq)
+    }
+}
+
+impl<K: BuildsOwnSchema, V: BuildsOwnSchema> BuildsOwnSchema for BTreeMap<K, V> {
+    fn build_schema<B>(builder: &mut B) -> TypeRef
+    where
+        B: SchemaBuilder<'static>,
+    {
+        let key = K::build_schema(builder);
+        let value = V::build_schema(builder);
+        let id = composed_id("map", &[&key, &value]);
+        builder.add_or_defer(id, TMap::new(key, value))
+    }
+}
+
+/// Composes an identifier from a prefix and the identifiers of the contained types (see module
+/// documentation). Returns an error if the identifier gets too long.
+fn composed_id(prefix: &str, contained: &[&TypeRef]) -> Result<StrIdentifier<'static>, LqError> {
+    let mut id = prefix.to_string();
+    for reference in contained {
+        let contained_id = match reference {
+            TypeRef::Identifier(identifier) => Cow::Borrowed(identifier.as_string()),
+            TypeRef::Numerical(number) => Cow::Owned(format!("t{}", number)),
+        };
+        let number_of_segments = contained_id.split('_').count();
+        id.push_str(&format!("_{}_{}", number_of_segments, contained_id));
+    }
+    StrIdentifier::try_from(Cow::Owned(id))
+}
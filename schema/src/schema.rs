@@ -12,15 +12,20 @@ use crate::schema_builder::{BuildsOwnSchema, SchemaBuilder};
 use crate::type_hash::TypeHash;
 use crate::types::key_ref::TKeyRef;
 use crate::types::root_map::TRootMap;
+use crate::validation_error::{
+    PathSegment, ValidationError, ValidationErrorKind, ValidationResult,
+};
 use liquesco_common::error::LqError;
 use liquesco_serialization::core::DeSerializer;
 use liquesco_serialization::core::LqReader;
+use liquesco_serialization::io_reader::{IoReader, SliceArena};
 use liquesco_serialization::value::Value;
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::hash::Hasher;
+use std::io::Read;
 use std::marker::PhantomData;
 
 /// Builds the liquesco schema schema.
@@ -140,6 +145,80 @@ impl<'a, C: TypeContainer + Clone> DefaultSchema<'a, C> {
         self.extended_diagnostics = enabled;
     }
 
+    /// Validates data read from a forward-only stream (e.g. a file, a pipe or a socket) without
+    /// reading all data into memory. The data must be consumed entirely.
+    ///
+    /// Memory usage does not depend on the total document size: Only the data required to
+    /// check sorting and uniqueness is retained (the previous element of sorted sequences;
+    /// the previous entry of maps). `max_buffer_size` limits the number of bytes buffered;
+    /// validation fails if data exceeds that limit (e.g. if two consecutive elements of a
+    /// sorted sequence are larger than the buffer). Note: Extended diagnostics are not
+    /// available in this mode (they would require the entire data to be retained).
+    pub fn validate_stream<Rd: Read>(
+        &self,
+        config: Config,
+        read: Rd,
+        max_buffer_size: usize,
+    ) -> Result<(), LqError> {
+        let arena = SliceArena::new();
+        let mut reader = IoReader::new(read, &arena).with_max_buffer_size(max_buffer_size);
+        {
+            let type_container: &C = &self.types;
+            let mut context = DefaultValidationContext {
+                types: type_container,
+                config,
+                reader: &mut reader,
+                extended_diagnostics: false,
+                key_ref_info: SmallVec::new(),
+                collected: None,
+                _phantom1: &PhantomData,
+                _phantom2: &PhantomData,
+            };
+            context
+                .validate(self.types.root())
+                .map_err(ValidationError::finish)?;
+        }
+        reader.finish()
+    }
+
+    /// Validates the data and returns all errors found (instead of stopping at the first
+    /// error); returns an empty vector if the data is valid. Invalid values are skipped (using
+    /// the content description) and validation continues with the next value. Validation
+    /// stops once `max_errors` errors have been found or if an invalid value cannot be
+    /// skipped (malformed data).
+    pub fn validate_all<'r, R: LqReader<'r>>(
+        &self,
+        config: Config,
+        reader: &mut R,
+        max_errors: usize,
+    ) -> Vec<LqError> {
+        let type_container: &C = &self.types;
+        let mut context = DefaultValidationContext {
+            types: type_container,
+            config,
+            reader,
+            extended_diagnostics: self.extended_diagnostics,
+            key_ref_info: SmallVec::new(),
+            collected: Some(Collected {
+                errors: Vec::new(),
+                max_errors,
+                aborted: false,
+            }),
+            _phantom1: &PhantomData,
+            _phantom2: &PhantomData,
+        };
+        let result = context.validate(self.types.root());
+        let collected = context.collected.take().unwrap();
+        let mut errors = collected.errors;
+        if let Err(err) = result {
+            // when aborted, the error has already been collected
+            if !collected.aborted {
+                errors.push(err);
+            }
+        }
+        errors.into_iter().map(ValidationError::finish).collect()
+    }
+
     #[inline]
     fn validate_internal<'c, 'r, R: LqReader<'r>>(
         &'c self,
@@ -153,10 +232,13 @@ impl<'a, C: TypeContainer + Clone> DefaultSchema<'a, C> {
             reader,
             extended_diagnostics: self.extended_diagnostics,
             key_ref_info: SmallVec::new(),
+            collected: None,
             _phantom1: &PhantomData,
             _phantom2: &PhantomData,
         };
-        context.validate(self.types.root())
+        context
+            .validate(self.types.root())
+            .map_err(ValidationError::finish)
     }
 }
 
@@ -168,10 +250,47 @@ struct DefaultValidationContext<'s, 'c, 'r, C: TypeContainer, R: LqReader<'r>> {
     /// The key ref info. Note: We use a smallvec of 4, since it's very rare that there are
     /// ever more than 4 levels.
     key_ref_info: SmallVec<[KeyRefInfo; 4]>,
+    /// Only present when collecting all errors (see `DefaultSchema::validate_all`).
+    collected: Option<Collected>,
     _phantom1: &'c PhantomData<()>,
     _phantom2: &'r PhantomData<()>,
 }
 
+/// Errors collected by `DefaultSchema::validate_all`.
+struct Collected {
+    errors: Vec<LqError>,
+    max_errors: usize,
+    /// True if validation cannot continue (too many errors or the data is malformed).
+    aborted: bool,
+}
+
+impl<'s, 'c, 'r, C: TypeContainer, R: LqReader<'r>> DefaultValidationContext<'s, 'c, 'r, C, R> {
+    /// Collects the error and skips the invalid value (so validation can continue with the
+    /// next value). `reader` and `key_ref_info_len` is the state before the invalid value.
+    fn collect(&mut self, err: LqError, reader: R, key_ref_info_len: usize) -> Result<(), LqError> {
+        let collected = self.collected.as_mut().unwrap();
+        if collected.aborted {
+            return Result::Err(err);
+        }
+        collected.errors.push(err);
+        if collected.errors.len() >= collected.max_errors {
+            collected.aborted = true;
+            return LqError::err_new(format!(
+                "Too many validation errors (maximum is {}).",
+                collected.max_errors
+            ));
+        }
+
+        *self.reader = reader;
+        self.key_ref_info.truncate(key_ref_info_len);
+        if let Err(err) = self.reader.skip() {
+            collected.aborted = true;
+            return Result::Err(err);
+        }
+        Result::Ok(())
+    }
+}
+
 impl<'s, 'c, 'r, C: TypeContainer, R: LqReader<'r>> CmpContext<'r>
     for DefaultValidationContext<'s, 'c, 'r, C, R>
 {
@@ -199,8 +318,55 @@ impl<'s, 'c, 'r, C: TypeContainer, R: LqReader<'r>> ValidationContext<'r>
     for DefaultValidationContext<'s, 'c, 'r, C, R>
 {
     fn validate(&mut self, reference: &TypeRef) -> Result<(), LqError> {
-        let any_type = self.types.require_type(reference)?;
-        self.validate_any_type(any_type)
+        let any_type = self
+            .types
+            .require_type(reference)
+            .kind(ValidationErrorKind::UnknownType)?;
+        let offset = self.reader.read_offset();
+        let saved_state = if self.collected.is_some() {
+            Some((self.reader.clone(), self.key_ref_info.len()))
+        } else {
+            None
+        };
+        let result = self.validate_any_type(any_type).map_err(|err| {
+            let type_identifier = self
+                .types
+                .identifier(reference)
+                .ok()
+                .map(|identifier| identifier.into_owned().into_owned());
+            ValidationError::attach(err, type_identifier, offset)
+        });
+        match (result, saved_state) {
+            (Err(err), Some((reader, key_ref_info_len))) => {
+                self.collect(err, reader, key_ref_info_len)
+            }
+            (result, _) => result,
+        }
+    }
+
+    fn validate_nested<F>(&mut self, reference: &TypeRef, segment: F) -> Result<(), LqError>
+    where
+        F: FnOnce() -> PathSegment,
+    {
+        let number_of_errors = self
+            .collected
+            .as_ref()
+            .map_or(0, |collected| collected.errors.len());
+        let result = self.validate(reference);
+        match &mut self.collected {
+            Some(collected) if collected.errors.len() > number_of_errors => {
+                // the collected errors of the nested value need the segment too
+                let segment = segment();
+                let nested_errors = collected.errors.split_off(number_of_errors);
+                collected.errors.extend(
+                    nested_errors
+                        .into_iter()
+                        .map(|err| ValidationError::prepend(err, segment.clone())),
+                );
+                result.in_path(|| segment)
+            }
+            _ => result.in_path(segment),
+        }
     }
 
     fn validate_any_type(&mut self, any_type: &AnyType) -> Result<(), LqError> {
@@ -7,7 +7,7 @@ use crate::type_container::DefaultTypeContainer;
 use crate::types::structure::TStruct;
 use liquesco_common::error::LqError;
 use std::borrow::Cow;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
 
 pub trait SchemaBuilder<'a> {
@@ -27,6 +27,31 @@ pub trait SchemaBuilder<'a> {
         self.add(identifier, item).unwrap()
     }
 
+    /// Like `add` but for `BuildsOwnSchema` implementations (these cannot return errors): If
+    /// the identifier is invalid or the type cannot be added, the error is returned by `finish`
+    /// (the reference returned must not be used in this case). The default implementation
+    /// panics instead.
+    fn add_or_defer<T: Into<AnyType<'a>>>(
+        &mut self,
+        id: Result<StrIdentifier<'static>, LqError>,
+        item: T,
+    ) -> TypeRef {
+        self.add(id.unwrap(), item).unwrap()
+    }
+
+    /// Called by `BuildsOwnSchema` implementations of types that might reference themselves
+    /// (recursive types) before building the type. Returns a reference to the type (by
+    /// identifier) if that type is already being built: In this case the type must not be
+    /// built again (infinite recursion); just return the reference. Otherwise returns `None`
+    /// and `end_type` has to be called once the type has been added. The default
+    /// implementation always returns `None` (builders that do not support recursive types).
+    fn begin_type(&mut self, _id: &StrIdentifier<'static>) -> Option<TypeRef> {
+        None
+    }
+
+    /// See `begin_type`. The default implementation does nothing.
+    fn end_type(&mut self, _id: &StrIdentifier<'static>) {}
+
     /// Finishes this builder by providing the root type.
     fn finish(self, root: TypeRef) -> Result<Self::TTypeContainer, LqError>;
 }
@@ -57,12 +82,18 @@ pub(crate) trait BaseTypeSchemaBuilder {
 /// with the same ID.
 pub struct DefaultSchemaBuilder<'a> {
     types: BTreeMap<StrIdentifier<'static>, AnyType<'a>>,
+    /// Types currently being built (see `begin_type`).
+    building: HashSet<StrIdentifier<'static>>,
+    /// The first error of `add_or_defer` (returned by `finish`).
+    error: Option<LqError>,
 }
 
 impl<'a> Default for DefaultSchemaBuilder<'a> {
     fn default() -> Self {
         Self {
             types: BTreeMap::new(),
+            building: HashSet::new(),
+            error: None,
         }
     }
 }
@@ -93,7 +124,38 @@ impl<'a> SchemaBuilder<'a> for DefaultSchemaBuilder<'a> {
         Ok(TypeRef::Identifier(id))
     }
 
+    fn add_or_defer<T: Into<AnyType<'a>>>(
+        &mut self,
+        id: Result<StrIdentifier<'static>, LqError>,
+        item: T,
+    ) -> TypeRef {
+        match id.and_then(|id| self.add(id, item)) {
+            Ok(reference) => reference,
+            Err(err) => {
+                if self.error.is_none() {
+                    self.error = Some(err);
+                }
+                TypeRef::new_numerical(u32::MAX)
+            }
+        }
+    }
+
+    fn begin_type(&mut self, id: &StrIdentifier<'static>) -> Option<TypeRef> {
+        if self.building.insert(id.clone()) {
+            None
+        } else {
+            Some(TypeRef::Identifier(id.clone()))
+        }
+    }
+
+    fn end_type(&mut self, id: &StrIdentifier<'static>) {
+        self.building.remove(id);
+    }
+
     fn finish(self, root: TypeRef) -> Result<Self::TTypeContainer, LqError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
         let len = self.types.len();
 
         // First collect indexes / decompose types
@@ -38,6 +38,16 @@ impl<'a> DefaultTypeContainer<'a> {
             cache: RefCell::new(HashCache::default()),
         }
     }
+
+    /// All types in this container (reference; identifier; type).
+    pub fn types(&self) -> impl Iterator<Item = (TypeRef, &Identifier<'a>, &AnyType<'a>)> {
+        self.types
+            .iter()
+            .enumerate()
+            .map(|(index, (identifier, any_type))| {
+                (TypeRef::new_numerical(index as u32), identifier, any_type)
+            })
+    }
 }
 
 impl<'a> TypeContainer for DefaultTypeContainer<'a> {
@@ -101,28 +111,12 @@ impl<'a> TypeContainer for DefaultTypeContainer<'a> {
         information: Information,
         state: &mut H,
     ) -> Result<(), LqError> {
-        let any_type = self.require_type(reference)?;
-        let vec = if let Some(reduced_metadata) = any_type.meta().reduce_information(information) {
-            let mut cloned_any = any_type.clone();
-            cloned_any.set_meta(reduced_metadata);
-            serialize_to_vec(&cloned_any)?
-        } else {
-            serialize_to_vec(any_type)?
+        let mut hashing = Hashing {
+            information,
+            components: components(self, reference)?,
+            hashes: HashMap::new(),
         };
-
-        vec.hash(state);
-
-        // Do the same for all dependencies
-        let mut index = 0;
-        while let Some(reference) = any_type.reference(index) {
-            self.hash_type(reference, information, state)?;
-            index = index + 1;
-        }
-
-        // write number of dependencies as u64
-        let number_of_dependencies: u64 = index.try_into()?;
-        number_of_dependencies.hash(state);
-
+        self.hash_entry(reference, &mut hashing)?.hash(state);
         Ok(())
     }
 
@@ -165,6 +159,149 @@ impl<'a> TypeContainer for DefaultTypeContainer<'a> {
     }
 }
 
+/// State of `TypeContainer::hash_type`.
+struct Hashing {
+    information: Information,
+    /// The strongly connected component of every type reachable from the type hashed.
+    components: HashMap<TypeRef, usize>,
+    /// The hashes of the types computed so far (see `DefaultTypeContainer::hash_entry`).
+    hashes: HashMap<TypeRef, TypeHash>,
+}
+
+impl<'a> DefaultTypeContainer<'a> {
+    /// Hashes the type graph starting at given type. Types that are not part of a cycle are
+    /// hashed once; their hash is used for every reference (so a schema that shares a type has
+    /// the same hash as a schema with two equal copies of that type). The types of a cycle
+    /// (strongly connected component) are hashed in one go (see `hash_component`) - once for
+    /// every type the cycle is entered at.
+    fn hash_entry(&self, reference: &TypeRef, hashing: &mut Hashing) -> Result<TypeHash, LqError> {
+        if let Some(hash) = hashing.hashes.get(reference) {
+            return Ok(hash.clone());
+        }
+        let mut hasher = TypeHasher::default();
+        self.hash_component(reference, hashing, &mut hasher, &mut HashMap::new())?;
+        let hash = hasher.finish();
+        hashing.hashes.insert(reference.clone(), hash.clone());
+        Ok(hash)
+    }
+
+    /// Hashes the type and (recursively) all types of the same strongly connected component
+    /// not yet hashed. `visited` contains those types (and the order they have been visited
+    /// in); references to them are hashed by that number. References to types of other
+    /// components are hashed by the hash of that type (see `hash_entry`).
+    fn hash_component<H: Hasher>(
+        &self,
+        reference: &TypeRef,
+        hashing: &mut Hashing,
+        state: &mut H,
+        visited: &mut HashMap<TypeRef, u64>,
+    ) -> Result<(), LqError> {
+        let number: u64 = visited.len().try_into()?;
+        visited.insert(reference.clone(), number);
+
+        let any_type = self.require_type(reference)?;
+        let mut cloned_any = any_type.clone();
+        if let Some(reduced_metadata) = any_type.meta().reduce_information(hashing.information) {
+            cloned_any.set_meta(reduced_metadata);
+        }
+        // the references are hashed separately (see below); so the hash does not depend on
+        // the numbering of the types in this container.
+        let mut number_of_dependencies = 0;
+        while cloned_any.reference(number_of_dependencies).is_some() {
+            cloned_any.set_reference(number_of_dependencies, TypeRef::new_numerical(0))?;
+            number_of_dependencies += 1;
+        }
+        serialize_to_vec(&cloned_any)?.hash(state);
+
+        let component = hashing.components.get(reference).cloned();
+        for index in 0..number_of_dependencies {
+            let dependency = any_type.reference(index).unwrap();
+            if hashing.components.get(dependency).cloned() != component {
+                HASH_NEW_REFERENCE.hash(state);
+                self.hash_entry(dependency, hashing)?.hash(state);
+            } else if let Some(number) = visited.get(dependency) {
+                HASH_BACK_REFERENCE.hash(state);
+                number.hash(state);
+            } else {
+                HASH_NEW_REFERENCE.hash(state);
+                self.hash_component(dependency, hashing, state, visited)?;
+            }
+        }
+
+        // write number of dependencies as u64
+        let number_of_dependencies: u64 = number_of_dependencies.try_into()?;
+        number_of_dependencies.hash(state);
+
+        Ok(())
+    }
+}
+
+/// Computes the strongly connected components of all types reachable from given type
+/// (Tarjan's algorithm). Returns the component of each type.
+fn components<C: TypeContainer>(
+    container: &C,
+    reference: &TypeRef,
+) -> Result<HashMap<TypeRef, usize>, LqError> {
+    let mut tarjan = Tarjan::default();
+    tarjan.visit(container, reference)?;
+    Ok(tarjan.components)
+}
+
+#[derive(Default)]
+struct Tarjan {
+    /// Order in which the types have been visited.
+    indexes: HashMap<TypeRef, usize>,
+    stack: Vec<TypeRef>,
+    components: HashMap<TypeRef, usize>,
+    number_of_components: usize,
+}
+
+impl Tarjan {
+    fn visit<C: TypeContainer>(
+        &mut self,
+        container: &C,
+        reference: &TypeRef,
+    ) -> Result<usize, LqError> {
+        let index = self.indexes.len();
+        self.indexes.insert(reference.clone(), index);
+        self.stack.push(reference.clone());
+        let mut low_link = index;
+
+        let any_type = container.require_type(reference)?;
+        let mut dependency_index = 0;
+        while let Some(dependency) = any_type.reference(dependency_index) {
+            if let Some(dependency_index) = self.indexes.get(dependency) {
+                // on the stack: part of the current component
+                if !self.components.contains_key(dependency) {
+                    low_link = low_link.min(*dependency_index);
+                }
+            } else {
+                low_link = low_link.min(self.visit(container, dependency)?);
+            }
+            dependency_index += 1;
+        }
+
+        if low_link == index {
+            // root of a component
+            while let Some(member) = self.stack.pop() {
+                let is_root = &member == reference;
+                self.components.insert(member, self.number_of_components);
+                if is_root {
+                    break;
+                }
+            }
+            self.number_of_components += 1;
+        }
+        Ok(low_link)
+    }
+}
+
+/// Written before a reference to a type that's hashed (see `hash_type`).
+const HASH_NEW_REFERENCE: u8 = 0;
+/// Written before a reference to a type of the same cycle that has already been hashed (see
+/// `hash_type`).
+const HASH_BACK_REFERENCE: u8 = 1;
+
 #[derive(Clone, Debug)]
 struct HashCache {
     entries: HashMap<CacheEntry, TypeHash>,
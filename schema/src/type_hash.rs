@@ -1,19 +1,26 @@
+use crate::identifier::Identifier;
 use blake2::digest::{Input, VariableOutput};
 use blake2::VarBlake2b;
+use liquesco_common::error::LqError;
+use std::convert::TryFrom;
 use std::fmt::{Display, Error, Formatter};
 use std::hash::Hasher;
+use std::ops::Deref;
 
 const TH_LEN: usize = 16;
 
 /// The hash of a type.
-#[derive(Eq, PartialEq, Debug, Clone)]
+#[derive(Eq, PartialEq, Hash, Debug, Clone)]
 pub struct TypeHash([u8; TH_LEN]);
 
+/// Prefix of the first segment when converted to an identifier.
+const IDENTIFIER_PREFIX: &str = "th0x";
+
 /// The default format is the lower hex format.
 impl Display for TypeHash {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         for byte in &self.0 {
-            std::fmt::LowerHex::fmt(byte, f)?;
+            write!(f, "{:02x}", byte)?;
         }
         Ok(())
     }
@@ -25,8 +32,56 @@ impl Into<String> for &TypeHash {
     }
 }
 
-// TODO: Into<Identifier> ('th0x_SEG1_SEG2')
-// TODO: From<String> (lower hex)
+/// Parses the lower hex format (see `Display`).
+impl TryFrom<&str> for TypeHash {
+    type Error = LqError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let is_lower_hex = value
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte));
+        if value.len() != TH_LEN * 2 || !is_lower_hex {
+            return LqError::err_new(format!(
+                "A type hash has to be {} lower hex characters; got {:?}.",
+                TH_LEN * 2,
+                value
+            ));
+        }
+        let mut result = [0u8; TH_LEN];
+        for (index, byte) in result.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&value[index * 2..index * 2 + 2], 16)?;
+        }
+        Ok(TypeHash(result))
+    }
+}
+
+/// The identifier looks like this: `th0x_SEG1_SEG2` (where `SEG1` and `SEG2` are the first
+/// and the second half of the lower hex format; a segment cannot hold all 32 characters).
+impl From<&TypeHash> for Identifier<'static> {
+    fn from(value: &TypeHash) -> Self {
+        let hex = value.to_string();
+        let (first, second) = hex.split_at(TH_LEN);
+        Identifier::new_owned(&format!("{}_{}_{}", IDENTIFIER_PREFIX, first, second))
+            .expect("A type hash is always a valid identifier")
+    }
+}
+
+/// The reverse of `From<&TypeHash> for Identifier`.
+impl TryFrom<&Identifier<'_>> for TypeHash {
+    type Error = LqError;
+
+    fn try_from(value: &Identifier) -> Result<Self, Self::Error> {
+        match value.segments() {
+            [prefix, first, second] if prefix.deref() == IDENTIFIER_PREFIX => {
+                TypeHash::try_from(format!("{}{}", first.deref(), second.deref()).as_str())
+            }
+            _ => LqError::err_new(format!(
+                "The identifier {} is not a type hash (expecting `{}_SEG1_SEG2`).",
+                value, IDENTIFIER_PREFIX
+            )),
+        }
+    }
+}
 
 pub(crate) struct TypeHasher {
     blake2: VarBlake2b,
@@ -15,10 +15,10 @@ use crate::types::seq::TSeq;
 use crate::types::structure::Field;
 use crate::types::structure::TStruct;
 use crate::types::uint::TUInt;
+use crate::validation_error::{ValidationErrorKind, ValidationResult};
 use liquesco_common::error::LqError;
 use liquesco_common::ine_range::{U32IneRange, U64IneRange};
 use liquesco_common::range::LqRangeBounds;
-use liquesco_serialization::core::DeSerializer;
 use liquesco_serialization::types::unicode::UncheckedUnicode;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
@@ -165,28 +165,30 @@ impl Type for TAscii<'_> {
     where
         C: ValidationContext<'c>,
     {
-        let bytes = UncheckedUnicode::de_serialize(context.reader())?;
-
-        // first check length (that's faster)
-        let length = bytes.len();
-        let length_u64 = u64::try_from(length)?;
-        self.length.require_within(
-            "Ascii schema validation (length; bytes; \
-             number of characters)",
-            &length_u64,
-        )?;
+        UncheckedUnicode::de_serialize_with(context.reader(), |bytes| {
+            // first check length (that's faster)
+            let length = bytes.len();
+            let length_u64 = u64::try_from(length)?;
+            self.length
+                .require_within(
+                    "Ascii schema validation (length; bytes; \
+                     number of characters)",
+                    &length_u64,
+                )
+                .kind(ValidationErrorKind::OutOfRange)?;
 
-        // now we have to check each character
-        for byte in bytes {
-            let contains = self.codes.contains(*byte);
-            if !contains {
-                return LqError::err_new(format!("The given ascii string contains a character \
-                that's not within the allowed code range. Ascii code is {:?}; code ranges is {:?}; \
-                note: it's a list of pairs (min; max exclusive).", byte, self.codes));
+            // now we have to check each character
+            for byte in bytes {
+                let contains = self.codes.contains(*byte);
+                if !contains {
+                    return ValidationErrorKind::InvalidValue.err(format!("The given ascii string contains a character \
+                    that's not within the allowed code range. Ascii code is {:?}; code ranges is {:?}; \
+                    note: it's a list of pairs (min; max exclusive).", byte, self.codes));
+                }
             }
-        }
 
-        Result::Ok(())
+            Result::Ok(())
+        })
     }
 
     fn compare<'c, C>(
@@ -198,10 +200,10 @@ impl Type for TAscii<'_> {
     where
         C: CmpContext<'c>,
     {
-        let bytes1 = UncheckedUnicode::de_serialize(r1)?;
-        let bytes2 = UncheckedUnicode::de_serialize(r2)?;
         // lex compare
-        Result::Ok(bytes1.cmp(&bytes2))
+        UncheckedUnicode::de_serialize_with(r1, |bytes1| {
+            UncheckedUnicode::de_serialize_with(r2, |bytes2| Result::Ok(bytes1.cmp(bytes2)))
+        })
     }
 
     fn reference(&self, _: usize) -> Option<&TypeRef> {
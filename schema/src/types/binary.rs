@@ -12,10 +12,10 @@ use crate::types::range::TRange;
 use crate::types::structure::Field;
 use crate::types::structure::TStruct;
 use crate::types::uint::TUInt;
+use crate::validation_error::{ValidationErrorKind, ValidationResult};
 use liquesco_common::error::LqError;
 use liquesco_common::ine_range::U64IneRange;
 use liquesco_common::range::LqRangeBounds;
-use liquesco_serialization::core::DeSerializer;
 use liquesco_serialization::types::binary::Binary;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
@@ -54,11 +54,11 @@ impl Type for TBinary<'_> {
     where
         C: ValidationContext<'c>,
     {
-        let bytes = Binary::de_serialize(context.reader())?;
-        let length = bytes.len();
+        let length = Binary::de_serialize_with(context.reader(), |bytes| Ok(bytes.len()))?;
         let length_u64 = u64::try_from(length)?;
         self.length
-            .require_within("Binary length validation", &length_u64)?;
+            .require_within("Binary length validation", &length_u64)
+            .kind(ValidationErrorKind::OutOfRange)?;
         Ok(())
     }
 
@@ -71,10 +71,10 @@ impl Type for TBinary<'_> {
     where
         C: CmpContext<'c>,
     {
-        let bytes1 = Binary::de_serialize(r1)?;
-        let bytes2 = Binary::de_serialize(r2)?;
         // lex compare
-        Result::Ok(bytes1.cmp(&bytes2))
+        Binary::de_serialize_with(r1, |bytes1| {
+            Binary::de_serialize_with(r2, |bytes2| Result::Ok(bytes1.cmp(bytes2)))
+        })
     }
 
     fn reference(&self, _: usize) -> Option<&TypeRef> {
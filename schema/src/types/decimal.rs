@@ -5,6 +5,7 @@ use crate::metadata::{Meta, MetadataSetter, WithMetadata};
 use crate::schema_builder::{BaseTypeSchemaBuilder, SchemaBuilder};
 use crate::types::range::{Inclusion, TRange};
 use crate::types::structure::{Field, TStruct};
+use crate::validation_error::{ValidationErrorKind, ValidationResult};
 use liquesco_common::decimal::Decimal;
 use liquesco_common::error::LqError;
 use liquesco_common::range::{LqRangeBounds, Range};
@@ -69,7 +70,7 @@ impl Type for TDecimal<'_> {
     {
         let decimal = DecimalSerialization::de_serialize(context.reader())?;
         if !decimal.is_normalized() {
-            return LqError::err_new(format!(
+            return ValidationErrorKind::InvalidValue.err(format!(
                 "The given decimal has not been normalized: \
                  0 has to be represented as 0*10^0 and the exponent has to be as close to 0 \
                  as possible. Given value: {:?}.",
@@ -77,11 +78,13 @@ impl Type for TDecimal<'_> {
             ));
         }
 
-        self.range.require_within(
-            "Decimal range validation \
-             (schema)",
-            &decimal,
-        )?;
+        self.range
+            .require_within(
+                "Decimal range validation \
+                 (schema)",
+                &decimal,
+            )
+            .kind(ValidationErrorKind::OutOfRange)?;
 
         Ok(())
     }
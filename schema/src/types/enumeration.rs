@@ -13,6 +13,7 @@ use crate::types::option::TOption;
 use crate::types::seq::TSeq;
 use crate::types::structure::Field;
 use crate::types::structure::TStruct;
+use crate::validation_error::{PathSegment, ValidationErrorKind};
 use lazy_static::lazy_static;
 use liquesco_common::error::LqError;
 use liquesco_common::ine_range::U32IneRange;
@@ -206,7 +207,7 @@ impl<'a> Type for TEnum<'a> {
 
         let usize_ordinal = usize::try_from(ordinal)?;
         if usize_ordinal >= number_of_variants {
-            return LqError::err_new(format!(
+            return ValidationErrorKind::UnknownVariant.err(format!(
                 "Got ordinal value {:?} for enum. \
                  There's no such variant defined for that ordinal value in \
                  the schema.",
@@ -218,7 +219,7 @@ impl<'a> Type for TEnum<'a> {
         let usize_number_of_values = usize::try_from(number_of_values)?;
         let schema_number_of_values = variant.values().len();
         if context.config().no_extension() && (schema_number_of_values != usize_number_of_values) {
-            return LqError::err_new(format!(
+            return ValidationErrorKind::InvalidNumberOfValues.err(format!(
                 "Error processing enum variant {} (ordinal \
                  {}); strict mode: Schema expects {} values - have {} values in \
                  data.",
@@ -228,7 +229,7 @@ impl<'a> Type for TEnum<'a> {
                 usize_number_of_values
             ));
         } else if usize_number_of_values < schema_number_of_values {
-            return LqError::err_new(format!(
+            return ValidationErrorKind::InvalidNumberOfValues.err(format!(
                 "Error processing enum variant {} (ordinal \
                  {}): Schema expects at least {} values - have {} values in \
                  data.",
@@ -242,8 +243,10 @@ impl<'a> Type for TEnum<'a> {
         let to_skip = usize_number_of_values - schema_number_of_values;
 
         // validate each element
-        for r#type in variant.values() {
-            context.validate(r#type)?;
+        for (index, r#type) in (0u32..).zip(variant.values()) {
+            context.validate_nested(r#type, || {
+                PathSegment::VariantValue(variant.name().clone().into_owned(), index)
+            })?;
         }
 
         if to_skip > 0 {
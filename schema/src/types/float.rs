@@ -12,6 +12,7 @@ use crate::types::range::Inclusion;
 use crate::types::range::TRange;
 use crate::types::structure::Field;
 use crate::types::structure::TStruct;
+use crate::validation_error::{ValidationErrorKind, ValidationResult};
 use liquesco_common::error::LqError;
 use liquesco_common::float::F32Ext;
 use liquesco_common::float::F64Ext;
@@ -149,18 +150,18 @@ impl<F: Eq + PartialOrd + Debug> TFloat<'_, F> {
         let need_range_check = match info.category {
             FpCategory::Nan => {
                 if !self.allow_nan {
-                    return LqError::err_new(NOT_A_NUMBER_ERR_STR);
+                    return ValidationErrorKind::InvalidValue.err(NOT_A_NUMBER_ERR_STR);
                 }
                 false
             }
             FpCategory::Infinite => {
                 if info.negative {
                     if !self.allow_negative_infinity {
-                        return LqError::err_new(NO_NEGATIVE_INFINITY);
+                        return ValidationErrorKind::InvalidValue.err(NO_NEGATIVE_INFINITY);
                     }
                 } else {
                     if !self.allow_positive_infinity {
-                        return LqError::err_new(NO_POSITIVE_INFINITY);
+                        return ValidationErrorKind::InvalidValue.err(NO_POSITIVE_INFINITY);
                     }
                 }
                 false
@@ -168,19 +169,19 @@ impl<F: Eq + PartialOrd + Debug> TFloat<'_, F> {
             FpCategory::Zero => {
                 if info.negative {
                     if !self.allow_negative_zero {
-                        return LqError::err_new(NO_NEGATIVE_ZERO);
+                        return ValidationErrorKind::InvalidValue.err(NO_NEGATIVE_ZERO);
                     }
                     false
                 } else {
                     if !self.allow_positive_zero {
-                        return LqError::err_new(NO_POSITIVE_ZERO);
+                        return ValidationErrorKind::InvalidValue.err(NO_POSITIVE_ZERO);
                     }
                     false
                 }
             }
             FpCategory::Subnormal => {
                 if !self.allow_subnormal {
-                    return LqError::err_new(NO_SUBNORMAL);
+                    return ValidationErrorKind::InvalidValue.err(NO_SUBNORMAL);
                 }
                 true
             }
@@ -189,11 +190,13 @@ impl<F: Eq + PartialOrd + Debug> TFloat<'_, F> {
 
         if need_range_check {
             // it's a number
-            self.range.require_within(
-                "Float range validation \
-                 (schema)",
-                &value,
-            )?;
+            self.range
+                .require_within(
+                    "Float range validation \
+                     (schema)",
+                    &value,
+                )
+                .kind(ValidationErrorKind::OutOfRange)?;
         }
         Ok(())
     }
@@ -11,6 +11,7 @@ use crate::schema_builder::SchemaBuilder;
 use crate::types::structure::Field;
 use crate::types::structure::TStruct;
 use crate::types::uint::TUInt;
+use crate::validation_error::ValidationErrorKind;
 use liquesco_common::error::LqError;
 use liquesco_serialization::core::DeSerializer;
 use liquesco_serialization::types::uint::UInt32;
@@ -60,7 +61,7 @@ impl Type for TKeyRef<'_> {
         let ref_int = UInt32::de_serialize(context.reader())?;
         if let Some(ref_info) = context.key_ref_info(self.level) {
             if ref_int >= ref_info.map_len() {
-                LqError::err_new(format!(
+                ValidationErrorKind::InvalidKeyRef.err(format!(
                     "You're referencing key at index {} in a map but \
                      the map only has {} keys.",
                     ref_int,
@@ -70,7 +71,7 @@ impl Type for TKeyRef<'_> {
                 Ok(())
             }
         } else {
-            LqError::err_new(format!(
+            ValidationErrorKind::InvalidKeyRef.err(format!(
                 "You're trying to reference key {} in a map but \
                  there's no map that's currently being processed; or there's no map at level {}. \
                  Key references can only \
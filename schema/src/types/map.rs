@@ -18,6 +18,7 @@ use crate::types::range::TRange;
 use crate::types::structure::Field;
 use crate::types::structure::TStruct;
 use crate::types::uint::TUInt;
+use crate::validation_error::{PathSegment, ValidationErrorKind, ValidationResult};
 use liquesco_common::error::LqError;
 use liquesco_common::ine_range::U32IneRange;
 use liquesco_common::range::LqRangeBounds;
@@ -73,6 +74,11 @@ impl<'a> TMap<'a> {
         self
     }
 
+    pub fn with_sorting(mut self, sorting: Sorting) -> Self {
+        self.sorting = sorting;
+        self
+    }
+
     /// The type of keys in this map.
     pub fn key(&self) -> &TypeRef {
         &self.key
@@ -249,11 +255,13 @@ where
     C: ValidationContext<'c>,
 {
     // length OK?
-    length_range.require_within(
-        "Given length of map is outside bounds defined \
-         in schema.",
-        &length,
-    )?;
+    length_range
+        .require_within(
+            "Given length of map is outside bounds defined \
+             in schema.",
+            &length,
+        )
+        .kind(ValidationErrorKind::InvalidLength)?;
 
     let wanted_ordering = match sorting {
         Sorting::Ascending => Ordering::Greater,
@@ -264,7 +272,7 @@ where
     for index in 0..length {
         let entry_header = SeqHeader::de_serialize(context.reader())?;
         if entry_header.length() != 2 {
-            return LqError::err_new(format!(
+            return ValidationErrorKind::InvalidNumberOfValues.err(format!(
                 "A map has to look like this: [[key1, \
                  value1], [key2, value2], ...]. So every key/value entry must be a sequence with \
                  two elements. The entry at index {} has {} elements.",
@@ -273,26 +281,27 @@ where
             ));
         }
 
-        // Create two copies (required for next iteration and for compare)
+        // Create two copies of the reader at the key (required for the compare and for the
+        // next iteration). Only the copy for the next iteration lives while the value is
+        // validated.
         let mut current_key_reader = context.reader().clone();
         let current_key_reader_for_next_iteration = context.reader().clone();
 
         if anchors {
             // This pop-push is required so keys cannot reference itself.
             let saved_info = context.pop_key_ref_info()?;
-            context.validate(key)?;
+            context.validate_nested(key, || PathSegment::MapKey(index))?;
             context.push_key_ref_info(saved_info);
         } else {
-            context.validate(key)?;
+            context.validate_nested(key, || PathSegment::MapKey(index))?;
         }
-        context.validate(value)?;
 
         // Compare this key and the previous key to make sure keys have correct sorting
-        // and there are no duplicates.
+        // and there are no duplicates (before the value is validated).
         if let Some(mut previous_reader) = previous_key_reader.take() {
             let key_cmp = context.compare(key, &mut current_key_reader, &mut previous_reader)?;
             if key_cmp != wanted_ordering {
-                return LqError::err_new(format!(
+                return ValidationErrorKind::InvalidOrdering.err(format!(
                     "There's an ordering problem in the map. \
                      Keys have to be sorted according to the schema - and no duplicates are \
                      allowed. Compare result key at index {} to {}: {:?}; wanted {:?}.",
@@ -303,6 +312,9 @@ where
                 ));
             }
         }
+        drop(current_key_reader);
+
+        context.validate_nested(value, || PathSegment::MapValue(index))?;
 
         previous_key_reader = Some(current_key_reader_for_next_iteration);
     }
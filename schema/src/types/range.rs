@@ -21,6 +21,7 @@ use crate::types::enumeration::Variant;
 use crate::types::key_ref::TKeyRef;
 use crate::types::structure::Field;
 use crate::types::structure::TStruct;
+use crate::validation_error::{PathSegment, ValidationErrorKind};
 use liquesco_serialization::core::LqReader;
 use liquesco_serialization::types::seq::SeqHeader;
 use std::cmp::Ordering::Equal;
@@ -86,7 +87,7 @@ impl Type for TRange<'_> {
         };
 
         if number_of_items != expected_number_of_items {
-            return LqError::err_new(format!(
+            return ValidationErrorKind::InvalidNumberOfValues.err(format!(
                 "The given range has a seq length of {}, we \
                  need a length of {} (start, end and maybe 2 more items with information about \
                  inclusion).",
@@ -96,10 +97,10 @@ impl Type for TRange<'_> {
 
         // start
         let mut start_reader = context.reader().clone();
-        context.validate(&self.element)?;
+        context.validate_nested(&self.element, || PathSegment::RangeStart)?;
         // end
         let mut end_reader = context.reader().clone();
-        context.validate(&self.element)?;
+        context.validate_nested(&self.element, || PathSegment::RangeEnd)?;
 
         let inclusive: (bool, bool) = match self.inclusion {
             Inclusion::Supplied => (
@@ -115,7 +116,7 @@ impl Type for TRange<'_> {
         // Now compare start and end
         let cmp = context.compare(&self.element, &mut start_reader, &mut end_reader)?;
         match cmp {
-            Ordering::Greater => LqError::err_new(
+            Ordering::Greater => ValidationErrorKind::InvalidOrdering.err(
                 "The given start (first element) is greater then \
                  given end (second element) in range. Start can never be greater than end.",
             ),
@@ -130,7 +131,7 @@ impl Type for TRange<'_> {
                         (false, true) => false,
                     };
                     if !ok {
-                        LqError::err_new(format!("Start (first element) is equal to \
+                        ValidationErrorKind::InvalidOrdering.err(format!("Start (first element) is equal to \
                         end (second element). \
                     This is not allowed according to the schema (see 'allow_empty'). Start \
                     inclusive {}, end inclusive {}.",
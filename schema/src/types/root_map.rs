@@ -18,6 +18,7 @@ use crate::types::range::TRange;
 use crate::types::structure::Field;
 use crate::types::structure::TStruct;
 use crate::types::uint::TUInt;
+use crate::validation_error::{PathSegment, ValidationErrorKind};
 use liquesco_common::error::LqError;
 use liquesco_common::ine_range::U32IneRange;
 use liquesco_common::range::NewFull;
@@ -89,7 +90,7 @@ impl Type for TRootMap<'_> {
     {
         let outer_seq = SeqHeader::de_serialize(context.reader())?;
         if outer_seq.length() != 2 {
-            return LqError::err_new(format!(
+            return ValidationErrorKind::InvalidNumberOfValues.err(format!(
                 "A root map has to look like this: [[[key1, \
                  value1], [key2, value2], ...], root]]. So the outer sequence has to have \
                  exactly 2 elements. Have {} elements.",
@@ -114,7 +115,7 @@ impl Type for TRootMap<'_> {
         )?;
 
         // now validate the root
-        context.validate(&self.root)?;
+        context.validate_nested(&self.root, || PathSegment::Root)?;
 
         // pop ref info
         context.pop_key_ref_info()?;
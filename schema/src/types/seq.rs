@@ -17,6 +17,7 @@ use crate::types::range::TRange;
 use crate::types::structure::Field;
 use crate::types::structure::TStruct;
 use crate::types::uint::TUInt;
+use crate::validation_error::{PathSegment, ValidationErrorKind, ValidationResult};
 use liquesco_common::error::LqError;
 use liquesco_common::ine_range::U32IneRange;
 use liquesco_common::range::LqRangeBounds;
@@ -125,16 +126,18 @@ impl Type for TSeq<'_> {
         let seq = SeqHeader::de_serialize(context.reader())?;
         let number_of_items = seq.length();
 
-        self.length.require_within(
-            "Sequence length validation \
-             (schema; min/max elements in sequence)",
-            &number_of_items,
-        )?;
+        self.length
+            .require_within(
+                "Sequence length validation \
+                 (schema; min/max elements in sequence)",
+                &number_of_items,
+            )
+            .kind(ValidationErrorKind::InvalidLength)?;
 
         // multiple of correct?
         if let Some(multiple_of) = self.multiple_of {
             if number_of_items % multiple_of != 0 {
-                return LqError::err_new(format!(
+                return ValidationErrorKind::InvalidLength.err(format!(
                     "There's {:?} elements in this list. \
                      According to the schema the number of elements must be a multiple of {:?}.",
                     number_of_items, multiple_of
@@ -145,8 +148,8 @@ impl Type for TSeq<'_> {
         match &self.ordering {
             Ordering::None => {
                 // validate each element
-                for _ in 0..number_of_items {
-                    context.validate(&self.element)?;
+                for index in 0..number_of_items {
+                    context.validate_nested(&self.element, || PathSegment::Index(index))?;
                 }
             }
             Ordering::Sorted(value) => {
@@ -268,7 +271,7 @@ where
         // we need 3 readers (one for validation, one for this cmp and one for next cmp)
         let mut saved_reader1 = context.reader().clone();
         let saved_reader2 = context.reader().clone();
-        context.validate(&this.element)?;
+        context.validate_nested(&this.element, || PathSegment::Index(idx))?;
 
         if let Some(mut previous) = previous.take() {
             let equality = context.compare(&this.element, &mut previous, &mut saved_reader1)?;
@@ -276,7 +279,7 @@ where
                 std::cmp::Ordering::Greater => {
                     // previous is greater: this is OK for descending lists
                     if direction != Direction::Descending {
-                        return LqError::err_new(format!(
+                        return ValidationErrorKind::InvalidOrdering.err(format!(
                             "Element at index {:?} is greater \
                              than element at index {:?}; this is OK but only for descending \
                              lists. This list is not a descending list.",
@@ -288,7 +291,7 @@ where
                 std::cmp::Ordering::Less => {
                     // previous is less: this is OK for ascending lists
                     if direction != Direction::Ascending {
-                        return LqError::err_new(format!(
+                        return ValidationErrorKind::InvalidOrdering.err(format!(
                             "Element at index {:?} is less \
                              than element at index {:?}; this is OK but only for ascending \
                              lists. This list is not an ascending list.",
@@ -300,7 +303,7 @@ where
                 std::cmp::Ordering::Equal => {
                     // this is only allowed when we accept duplicates
                     if unique {
-                        return LqError::err_new(format!(
+                        return ValidationErrorKind::InvalidOrdering.err(format!(
                             "Elements at index {:?} and {:?} in \
                              sequence are equal. This is not allowed, since sequence must not \
                              contain duplicates.",
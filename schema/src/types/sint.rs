@@ -11,6 +11,7 @@ use crate::types::range::{Inclusion, TRange};
 use crate::types::structure::Field;
 use crate::types::structure::TStruct;
 use crate::types::tint::TInt;
+use crate::validation_error::{ValidationErrorKind, ValidationResult};
 use liquesco_common::error::LqError;
 use liquesco_common::ine_range::I128IneRange;
 use liquesco_common::int_memory::IntMemory;
@@ -68,7 +69,8 @@ impl Type for TSInt<'_> {
     {
         let int_value = SInt128::de_serialize(context.reader())?;
         self.range
-            .require_within("Signed integer schema validation", &int_value)?;
+            .require_within("Signed integer schema validation", &int_value)
+            .kind(ValidationErrorKind::OutOfRange)?;
         Result::Ok(())
     }
 
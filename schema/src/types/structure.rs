@@ -10,6 +10,7 @@ use crate::schema_builder::BuildsOwnSchema;
 use crate::schema_builder::{BaseTypeSchemaBuilder, SchemaBuilder};
 use crate::types::key_ref::TKeyRef;
 use crate::types::seq::TSeq;
+use crate::validation_error::{PathSegment, ValidationErrorKind};
 use liquesco_common::error::LqError;
 use liquesco_common::ine_range::U32IneRange;
 use liquesco_serialization::core::DeSerializer;
@@ -82,14 +83,14 @@ impl<'a> Type for TStruct<'a> {
         // length check
         if context.config().no_extension() {
             if number_of_items != schema_number_of_fields {
-                return LqError::err_new(format!(
+                return ValidationErrorKind::InvalidNumberOfValues.err(format!(
                     "Invalid number of items in struct. \
                      Need {:?} fields, have {:?} fields (strict mode)",
                     schema_number_of_fields, number_of_items
                 ));
             }
         } else if number_of_items < schema_number_of_fields {
-            return LqError::err_new(format!(
+            return ValidationErrorKind::InvalidNumberOfValues.err(format!(
                 "Some fields are missing in the given struct. \
                  Need at least {:?} fields, have {:?} fields.",
                 schema_number_of_fields, number_of_items
@@ -99,7 +100,9 @@ impl<'a> Type for TStruct<'a> {
         let schema_number_of_fields_usize = usize::try_from(schema_number_of_fields)?;
         for index in 0..schema_number_of_fields_usize {
             let field = &self.fields()[index];
-            context.validate(&field.r#type)?;
+            context.validate_nested(&field.r#type, || {
+                PathSegment::Field(field.name().clone().into_owned())
+            })?;
         }
         // skip the rest of the fields
         let to_skip = number_of_items - schema_number_of_fields;
@@ -14,10 +14,10 @@ use crate::types::range::TRange;
 use crate::types::structure::Field;
 use crate::types::structure::TStruct;
 use crate::types::uint::TUInt;
+use crate::validation_error::{ValidationErrorKind, ValidationResult};
 use liquesco_common::error::LqError;
 use liquesco_common::ine_range::U64IneRange;
 use liquesco_common::range::LqRangeBounds;
-use liquesco_serialization::core::DeSerializer;
 use liquesco_serialization::types::unicode::UncheckedUnicode;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
@@ -99,22 +99,25 @@ impl Type for TUnicode<'_> {
         C: ValidationContext<'c>,
     {
         // we read the unchecked unicode (so we know the length of utf-8 bytes)
-        let bytes = UncheckedUnicode::de_serialize(context.reader())?;
-        // this makes sure the data is valid UTF8
-        let utf8_string = match from_utf8(bytes) {
-            Result::Ok(value) => value,
-            Result::Err(err) => {
-                return LqError::err_new(format!("The given string is not valid UTF-8: {:?}", err));
-            }
-        };
-        let length = match self.length_type {
-            LengthType::Byte => bytes.len(),
-            LengthType::Utf8Byte => bytes.len(),
-            LengthType::ScalarValue => utf8_string.chars().count(),
-        };
+        let length = UncheckedUnicode::de_serialize_with(context.reader(), |bytes| {
+            // this makes sure the data is valid UTF8
+            let utf8_string = match from_utf8(bytes) {
+                Result::Ok(value) => value,
+                Result::Err(err) => {
+                    return ValidationErrorKind::InvalidValue
+                        .err(format!("The given string is not valid UTF-8: {:?}", err));
+                }
+            };
+            Result::Ok(match self.length_type {
+                LengthType::Byte => bytes.len(),
+                LengthType::Utf8Byte => bytes.len(),
+                LengthType::ScalarValue => utf8_string.chars().count(),
+            })
+        })?;
         let length_u64 = u64::try_from(length)?;
         self.length
-            .require_within("Unicode schema validation (length)", &length_u64)?;
+            .require_within("Unicode schema validation (length)", &length_u64)
+            .kind(ValidationErrorKind::OutOfRange)?;
 
         Result::Ok(())
     }
@@ -128,10 +131,10 @@ impl Type for TUnicode<'_> {
     where
         C: CmpContext<'c>,
     {
-        let bytes1 = UncheckedUnicode::de_serialize(r1)?;
-        let bytes2 = UncheckedUnicode::de_serialize(r2)?;
         // lex compare
-        Result::Ok(bytes1.cmp(&bytes2))
+        UncheckedUnicode::de_serialize_with(r1, |bytes1| {
+            UncheckedUnicode::de_serialize_with(r2, |bytes2| Result::Ok(bytes1.cmp(bytes2)))
+        })
     }
 
     fn reference(&self, _: usize) -> Option<&TypeRef> {
@@ -48,9 +48,9 @@ impl Type for TUuid<'_> {
         C: CmpContext<'c>,
     {
         // compare like "normal" binaries
-        let bin1 = Binary::de_serialize(r1)?;
-        let bin2 = Binary::de_serialize(r2)?;
-        Result::Ok(bin1.cmp(&bin2))
+        Binary::de_serialize_with(r1, |bin1| {
+            Binary::de_serialize_with(r2, |bin2| Result::Ok(bin1.cmp(bin2)))
+        })
     }
 
     fn reference(&self, _: usize) -> Option<&TypeRef> {
@@ -0,0 +1,225 @@
+use crate::identifier::{Format, Identifier};
+use liquesco_common::error::{ErrData, ErrMsg, LqError};
+use std::any::Any;
+use std::fmt::{Display, Error, Formatter};
+
+/// Information attached to errors returned by `Schema::validate` (see `LqError::data`):
+/// Where in the data the error occurred and what kind of error it is.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationError {
+    kind: ValidationErrorKind,
+    path: DataPath,
+    type_identifier: Option<Identifier<'static>>,
+    offset: Option<u64>,
+}
+
+impl ErrData for ValidationError {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// What kind of validation error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ValidationErrorKind {
+    /// The data could not be read as expected by the schema (e.g. malformed data or a
+    /// different type than the one defined in the schema).
+    Serialization,
+    /// A value (integer, float, decimal or the length of a text or binary) is outside the
+    /// range defined in the schema.
+    OutOfRange,
+    /// The number of elements in a sequence or a map is outside the range defined in the
+    /// schema (or not a multiple of the number defined in the schema).
+    InvalidLength,
+    /// Fields of a structure or values of an enum variant are missing (or there are
+    /// more than allowed).
+    InvalidNumberOfValues,
+    /// There's no enum variant for the ordinal.
+    UnknownVariant,
+    /// Elements (of sorted sequences) or keys (of maps) are not sorted or not unique.
+    InvalidOrdering,
+    /// The value is not allowed by the schema (e.g. invalid UTF-8, ascii characters outside
+    /// the allowed range, a not normalized decimal or a float that's not allowed).
+    InvalidValue,
+    /// A key reference references a key that does not exist.
+    InvalidKeyRef,
+    /// The schema references a type that does not exist.
+    UnknownType,
+}
+
+impl ErrData for ValidationErrorKind {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// The path through the data (from the root value to the value with the error).
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataPath(Vec<PathSegment>);
+
+/// A single segment of `DataPath`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathSegment {
+    /// A field of a structure.
+    Field(Identifier<'static>),
+    /// An element of a sequence.
+    Index(u32),
+    /// A value of an enum variant.
+    VariantValue(Identifier<'static>, u32),
+    /// The key of the map entry at given index.
+    MapKey(u32),
+    /// The value of the map entry at given index.
+    MapValue(u32),
+    /// Start of a range.
+    RangeStart,
+    /// End of a range.
+    RangeEnd,
+    /// The root value of a root map.
+    Root,
+}
+
+impl ValidationError {
+    /// What kind of error.
+    pub fn kind(&self) -> ValidationErrorKind {
+        self.kind
+    }
+
+    /// Where in the data the error occurred.
+    pub fn path(&self) -> &DataPath {
+        &self.path
+    }
+
+    /// The identifier of the type of the value with the error (from `TypeContainer::identifier`).
+    pub fn type_identifier(&self) -> Option<&Identifier<'static>> {
+        self.type_identifier.as_ref()
+    }
+
+    /// Byte offset of the value with the error in the data (if the reader knows the offset;
+    /// see `LqReader::read_offset`).
+    pub fn offset(&self) -> Option<u64> {
+        self.offset
+    }
+
+    /// Attaches validation error information to the error of the value (unless it already has
+    /// that information; in that case the error comes from a nested value).
+    pub(crate) fn attach(
+        err: LqError,
+        type_identifier: Option<Identifier<'static>>,
+        offset: Option<u64>,
+    ) -> LqError {
+        if err.data::<ValidationError>().is_some() {
+            return err;
+        }
+        let kind = err
+            .data::<ValidationErrorKind>()
+            .cloned()
+            .unwrap_or(ValidationErrorKind::Serialization);
+        err.with_data(ValidationError {
+            kind,
+            path: DataPath::default(),
+            type_identifier,
+            offset,
+        })
+    }
+
+    /// Adds the segment to the start of the path (if the error has validation error
+    /// information).
+    pub(crate) fn prepend(err: LqError, segment: PathSegment) -> LqError {
+        if let Some(info) = err.data::<ValidationError>() {
+            let mut info = info.clone();
+            info.path.0.insert(0, segment);
+            err.with_data(info)
+        } else {
+            err
+        }
+    }
+
+    /// Adds the information to the message; this is done once validation has finished (when
+    /// the path is complete).
+    pub(crate) fn finish(err: LqError) -> LqError {
+        let msg = if let Some(info) = err.data::<ValidationError>() {
+            format!(
+                "{} (data path: `{}`; type: `{}`; byte offset: {}; kind: {:?})",
+                err.msg(),
+                info.path,
+                info.type_identifier
+                    .as_ref()
+                    .map(|identifier| identifier.to_string(Format::SnakeCase))
+                    .unwrap_or_else(|| "unknown".to_string()),
+                info.offset
+                    .map(|offset| offset.to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                info.kind
+            )
+        } else {
+            return err;
+        };
+        err.with_msg(msg)
+    }
+}
+
+impl ValidationErrorKind {
+    /// Creates a new error of this kind.
+    pub(crate) fn err<Ok, T: Into<ErrMsg>>(self, msg: T) -> Result<Ok, LqError> {
+        Result::Err(LqError::new(msg).with_data(self))
+    }
+}
+
+impl DataPath {
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+}
+
+impl Display for DataPath {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        if self.0.is_empty() {
+            return write!(f, "<root>");
+        }
+        for (index, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) => {
+                    if index > 0 {
+                        write!(f, ".")?;
+                    }
+                    write!(f, "{}", name.to_string(Format::SnakeCase))?
+                }
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+                PathSegment::VariantValue(name, index) => {
+                    write!(f, "::{}[{}]", name.to_string(Format::SnakeCase), index)?
+                }
+                PathSegment::MapKey(index) => write!(f, "[key #{}]", index)?,
+                PathSegment::MapValue(index) => write!(f, "[key #{} value]", index)?,
+                PathSegment::RangeStart => write!(f, "[start]")?,
+                PathSegment::RangeEnd => write!(f, "[end]")?,
+                PathSegment::Root => write!(f, "[root]")?,
+            }
+        }
+        Result::Ok(())
+    }
+}
+
+/// Helper methods for results of validation.
+pub(crate) trait ValidationResult<T> {
+    /// Sets the kind of the error (unless the error already has a kind).
+    fn kind(self, kind: ValidationErrorKind) -> Result<T, LqError>;
+
+    /// The error is an error of a nested value: Adds the segment to the path.
+    fn in_path<F: FnOnce() -> PathSegment>(self, segment: F) -> Result<T, LqError>;
+}
+
+impl<T> ValidationResult<T> for Result<T, LqError> {
+    fn kind(self, kind: ValidationErrorKind) -> Result<T, LqError> {
+        self.map_err(|err| {
+            if err.data::<ValidationErrorKind>().is_some() {
+                err
+            } else {
+                err.with_data(kind)
+            }
+        })
+    }
+
+    fn in_path<F: FnOnce() -> PathSegment>(self, segment: F) -> Result<T, LqError> {
+        self.map_err(|err| ValidationError::prepend(err, segment()))
+    }
+}
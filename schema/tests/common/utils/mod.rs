@@ -3,7 +3,9 @@
 use liquesco_schema::any_type::AnyType;
 use liquesco_schema::core::Config;
 use liquesco_schema::core::Schema;
+use liquesco_schema::core::TypeRef;
 use liquesco_schema::identifier::Identifier;
+use liquesco_schema::identifier::StrIdentifier;
 use liquesco_schema::schema::DefaultSchema;
 use liquesco_schema::schema_builder::{DefaultSchemaBuilder, SchemaBuilder};
 use liquesco_schema::type_container::DefaultTypeContainer;
@@ -11,6 +13,8 @@ use liquesco_serialization::core::ToVecLqWriter;
 use liquesco_serialization::serde::serialize;
 use liquesco_serialization::slice_reader::SliceReader;
 use liquesco_serialization::vec_writer::VecWriter;
+use std::borrow::Cow;
+use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::fmt::Debug;
 
@@ -18,6 +22,11 @@ pub fn id(string: &'static str) -> Identifier<'static> {
     string.try_into().unwrap()
 }
 
+/// A reference (by identifier) to a type that is added later (e.g. recursive types).
+pub fn forward_ref(identifier: &'static str) -> TypeRef {
+    TypeRef::Identifier(StrIdentifier::try_from(Cow::Borrowed(identifier)).unwrap())
+}
+
 pub fn assert_valid_invalid<S, TSchema>(
     item: S,
     schema: &TSchema,
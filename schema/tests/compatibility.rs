@@ -0,0 +1,222 @@
+mod common;
+
+use common::builder::builder;
+use common::builder::into_schema;
+use common::utils::id;
+use liquesco_schema::compatibility::{check, Compatibility, IncompatibilityKind};
+use liquesco_schema::core::{Config, Schema, TypeRef};
+use liquesco_schema::identifier::StrIdentifier;
+use liquesco_schema::schema::DefaultSchema;
+use liquesco_schema::schema_builder::SchemaBuilder;
+use liquesco_schema::type_container::DefaultTypeContainer;
+use liquesco_schema::types::enumeration::{TEnum, Variant};
+use liquesco_schema::types::map::{Sorting, TMap};
+use liquesco_schema::types::option::TOption;
+use liquesco_schema::types::structure::Field;
+use liquesco_schema::types::structure::TStruct;
+use liquesco_schema::types::uint::TUInt;
+use liquesco_serialization::serde::serialize_to_vec;
+use liquesco_serialization::slice_reader::SliceReader;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+type TestSchema = DefaultSchema<'static, DefaultTypeContainer<'static>>;
+
+/// A structure with unsigned integer fields (name; maximum value).
+fn person(fields: &[(&'static str, u32)]) -> TestSchema {
+    let mut builder = builder();
+    let mut person = TStruct::default();
+    for (name, max) in fields {
+        let field_type = builder.add_unwrap(name, TUInt::try_new(0u32, *max).unwrap());
+        person = person.add(Field::new(id(name), field_type));
+    }
+    let person = builder.add_unwrap("person", person);
+    into_schema(builder, person)
+}
+
+fn kinds(old: &TestSchema, new: &TestSchema) -> Vec<IncompatibilityKind> {
+    check(old, new, &Config::new())
+        .unwrap()
+        .incompatibilities()
+        .map(|item| item.kind())
+        .collect()
+}
+
+#[test]
+fn field_appended() {
+    let old = person(&[("name", 100), ("age", 100)]);
+    let new = person(&[("name", 100), ("age", 100), ("email", 100)]);
+
+    let report = check(&old, &new, &Config::new()).unwrap();
+    assert_eq!(Compatibility::Forward, report.compatibility());
+    assert_eq!(vec![IncompatibilityKind::FieldAdded], kinds(&old, &new));
+    let report = check(&old, &new, &Config::strict()).unwrap();
+    assert_eq!(Compatibility::None, report.compatibility());
+
+    // the report matches validation: data written with the new schema validates under the
+    // old schema (unless extensions are not allowed)
+    #[derive(Serialize)]
+    struct NewPerson {
+        name: u32,
+        age: u32,
+        email: u32,
+    }
+    let data = serialize_to_vec(&NewPerson {
+        name: 1,
+        age: 2,
+        email: 3,
+    })
+    .unwrap();
+    let mut reader: SliceReader = (&data).into();
+    assert!(old.validate(Config::new(), &mut reader).is_ok());
+    let mut reader: SliceReader = (&data).into();
+    assert!(old.validate(Config::strict(), &mut reader).is_err());
+}
+
+#[test]
+fn field_inserted_in_the_middle() {
+    let old = person(&[("name", 100), ("age", 100)]);
+    let new = person(&[("name", 100), ("email", 100), ("age", 100)]);
+
+    let report = check(&old, &new, &Config::new()).unwrap();
+    assert_eq!(Compatibility::None, report.compatibility());
+    let incompatibilities: Vec<_> = report.incompatibilities().collect();
+    assert_eq!(1, incompatibilities.len());
+    assert_eq!(
+        IncompatibilityKind::FieldInserted,
+        incompatibilities[0].kind()
+    );
+    assert_eq!(
+        "field `email` inserted in the middle (at index 1)",
+        incompatibilities[0].description()
+    );
+
+    // renamed fields are fine (only the position matters)
+    let renamed = person(&[("full_name", 100), ("age", 100)]);
+    assert_eq!(
+        Compatibility::Full,
+        check(&old, &renamed, &Config::new())
+            .unwrap()
+            .compatibility()
+    );
+}
+
+#[test]
+fn uint_range_narrowed() {
+    let old = person(&[("name", 100), ("age", 100)]);
+    let new = person(&[("name", 100), ("age", 50)]);
+
+    let report = check(&old, &new, &Config::new()).unwrap();
+    // the structure itself is unchanged but references the changed type
+    assert_eq!(Compatibility::Forward, report.compatibility());
+    assert!(report.types()[0].incompatibilities().is_empty());
+    let age = report
+        .types()
+        .iter()
+        .find(|item| item.new_identifier() == &id("age"))
+        .unwrap();
+    assert_eq!(Compatibility::Forward, age.compatibility());
+    assert_eq!(
+        "uint range narrowed from [0, 100] to [0, 50]",
+        age.incompatibilities()[0].description()
+    );
+
+    let report = check(&new, &old, &Config::new()).unwrap();
+    assert_eq!(Compatibility::Backward, report.compatibility());
+    assert_eq!(
+        vec![IncompatibilityKind::ConstraintWidened],
+        kinds(&new, &old)
+    );
+}
+
+#[test]
+fn enum_variant_removed() {
+    let schema = |variants: &[&'static str]| {
+        let mut builder = builder();
+        let mut colour = TEnum::default();
+        for variant in variants {
+            colour = colour.add_variant(Variant::new(id(variant)));
+        }
+        let colour = builder.add_unwrap("colour", colour);
+        into_schema(builder, colour)
+    };
+    let old = schema(&["red", "green", "blue"]);
+
+    let removed_at_end = schema(&["red", "green"]);
+    assert_eq!(
+        Compatibility::Forward,
+        check(&old, &removed_at_end, &Config::new())
+            .unwrap()
+            .compatibility()
+    );
+    assert_eq!(
+        vec![IncompatibilityKind::VariantRemoved],
+        kinds(&old, &removed_at_end)
+    );
+    assert_eq!(
+        Compatibility::Backward,
+        check(&removed_at_end, &old, &Config::new())
+            .unwrap()
+            .compatibility()
+    );
+
+    let removed_from_middle = schema(&["red", "blue"]);
+    assert_eq!(
+        Compatibility::None,
+        check(&old, &removed_from_middle, &Config::new())
+            .unwrap()
+            .compatibility()
+    );
+}
+
+#[test]
+fn map_sorting_changed() {
+    let schema = |sorting: Sorting| {
+        let mut builder = builder();
+        let key = builder.add_unwrap("key", TUInt::try_new(0u32, 100u32).unwrap());
+        let value = builder.add_unwrap("value", TUInt::try_new(0u32, 100u32).unwrap());
+        let map = builder.add_unwrap("map", TMap::new(key, value).with_sorting(sorting));
+        into_schema(builder, map)
+    };
+    let old = schema(Sorting::Ascending);
+    let new = schema(Sorting::Descending);
+
+    let report = check(&old, &new, &Config::new()).unwrap();
+    assert_eq!(Compatibility::None, report.compatibility());
+    assert_eq!(vec![IncompatibilityKind::SortingChanged], kinds(&old, &new));
+    assert_eq!(
+        Compatibility::Full,
+        check(&old, &old, &Config::new()).unwrap().compatibility()
+    );
+}
+
+#[test]
+fn recursive_schema() {
+    // a linked list: `node` has a value and an optional next `node`.
+    let schema = |max_value: u32| {
+        let mut builder = builder();
+        let next_node =
+            TypeRef::Identifier(StrIdentifier::try_from(Cow::Borrowed("node")).unwrap());
+        let maybe_next = builder.add_unwrap("maybe_next", TOption::new(next_node));
+        let value = builder.add_unwrap("value", TUInt::try_new(0u32, max_value).unwrap());
+        let node = builder.add_unwrap(
+            "node",
+            TStruct::default()
+                .add(Field::new(id("value"), value))
+                .add(Field::new(id("next"), maybe_next)),
+        );
+        into_schema(builder, node)
+    };
+
+    let report = check(&schema(100), &schema(100), &Config::new()).unwrap();
+    assert_eq!(Compatibility::Full, report.compatibility());
+    assert_eq!(3, report.types().len());
+
+    let report = check(&schema(100), &schema(200), &Config::new()).unwrap();
+    // the option references the node: the option is also affected
+    assert!(report
+        .types()
+        .iter()
+        .all(|item| item.compatibility() == Compatibility::Backward));
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use common::builder::builder;
+use common::utils::id;
+use liquesco_schema::diff::{diff, ChangeKind};
+use liquesco_schema::metadata::MetadataSetter;
+use liquesco_schema::schema_builder::SchemaBuilder;
+use liquesco_schema::type_container::DefaultTypeContainer;
+use liquesco_schema::types::ascii::TAscii;
+use liquesco_schema::types::map::{Sorting, TMap};
+use liquesco_schema::types::structure::Field;
+use liquesco_schema::types::structure::TStruct;
+use liquesco_schema::types::uint::TUInt;
+use liquesco_schema::types::unicode::{LengthType, TUnicode};
+
+/// Old version: a person with name, age and phone number.
+fn old_person() -> DefaultTypeContainer<'static> {
+    let mut builder = builder();
+    let text = builder.add_unwrap(
+        "text",
+        TUnicode::try_new(0, 100, LengthType::Byte)
+            .unwrap()
+            .with_doc("A text."),
+    );
+    let years = builder.add_unwrap("years", TUInt::try_new(0u32, 150u32).unwrap());
+    let phone = builder.add_unwrap("phone", TAscii::try_new(0, 20, 48, 57).unwrap());
+    let person = builder.add_unwrap(
+        "person",
+        TStruct::default()
+            .add(Field::new(id("name"), text))
+            .add(Field::new(id("age"), years))
+            .add(Field::new(id("phone"), phone)),
+    );
+    builder.finish(person).unwrap()
+}
+
+/// New version: the phone number has been replaced by an email address.
+fn new_person() -> DefaultTypeContainer<'static> {
+    let mut builder = builder();
+    let text = builder.add_unwrap(
+        "text",
+        TUnicode::try_new(0, 100, LengthType::Byte)
+            .unwrap()
+            .with_doc("Some text."),
+    );
+    let age = builder.add_unwrap("age_in_years", TUInt::try_new(0u32, 150u32).unwrap());
+    let email = builder.add_unwrap("email", TAscii::try_new(0, 200, 32, 126).unwrap());
+    let person = builder.add_unwrap(
+        "person",
+        TStruct::default()
+            .add(Field::new(id("name"), text))
+            .add(Field::new(id("age"), age))
+            .add(Field::new(id("email"), email)),
+    );
+    builder.finish(person).unwrap()
+}
+
+#[test]
+fn added_removed_and_renamed_types() {
+    let schema_diff = diff(&old_person(), &new_person()).unwrap();
+
+    assert_eq!(&[id("email")], schema_diff.added());
+    assert_eq!(&[id("phone")], schema_diff.removed());
+    // same type (hash), other identifier
+    assert_eq!(&[(id("years"), id("age_in_years"))], schema_diff.renamed());
+    assert!(schema_diff.root().is_none());
+
+    let changed = schema_diff.changed();
+    assert_eq!(2, changed.len());
+    let person = changed
+        .iter()
+        .find(|item| item.old_identifier() == &id("person"))
+        .unwrap();
+    let kinds: Vec<_> = person.changes().iter().map(|item| item.kind()).collect();
+    assert_eq!(
+        vec![ChangeKind::FieldTypeChanged, ChangeKind::FieldRenamed],
+        kinds
+    );
+    assert!(!person.documentation_only());
+    let text = changed
+        .iter()
+        .find(|item| item.old_identifier() == &id("text"))
+        .unwrap();
+    assert!(text.documentation_only());
+
+    assert_eq!(
+        "Added types:\n\
+         \x20 + `email`\n\
+         Removed types:\n\
+         \x20 - `phone`\n\
+         Renamed types:\n\
+         \x20 * `years` renamed to `age_in_years`\n\
+         Changed types:\n\
+         \x20 `person`:\n\
+         \x20   - type of field `email` changed from `phone` to `email`\n\
+         \x20   - field `phone` renamed to `email`\n\
+         \x20 `text` (documentation only):\n\
+         \x20   - documentation changed\n",
+        schema_diff.to_string()
+    );
+}
+
+#[test]
+fn changed_constraints_as_markdown() {
+    let schema = |max: u32, sorting: Sorting| {
+        let mut builder = builder();
+        let key = builder.add_unwrap("key", TUInt::try_new(0u32, max).unwrap());
+        let value = builder.add_unwrap("value", TUInt::try_new(0u32, 100u32).unwrap());
+        let map = builder.add_unwrap("map", TMap::new(key, value).with_sorting(sorting));
+        builder.finish(map).unwrap()
+    };
+
+    let schema_diff = diff(
+        &schema(100, Sorting::Ascending),
+        &schema(50, Sorting::Descending),
+    )
+    .unwrap();
+    assert_eq!(
+        "# Schema changes\n\
+         \n\
+         ## Changed types\n\
+         \n\
+         ### `key`\n\
+         \n\
+         - uint range changed from [0, 100] to [0, 50]\n\
+         \n\
+         ### `map`\n\
+         \n\
+         - map sorting changed from Ascending to Descending\n",
+        schema_diff.to_markdown()
+    );
+
+    let unchanged = diff(
+        &schema(100, Sorting::Ascending),
+        &schema(100, Sorting::Ascending),
+    )
+    .unwrap();
+    assert!(unchanged.is_empty());
+    assert_eq!("# Schema changes\n\nNo changes.\n", unchanged.to_markdown());
+}
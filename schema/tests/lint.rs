@@ -0,0 +1,176 @@
+mod common;
+
+use common::builder::builder;
+use common::builder::into_schema;
+use common::utils::forward_ref;
+use common::utils::id;
+use liquesco_common::range::Range;
+use liquesco_schema::identifier::Format;
+use liquesco_schema::lint::{lint, LintKind, LintReport};
+use liquesco_schema::metadata::MetadataSetter;
+use liquesco_schema::schema_builder::SchemaBuilder;
+use liquesco_schema::types::float::TFloat64;
+use liquesco_schema::types::key_ref::TKeyRef;
+use liquesco_schema::types::map::TMap;
+use liquesco_schema::types::option::TOption;
+use liquesco_schema::types::seq::TSeq;
+use liquesco_schema::types::structure::Field;
+use liquesco_schema::types::structure::TStruct;
+use liquesco_schema::types::uint::TUInt;
+use liquesco_schema::types::unicode::{LengthType, TUnicode};
+
+/// All issues (identifier; kind) except missing documentation.
+fn issues(report: &LintReport) -> Vec<(String, LintKind)> {
+    report
+        .issues()
+        .iter()
+        .filter(|issue| issue.kind() != LintKind::MissingDocumentation)
+        .map(|issue| {
+            (
+                issue.identifier().to_string(Format::SnakeCase),
+                issue.kind(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn no_issues() {
+    let mut builder = builder();
+    let age = builder.add_unwrap(
+        "age",
+        TUInt::try_new(0u32, 150u32)
+            .unwrap()
+            .with_doc("Age in years."),
+    );
+    let person = builder.add_unwrap(
+        "person",
+        TStruct::default()
+            .add(Field::new(id("age"), age))
+            .with_doc("A person."),
+    );
+    let schema = into_schema(builder, person);
+
+    let report = lint(&schema).unwrap();
+    assert!(report.is_empty());
+    assert_eq!("No issues.\n", report.to_string());
+}
+
+#[test]
+fn unreachable_types_and_key_refs() {
+    let mut builder = builder();
+    let name = builder.add_unwrap("name", TUnicode::try_new(1, 20, LengthType::Byte).unwrap());
+    // references the next node in `nodes`
+    let next = builder.add_unwrap("next", TKeyRef::default());
+    // there's no map with anchors around `nodes`
+    let outer = builder.add_unwrap("outer", TKeyRef::default().with_level(1));
+    let node = builder.add_unwrap(
+        "node",
+        TStruct::default()
+            .add(Field::new(id("next"), next))
+            .add(Field::new(id("outer"), outer)),
+    );
+    let nodes = builder.add_unwrap("nodes", TMap::new(name.clone(), node).with_anchors(true));
+    let count = builder.add_unwrap("count", TUInt::try_new(0u32, 100u32).unwrap());
+    // nothing references those keys
+    let counts = builder.add_unwrap("counts", TMap::new(name, count).with_anchors(true));
+    builder.add_unwrap("orphan", TUInt::try_new(0u32, 100u32).unwrap());
+    let graph = builder.add_unwrap(
+        "graph",
+        TStruct::default()
+            .add(Field::new(id("nodes"), nodes))
+            .add(Field::new(id("counts"), counts)),
+    );
+    let schema = into_schema(builder, graph);
+
+    let report = lint(&schema).unwrap();
+    assert_eq!(
+        vec![
+            ("counts".to_string(), LintKind::UnusedAnchors),
+            ("orphan".to_string(), LintKind::UnreachableType),
+            ("outer".to_string(), LintKind::DeadKeyRef),
+        ],
+        issues(&report)
+    );
+    assert_eq!(
+        "`outer`: key ref can never reference a key: there's no map with anchors at level 1",
+        report
+            .issues_of(LintKind::DeadKeyRef)
+            .next()
+            .unwrap()
+            .to_string()
+    );
+    // no documentation at all
+    assert_eq!(9, report.issues_of(LintKind::MissingDocumentation).count());
+}
+
+#[test]
+fn impossible_constraints() {
+    let mut builder = builder();
+    let value = builder.add_unwrap("value", TUInt::try_new(0u32, 100u32).unwrap());
+    // [1, 1)
+    let empty = builder.add_unwrap(
+        "empty",
+        TFloat64::new(
+            Range::try_new_inclusive(1f64.into(), 1f64.into())
+                .unwrap()
+                .with_end_included(false),
+        ),
+    );
+    let triple = builder.add_unwrap(
+        "triple",
+        TSeq::try_new(value.clone(), 4, 5)
+            .unwrap()
+            .with_multiple_of(3),
+    );
+    // fine: the list can end
+    let maybe_list = builder.add_unwrap("maybe_list", TOption::new(forward_ref("list")));
+    let list = builder.add_unwrap(
+        "list",
+        TStruct::default()
+            .add(Field::new(id("value"), value.clone()))
+            .add(Field::new(id("next"), maybe_list)),
+    );
+    // never ends
+    let endless = builder.add_unwrap(
+        "endless",
+        TStruct::default()
+            .add(Field::new(id("value"), value))
+            .add(Field::new(id("next"), forward_ref("endless"))),
+    );
+    let root = builder.add_unwrap(
+        "root",
+        TStruct::default()
+            .add(Field::new(id("empty"), empty))
+            .add(Field::new(id("triple"), triple))
+            .add(Field::new(id("list"), list))
+            .add(Field::new(id("endless"), endless)),
+    );
+    let schema = into_schema(builder, root);
+
+    let report = lint(&schema).unwrap();
+    assert_eq!(
+        vec![
+            ("empty".to_string(), LintKind::EmptyRange),
+            ("endless".to_string(), LintKind::NotInstantiable),
+            ("root".to_string(), LintKind::NotInstantiable),
+            ("triple".to_string(), LintKind::ImpossibleMultipleOf),
+        ],
+        issues(&report)
+    );
+    let descriptions: Vec<_> = report
+        .issues()
+        .iter()
+        .filter(|issue| {
+            issue.kind() == LintKind::EmptyRange || issue.kind() == LintKind::ImpossibleMultipleOf
+        })
+        .map(|issue| issue.description())
+        .collect();
+    assert_eq!(
+        vec![
+            "f64 range [1, 1) is empty",
+            "no seq length within [4, 5] is a multiple of 3"
+        ],
+        descriptions
+    );
+}
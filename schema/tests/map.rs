@@ -6,12 +6,17 @@ use common::utils::assert_valid_strict;
 use liquesco_schema::core::Schema;
 
 use crate::common::builder::into_schema;
+use liquesco_schema::core::Config;
 use liquesco_schema::schema_builder::SchemaBuilder;
 use liquesco_schema::types::map::TMap;
 use liquesco_schema::types::sint::TSInt;
 use liquesco_schema::types::unicode::LengthType;
 use liquesco_schema::types::unicode::TUnicode;
+use liquesco_schema::validation_error::{ValidationError, ValidationErrorKind};
+use liquesco_serialization::serde::serialize_to_vec_canonical;
+use liquesco_serialization::slice_reader::SliceReader;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
 
 #[test]
 fn ok_empty_map() {
@@ -56,6 +61,19 @@ fn err_wrong_key_ordering() {
     assert_invalid_strict(map, &schema);
 }
 
+#[test]
+fn err_key_ordering_before_value() {
+    let schema = create_schema_numbers();
+    // the key ordering is wrong and the value is out of range: keys are compared before the
+    // value is validated
+    let map: Vec<(isize, isize)> = vec![(10, 30), (9, 2000)];
+    let data = serialize_to_vec_canonical(&map).unwrap();
+    let mut reader: SliceReader = (&data).into();
+    let err = schema.validate(Config::strict(), &mut reader).unwrap_err();
+    let info = err.data::<ValidationError>().unwrap();
+    assert_eq!(ValidationErrorKind::InvalidOrdering, info.kind());
+}
+
 /// When using BTreeMap we get correct key ordering automatically
 #[test]
 fn ok_with_btree() {
@@ -84,6 +102,20 @@ fn ok_with_btree_2() {
     assert_valid_strict(my_map, &schema);
 }
 
+/// `HashMap` has no ordering; the canonical serializer sorts the entries.
+#[test]
+fn ok_with_hash_map_canonical() {
+    let mut my_map: HashMap<isize, String> = HashMap::new();
+    for index in -300..300 {
+        my_map.insert(index, format!("Hello Entry #{}", index));
+    }
+
+    let schema = create_schema2();
+    let data = serialize_to_vec_canonical(&my_map).unwrap();
+    let mut reader: SliceReader = (&data).into();
+    schema.validate(Config::strict(), &mut reader).unwrap();
+}
+
 fn create_schema2() -> impl Schema {
     let mut builder = builder();
     let key = builder.add_unwrap("key", TSInt::try_new(-1000, 1000).unwrap());
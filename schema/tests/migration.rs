@@ -0,0 +1,242 @@
+mod common;
+
+use common::builder::builder;
+use common::builder::into_schema;
+use common::utils::id;
+use liquesco_schema::migration::{migrate, Migration, TypeMigration};
+use liquesco_schema::schema_builder::SchemaBuilder;
+use liquesco_schema::types::boolean::TBool;
+use liquesco_schema::types::enumeration::{TEnum, Variant};
+use liquesco_schema::types::key_ref::TKeyRef;
+use liquesco_schema::types::map::TMap;
+use liquesco_schema::types::option::TOption;
+use liquesco_schema::types::structure::Field;
+use liquesco_schema::types::structure::TStruct;
+use liquesco_schema::types::uint::TUInt;
+use liquesco_schema::types::unicode::{LengthType, TUnicode};
+use liquesco_serialization::serde::{de_serialize_from_slice, serialize_to_vec};
+use liquesco_serialization::value::{Value, ValueSeq, ValueVariant};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+struct OldPerson {
+    name: String,
+    age: u32,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct NewPerson {
+    years: u64,
+    name: String,
+    email: Option<String>,
+    active: bool,
+}
+
+#[test]
+fn reordered_renamed_and_new_fields() {
+    let old = {
+        let mut builder = builder();
+        let name = builder.add_unwrap("name", TUnicode::try_new(0, 100, LengthType::Byte).unwrap());
+        let age = builder.add_unwrap("age", TUInt::try_new(0u32, 150u32).unwrap());
+        let person = builder.add_unwrap(
+            "person",
+            TStruct::default()
+                .add(Field::new(id("name"), name))
+                .add(Field::new(id("age"), age)),
+        );
+        into_schema(builder, person)
+    };
+
+    let new = {
+        let mut builder = builder();
+        let name = builder.add_unwrap("name", TUnicode::try_new(0, 100, LengthType::Byte).unwrap());
+        // widened
+        let years = builder.add_unwrap("years", TUInt::try_new(0u32, 1000u32).unwrap());
+        let email_address = builder.add_unwrap(
+            "email_address",
+            TUnicode::try_new(3, 100, LengthType::Byte).unwrap(),
+        );
+        let email = builder.add_unwrap("email", TOption::new(email_address));
+        let active = builder.add_unwrap("active", TBool::default());
+        let person = builder.add_unwrap(
+            "person",
+            TStruct::default()
+                .add(Field::new(id("years"), years))
+                .add(Field::new(id("name"), name))
+                .add(Field::new(id("email"), email))
+                .add(Field::new(id("active"), active)),
+        );
+        into_schema(builder, person)
+    };
+
+    let data = serialize_to_vec(&OldPerson {
+        name: "Anna".to_string(),
+        age: 42,
+    })
+    .unwrap();
+
+    // the new field `active` has no default value
+    let err = migrate(&old, &new, &Migration::default(), &data).unwrap_err();
+    assert!(err.msg().contains("no default value"), "{}", err.msg());
+
+    let migration = Migration::default().with_type(
+        id("person"),
+        TypeMigration::default()
+            .with_renamed_field(id("years"), id("age"))
+            .with_default(id("active"), Value::Bool(true)),
+    );
+    let new_data = migrate(&old, &new, &migration, &data).unwrap();
+    let person: NewPerson = de_serialize_from_slice(&new_data).unwrap();
+    assert_eq!(
+        NewPerson {
+            years: 42,
+            name: "Anna".to_string(),
+            email: None,
+            active: true,
+        },
+        person
+    );
+}
+
+#[derive(Serialize)]
+enum OldShape {
+    Circle(u32),
+    Square,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum NewShape {
+    Square,
+    Round(u32),
+}
+
+#[test]
+fn remapped_variants_and_unit_conversion() {
+    let old = {
+        let mut builder = builder();
+        let millimeters = builder.add_unwrap("millimeters", TUInt::try_new(0u32, 1000u32).unwrap());
+        let shape = builder.add_unwrap(
+            "shape",
+            TEnum::default()
+                .add_variant(Variant::new(id("circle")).add_value(millimeters))
+                .add_variant(Variant::new(id("square"))),
+        );
+        into_schema(builder, shape)
+    };
+
+    let new = {
+        let mut builder = builder();
+        let micrometers =
+            builder.add_unwrap("micrometers", TUInt::try_new(0u32, 1_000_000u32).unwrap());
+        let shape = builder.add_unwrap(
+            "shape",
+            TEnum::default()
+                .add_variant(Variant::new(id("square")))
+                .add_variant(Variant::new(id("round")).add_value(micrometers)),
+        );
+        into_schema(builder, shape)
+    };
+
+    let migration = Migration::default()
+        .with_type(
+            id("shape"),
+            TypeMigration::default().with_variant(id("circle"), id("round")),
+        )
+        .with_type(
+            id("micrometers"),
+            TypeMigration::default().with_conversion(|value| match value {
+                Value::UInt(millimeters) => Ok(Value::UInt(millimeters * 1000)),
+                _ => panic!("Expecting an unsigned integer"),
+            }),
+        );
+
+    let migrate_shape = |shape: OldShape| {
+        let data = serialize_to_vec(&shape).unwrap();
+        let new_data = migrate(&old, &new, &migration, &data).unwrap();
+        de_serialize_from_slice::<NewShape>(&new_data).unwrap()
+    };
+    assert_eq!(NewShape::Round(12_000), migrate_shape(OldShape::Circle(12)));
+    assert_eq!(NewShape::Square, migrate_shape(OldShape::Square));
+}
+
+#[test]
+fn maps_are_sorted_again() {
+    // a map where each value references another entry
+    let schema = |key_identifier: &'static str| {
+        let mut builder = builder();
+        let key = builder.add_unwrap(key_identifier, TUInt::try_new(0u32, 100u32).unwrap());
+        let value = builder.add_unwrap("other", TKeyRef::default());
+        let map = builder.add_unwrap("map", TMap::new(key, value).with_anchors(true));
+        into_schema(builder, map)
+    };
+    let old = schema("key");
+    let new = schema("inverted_key");
+    // the keys sort the other way round after the migration
+    let migration = Migration::default().with_type(
+        id("inverted_key"),
+        TypeMigration::default().with_conversion(|value| match value {
+            Value::UInt(key) => Ok(Value::UInt(100 - key)),
+            _ => panic!("Expecting an unsigned integer"),
+        }),
+    );
+
+    // 10 references 20 and 20 references 10
+    let data = serialize_to_vec(vec![(10u32, 1u32), (20u32, 0u32)]).unwrap();
+    let new_data = migrate(&old, &new, &migration, &data).unwrap();
+    // 80 (was 20) references 90 (was 10) and vice versa
+    let map: Vec<(u32, u32)> = de_serialize_from_slice(&new_data).unwrap();
+    assert_eq!(vec![(80, 1), (90, 0)], map);
+}
+
+#[derive(Serialize)]
+enum ExtendedShape {
+    Circle(u32, u32),
+}
+
+#[test]
+fn extension_values_without_counterpart() {
+    let shape = |number_of_values: usize| {
+        let mut builder = builder();
+        let millimeters = builder.add_unwrap("millimeters", TUInt::try_new(0u32, 1000u32).unwrap());
+        let mut circle = Variant::new(id("circle"));
+        for _ in 0..number_of_values {
+            circle = circle.add_value(millimeters.clone());
+        }
+        let shape = builder.add_unwrap("shape", TEnum::default().add_variant(circle));
+        into_schema(builder, shape)
+    };
+    let old = shape(1);
+    let new = shape(2);
+
+    // the second value is an extension value (not declared in the old version)
+    let data = serialize_to_vec(ExtendedShape::Circle(12, 14)).unwrap();
+    let err = migrate(&old, &new, &Migration::default(), &data).unwrap_err();
+    assert!(err.msg().contains("Declare a conversion"), "{}", err.msg());
+}
+
+#[test]
+fn converted_to_unknown_variant() {
+    let shape = || {
+        let mut builder = builder();
+        let shape = builder.add_unwrap(
+            "shape",
+            TEnum::default()
+                .add_variant(Variant::new(id("circle")))
+                .add_variant(Variant::new(id("square"))),
+        );
+        into_schema(builder, shape)
+    };
+    let migration = Migration::default().with_type(
+        id("shape"),
+        TypeMigration::default().with_conversion(|_| {
+            Ok(Value::Enum(ValueVariant::new_values(
+                5,
+                ValueSeq::Owned(Vec::new()),
+            )))
+        }),
+    );
+
+    let data = serialize_to_vec(&OldShape::Square).unwrap();
+    let err = migrate(&shape(), &shape(), &migration, &data).unwrap_err();
+    assert!(err.msg().contains("ordinal 5"), "{}", err.msg());
+}
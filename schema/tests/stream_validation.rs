@@ -0,0 +1,122 @@
+mod common;
+
+use common::builder::builder;
+use common::builder::into_schema;
+use liquesco_schema::core::Config;
+use liquesco_schema::schema::DefaultSchema;
+use liquesco_schema::schema_builder::SchemaBuilder;
+use liquesco_schema::type_container::DefaultTypeContainer;
+use liquesco_schema::types::map::TMap;
+use liquesco_schema::types::seq::Direction;
+use liquesco_schema::types::seq::Sorted;
+use liquesco_schema::types::seq::TSeq;
+use liquesco_schema::types::uint::TUInt;
+use liquesco_schema::types::unicode::LengthType;
+use liquesco_schema::types::unicode::TUnicode;
+use liquesco_serialization::serde::serialize_to_vec;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+/// Much smaller than the serialized data (so validation only works if data is discarded).
+const MAX_BUFFER_SIZE: usize = 128;
+
+#[test]
+fn ok_sorted_unique_set() {
+    let schema = create_set_schema();
+    let mut set = BTreeSet::new();
+    for index in 0..10_000 {
+        set.insert(format!("Entry #{:06}", index));
+    }
+    let data = serialize_to_vec(set).unwrap();
+    assert!(data.len() > 100 * MAX_BUFFER_SIZE);
+    schema
+        .validate_stream(Config::strict(), data.as_slice(), MAX_BUFFER_SIZE)
+        .unwrap();
+}
+
+#[test]
+fn err_set_not_unique() {
+    let schema = create_set_schema();
+    let mut list: Vec<String> = (0..1000).map(|index| format!("#{:06}", index)).collect();
+    list.push("#000999".to_string());
+    let data = serialize_to_vec(list).unwrap();
+    assert!(schema
+        .validate_stream(Config::strict(), data.as_slice(), MAX_BUFFER_SIZE)
+        .is_err());
+}
+
+#[test]
+fn ok_map() {
+    let schema = create_map_schema();
+    let mut map = BTreeMap::new();
+    for index in 0..10_000u32 {
+        map.insert(format!("Key #{:06}", index), index);
+    }
+    let data = serialize_to_vec(map).unwrap();
+    schema
+        .validate_stream(Config::strict(), data.as_slice(), MAX_BUFFER_SIZE)
+        .unwrap();
+}
+
+#[test]
+fn err_map_wrong_ordering() {
+    let schema = create_map_schema();
+    let mut map: Vec<(String, u32)> = (0..1000u32)
+        .map(|index| (format!("Key #{:06}", index), index))
+        .collect();
+    map.swap(500, 501);
+    let data = serialize_to_vec(map).unwrap();
+    assert!(schema
+        .validate_stream(Config::strict(), data.as_slice(), MAX_BUFFER_SIZE)
+        .is_err());
+}
+
+#[test]
+fn err_additional_data() {
+    let schema = create_map_schema();
+    let map: BTreeMap<String, u32> = BTreeMap::new();
+    let mut data = serialize_to_vec(map).unwrap();
+    data.push(0);
+    assert!(schema
+        .validate_stream(Config::strict(), data.as_slice(), MAX_BUFFER_SIZE)
+        .is_err());
+}
+
+#[test]
+fn err_buffer_too_small() {
+    let schema = create_set_schema();
+    let set: BTreeSet<String> = vec!["a".repeat(100), "b".repeat(100)].into_iter().collect();
+    let data = serialize_to_vec(set).unwrap();
+    assert!(schema
+        .validate_stream(Config::strict(), data.as_slice(), MAX_BUFFER_SIZE)
+        .is_err());
+}
+
+fn create_set_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+    let mut builder = builder();
+    let element = builder.add_unwrap(
+        "element",
+        TUnicode::try_new(0, 100, LengthType::Utf8Byte).unwrap(),
+    );
+    let root = builder.add_unwrap(
+        "root",
+        TSeq::try_new(element, 0, 100_000)
+            .unwrap()
+            .with_sorted(Sorted {
+                direction: Direction::Ascending,
+                unique: true,
+            }),
+    );
+    into_schema(builder, root)
+}
+
+fn create_map_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+    let mut builder = builder();
+    let key = builder.add_unwrap(
+        "key",
+        TUnicode::try_new(0, 100, LengthType::Utf8Byte).unwrap(),
+    );
+    let value = builder.add_unwrap("value", TUInt::try_new(0u32, 100_000u32).unwrap());
+    let root = builder.add_unwrap("root", TMap::new(key, value));
+    into_schema(builder, root)
+}
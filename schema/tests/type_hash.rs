@@ -0,0 +1,134 @@
+mod common;
+
+use common::builder::builder;
+use common::builder::into_schema;
+use common::utils::forward_ref;
+use common::utils::id;
+use liquesco_schema::core::TypeContainer;
+use liquesco_schema::identifier::Identifier;
+use liquesco_schema::identifier::StrIdentifier;
+use liquesco_schema::metadata::Information;
+use liquesco_schema::schema_builder::SchemaBuilder;
+use liquesco_schema::type_hash::TypeHash;
+use liquesco_schema::types::boolean::TBool;
+use liquesco_schema::types::option::TOption;
+use liquesco_schema::types::structure::Field;
+use liquesco_schema::types::structure::TStruct;
+use liquesco_schema::types::uint::TUInt;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+/// A linked list: `node` has a value and an optional next `node`.
+fn linked_list_hash(value_first: bool, max_value: u32) -> TypeHash {
+    let mut builder = builder();
+    let maybe_next = builder.add_unwrap("maybe_next", TOption::new(forward_ref("node")));
+    let value = builder.add_unwrap("value", TUInt::try_new(0u32, max_value).unwrap());
+    let node = if value_first {
+        TStruct::default()
+            .add(Field::new(id("value"), value))
+            .add(Field::new(id("next"), maybe_next))
+    } else {
+        TStruct::default()
+            .add(Field::new(id("next"), maybe_next))
+            .add(Field::new(id("value"), value))
+    };
+    let node = builder.add_unwrap("node", node);
+    let schema = into_schema(builder, node);
+    schema.type_hash(schema.root(), Information::Full).unwrap()
+}
+
+#[test]
+fn recursive_schema() {
+    let hash = linked_list_hash(true, 100);
+    assert_eq!(hash, linked_list_hash(true, 100));
+    assert_ne!(hash, linked_list_hash(true, 101));
+    assert_ne!(hash, linked_list_hash(false, 100));
+}
+
+#[test]
+fn independent_of_type_order_and_identifiers() {
+    let hash = |reverse: bool| {
+        let mut builder = builder();
+        let (int, boolean) = if reverse {
+            let boolean = builder.add_unwrap("b_bool", TBool::default());
+            (
+                builder.add_unwrap("a_int", TUInt::try_new(0u32, 5u32).unwrap()),
+                boolean,
+            )
+        } else {
+            let int = builder.add_unwrap("int", TUInt::try_new(0u32, 5u32).unwrap());
+            (int, builder.add_unwrap("bool", TBool::default()))
+        };
+        let root = builder.add_unwrap(
+            "root",
+            TStruct::default()
+                .add(Field::new(id("int"), int))
+                .add(Field::new(id("bool"), boolean)),
+        );
+        let schema = into_schema(builder, root);
+        schema.type_hash(schema.root(), Information::Full).unwrap()
+    };
+    assert_eq!(hash(false), hash(true));
+}
+
+#[test]
+fn lower_hex_and_identifier() {
+    let hash = linked_list_hash(true, 100);
+    let hex = hash.to_string();
+    assert_eq!(32, hex.len());
+    assert_eq!(hash, TypeHash::try_from(hex.as_str()).unwrap());
+    assert!(TypeHash::try_from(hex.to_uppercase().as_str()).is_err());
+    assert!(TypeHash::try_from(&hex[1..]).is_err());
+
+    let identifier: Identifier = (&hash).into();
+    let identifier_string = identifier.to_string(liquesco_schema::identifier::Format::SnakeCase);
+    assert_eq!(
+        format!("th0x_{}_{}", &hex[..16], &hex[16..]),
+        identifier_string
+    );
+    assert_eq!(hash, TypeHash::try_from(&identifier).unwrap());
+    assert!(TypeHash::try_from(&id("node")).is_err());
+}
+
+#[test]
+fn shared_type_equals_copies() {
+    let hash = |shared: bool| {
+        let mut builder = builder();
+        let first = builder.add_unwrap("first", TUInt::try_new(0u32, 5u32).unwrap());
+        let second = if shared {
+            first.clone()
+        } else {
+            builder.add_unwrap("second", TUInt::try_new(0u32, 5u32).unwrap())
+        };
+        let root = builder.add_unwrap(
+            "root",
+            TStruct::default()
+                .add(Field::new(id("from"), first))
+                .add(Field::new(id("to"), second)),
+        );
+        let schema = into_schema(builder, root);
+        schema.type_hash(schema.root(), Information::Full).unwrap()
+    };
+    assert_eq!(hash(true), hash(false));
+}
+
+#[test]
+fn shared_types_are_hashed_once() {
+    // a chain of structures; each structure references the next structure twice (the number
+    // of paths doubles with each level)
+    let mut builder = builder();
+    let mut next = builder.add_unwrap("value", TBool::default());
+    for level in 0..64 {
+        let identifier = StrIdentifier::try_from(Cow::Owned(format!("level_{}", level))).unwrap();
+        next = builder
+            .add(
+                identifier,
+                TStruct::default()
+                    .add(Field::new(id("first"), next.clone()))
+                    .add(Field::new(id("second"), next)),
+            )
+            .unwrap();
+    }
+    let schema = into_schema(builder, next);
+    schema.type_hash(schema.root(), Information::Full).unwrap();
+}
@@ -0,0 +1,197 @@
+mod common;
+
+use common::builder::builder;
+use common::builder::into_schema;
+use common::utils::id;
+use liquesco_common::error::LqError;
+use liquesco_schema::core::Config;
+use liquesco_schema::core::Schema;
+use liquesco_schema::schema::DefaultSchema;
+use liquesco_schema::schema_builder::SchemaBuilder;
+use liquesco_schema::type_container::DefaultTypeContainer;
+use liquesco_schema::types::ascii::TAscii;
+use liquesco_schema::types::map::TMap;
+use liquesco_schema::types::seq::TSeq;
+use liquesco_schema::types::structure::Field;
+use liquesco_schema::types::structure::TStruct;
+use liquesco_schema::types::uint::TUInt;
+use liquesco_schema::validation_error::{PathSegment, ValidationError, ValidationErrorKind};
+use liquesco_serialization::serde::serialize_to_vec;
+use liquesco_serialization::slice_reader::SliceReader;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Customer {
+    name: String,
+}
+
+#[derive(Serialize)]
+struct Order {
+    quantity: u32,
+    customer: Customer,
+}
+
+fn validate<T: Serialize>(value: &T) -> Result<(), LqError> {
+    let data = serialize_to_vec(value).unwrap();
+    let mut reader: SliceReader = (&data).into();
+    orders_schema().validate(Config::strict(), &mut reader)
+}
+
+fn orders_schema() -> DefaultSchema<'static, DefaultTypeContainer<'static>> {
+    let mut builder = builder();
+    let quantity = builder.add_unwrap("quantity", TUInt::try_new(1u32, 100u32).unwrap());
+    let name = builder.add_unwrap("customer_name", TAscii::try_new(1, 20, 97, 123).unwrap());
+    let customer = builder.add_unwrap(
+        "customer",
+        TStruct::default().add(Field::new(id("name"), name)),
+    );
+    let order = builder.add_unwrap(
+        "order",
+        TStruct::default()
+            .add(Field::new(id("quantity"), quantity))
+            .add(Field::new(id("customer"), customer)),
+    );
+    let orders = builder.add_unwrap("orders", TSeq::try_new(order, 0, 10).unwrap());
+    let root = builder.add_unwrap(
+        "root",
+        TStruct::default().add(Field::new(id("orders"), orders)),
+    );
+    into_schema(builder, root)
+}
+
+fn order(quantity: u32, name: &str) -> Order {
+    Order {
+        quantity,
+        customer: Customer {
+            name: name.to_string(),
+        },
+    }
+}
+
+#[derive(Serialize)]
+struct Orders {
+    orders: Vec<Order>,
+}
+
+#[test]
+fn path_to_nested_field() {
+    let orders = Orders {
+        orders: vec![order(1, "john"), order(2, "max"), order(3, "INVALID")],
+    };
+    let err = validate(&orders).unwrap_err();
+    let info = err.data::<ValidationError>().unwrap();
+
+    assert_eq!(ValidationErrorKind::InvalidValue, info.kind());
+    assert_eq!("orders[2].customer.name", info.path().to_string());
+    assert_eq!(
+        &PathSegment::Field(id("orders")),
+        info.path().segments().first().unwrap()
+    );
+    assert_eq!(Some(&id("customer_name")), info.type_identifier());
+    assert!(err.msg().contains("orders[2].customer.name"));
+
+    // the offset points to the invalid name ("INVALID" is the last value)
+    let data = serialize_to_vec(&orders).unwrap();
+    let offset = info.offset().unwrap() as usize;
+    assert_eq!(&b"INVALID"[..], &data[data.len() - 7..]);
+    assert!(offset < data.len() - 7 && offset > data.len() - 10);
+}
+
+#[test]
+fn out_of_range() {
+    let orders = Orders {
+        orders: vec![order(101, "john")],
+    };
+    let err = validate(&orders).unwrap_err();
+    let info = err.data::<ValidationError>().unwrap();
+    assert_eq!(ValidationErrorKind::OutOfRange, info.kind());
+    assert_eq!("orders[0].quantity", info.path().to_string());
+    assert_eq!(Some(&id("quantity")), info.type_identifier());
+}
+
+#[test]
+fn invalid_length() {
+    let orders = Orders {
+        orders: (1..12).map(|quantity| order(quantity, "john")).collect(),
+    };
+    let err = validate(&orders).unwrap_err();
+    let info = err.data::<ValidationError>().unwrap();
+    assert_eq!(ValidationErrorKind::InvalidLength, info.kind());
+    assert_eq!("orders", info.path().to_string());
+    assert_eq!(Some(&id("orders")), info.type_identifier());
+}
+
+#[test]
+fn serialization_error() {
+    let err = validate(&"not a struct").unwrap_err();
+    let info = err.data::<ValidationError>().unwrap();
+    assert_eq!(ValidationErrorKind::Serialization, info.kind());
+    assert_eq!("<root>", info.path().to_string());
+    assert_eq!(Some(0), info.offset());
+}
+
+#[test]
+fn path_to_map_value() {
+    let mut builder = builder();
+    let key = builder.add_unwrap("key", TUInt::try_new(0u32, 100u32).unwrap());
+    let value = builder.add_unwrap("value", TUInt::try_new(0u32, 10u32).unwrap());
+    let root = builder.add_unwrap("root", TMap::new(key, value));
+    let schema = into_schema(builder, root);
+
+    let map: Vec<(u32, u32)> = vec![(1, 1), (2, 2), (3, 30)];
+    let data = serialize_to_vec(&map).unwrap();
+    let mut reader: SliceReader = (&data).into();
+    let err = schema.validate(Config::strict(), &mut reader).unwrap_err();
+    let info = err.data::<ValidationError>().unwrap();
+    assert_eq!(ValidationErrorKind::OutOfRange, info.kind());
+    assert_eq!("[key #2 value]", info.path().to_string());
+}
+
+#[test]
+fn collect_all_errors() {
+    let orders = Orders {
+        orders: vec![
+            order(0, "john"),
+            order(2, "max"),
+            order(3, "INVALID"),
+            order(200, "ALSO_INVALID"),
+        ],
+    };
+    let data = serialize_to_vec(&orders).unwrap();
+    let schema = orders_schema();
+
+    let mut reader: SliceReader = (&data).into();
+    let errors = schema.validate_all(Config::strict(), &mut reader, 10);
+    let paths: Vec<String> = errors
+        .iter()
+        .map(|err| err.data::<ValidationError>().unwrap().path().to_string())
+        .collect();
+    assert_eq!(
+        vec![
+            "orders[0].quantity",
+            "orders[2].customer.name",
+            "orders[3].quantity",
+            "orders[3].customer.name"
+        ],
+        paths
+    );
+    // everything has been read
+    reader.finish().unwrap();
+
+    // maximum number of errors
+    let mut reader: SliceReader = (&data).into();
+    assert_eq!(
+        2,
+        schema.validate_all(Config::strict(), &mut reader, 2).len()
+    );
+
+    // valid data
+    let valid = Orders {
+        orders: vec![order(1, "john")],
+    };
+    let data = serialize_to_vec(&valid).unwrap();
+    let mut reader: SliceReader = (&data).into();
+    assert!(schema
+        .validate_all(Config::strict(), &mut reader, 10)
+        .is_empty());
+}
@@ -0,0 +1,286 @@
+use crate::core::DeSerializer;
+use crate::core::LqReader;
+use crate::core::MajorType;
+use crate::limits::ReadLimitState;
+use crate::major_types::TYPE_BINARY;
+use crate::major_types::TYPE_BOOL_FALSE;
+use crate::major_types::TYPE_BOOL_TRUE;
+use crate::major_types::TYPE_ENUM_0;
+use crate::major_types::TYPE_ENUM_1;
+use crate::major_types::TYPE_ENUM_2;
+use crate::major_types::TYPE_ENUM_3;
+use crate::major_types::TYPE_ENUM_N;
+use crate::major_types::TYPE_FLOAT;
+use crate::major_types::TYPE_OPTION;
+use crate::major_types::TYPE_SEQ;
+use crate::major_types::TYPE_SINT;
+use crate::major_types::TYPE_UINT;
+use crate::major_types::TYPE_UNICODE;
+use crate::slice_reader::SliceReader;
+use crate::types::binary::Binary;
+use crate::types::boolean::Bool;
+use crate::types::enumeration::EnumHeader;
+use crate::types::float::Float;
+use crate::types::option::Presence;
+use crate::types::seq::SeqHeader;
+use crate::types::sint::SInt128;
+use crate::types::uint::UInt128;
+use crate::types::unicode::UncheckedUnicode;
+use core::cmp::Ordering;
+use core::str::from_utf8;
+use liquesco_common::error::LqError;
+use liquesco_common::float::F32Ext;
+use liquesco_common::float::F64Ext;
+
+/// Wraps a reader and enables the strict (canonical only) mode: Every `DeSerializer` reading
+/// from this reader rejects data that is not encoded in canonical form (the form the
+/// serializers produce). Non-canonical encodings are:
+///
+///  - Overlong varints.
+///  - Content descriptions not using the shortest content info (e.g. a separate varint
+///    for the self length where the length could have been encoded in the header byte).
+///  - Integers not using the minimal width.
+///  - Enum ordinals 0-3 not using `TYPE_ENUM_0`-`TYPE_ENUM_3`; ordinals not using the
+///    minimal width.
+///
+/// This is required if the serialized data is used for hashing or signing (two different
+/// encodings of the same value would result in different hashes).
+pub struct CanonicalReader<R>(R);
+
+impl<R> CanonicalReader<R> {
+    pub fn new(reader: R) -> Self {
+        CanonicalReader(reader)
+    }
+
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+impl<'a, R: LqReader<'a>> LqReader<'a> for CanonicalReader<R> {
+    #[inline]
+    fn read_offset(&self) -> Option<u64> {
+        self.0.read_offset()
+    }
+
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, LqError> {
+        self.0.peek_u8()
+    }
+
+    #[inline]
+    fn read_u8(&mut self) -> Result<u8, LqError> {
+        self.0.read_u8()
+    }
+
+    #[inline]
+    fn read_slice(&mut self, len: usize) -> Result<&'a [u8], LqError> {
+        self.0.read_slice(len)
+    }
+
+    #[inline]
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<(), LqError> {
+        self.0.read_into(buf)
+    }
+
+    #[inline]
+    fn read_slice_with<Ret, F>(&mut self, len: usize, function: F) -> Result<Ret, LqError>
+    where
+        F: FnOnce(&[u8]) -> Result<Ret, LqError>,
+    {
+        self.0.read_slice_with(len, function)
+    }
+
+    #[inline]
+    fn skip_bytes_u64(&mut self, number_of_bytes: u64) -> Result<(), LqError> {
+        self.0.skip_bytes_u64(number_of_bytes)
+    }
+
+    fn clone(&self) -> Self {
+        CanonicalReader(self.0.clone())
+    }
+
+    #[inline]
+    fn canonical_only(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn limit_state(&mut self) -> Option<&mut ReadLimitState> {
+        self.0.limit_state()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> std::io::Read for CanonicalReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+/// Checks whether the next value (including all embedded values) in given reader is encoded
+/// in canonical form (see `CanonicalReader`); also checks that unicode text is valid UTF-8.
+/// On success the reader is positioned after the value.
+///
+/// Note: This does not require a schema and does not check schema constraints (like
+/// ordering of sequences); use the schema validation for that.
+pub fn check_canonical<'a, R: LqReader<'a>>(reader: &mut R) -> Result<(), LqError> {
+    let mut canonical = CanonicalReader::new(reader.clone());
+    // iterative (not recursive): deeply nested data cannot overflow the stack.
+    let mut remaining: u64 = 1;
+    while remaining > 0 {
+        remaining -= 1;
+        let major_type = canonical.peek_header_byte()?.major_type();
+        match major_type {
+            TYPE_BOOL_FALSE | TYPE_BOOL_TRUE => {
+                Bool::de_serialize(&mut canonical)?;
+            }
+            TYPE_OPTION => {
+                if Presence::de_serialize(&mut canonical)? == Presence::Present {
+                    remaining += 1;
+                }
+            }
+            TYPE_SEQ => {
+                remaining += u64::from(SeqHeader::de_serialize(&mut canonical)?.length());
+            }
+            TYPE_BINARY => {
+                Binary::de_serialize_with(&mut canonical, |_| Ok(()))?;
+            }
+            TYPE_UNICODE => {
+                UncheckedUnicode::de_serialize_with(&mut canonical, |bytes| {
+                    from_utf8(bytes)
+                        .map(|_| ())
+                        .map_err(|_| LqError::new("Invalid utf8 data found"))
+                })?;
+            }
+            TYPE_UINT => {
+                UInt128::de_serialize(&mut canonical)?;
+            }
+            TYPE_SINT => {
+                SInt128::de_serialize(&mut canonical)?;
+            }
+            TYPE_FLOAT => {
+                Float::de_serialize(&mut canonical)?;
+            }
+            TYPE_ENUM_0 | TYPE_ENUM_1 | TYPE_ENUM_2 | TYPE_ENUM_3 | TYPE_ENUM_N => {
+                remaining +=
+                    u64::from(EnumHeader::de_serialize(&mut canonical)?.number_of_values());
+            }
+            _ => {
+                return LqError::err_new(lq_msg!(
+                    "Unknown major type {:?}; unable to check canonical encoding.",
+                    major_type
+                ))
+            }
+        }
+    }
+    *reader = canonical.into_inner();
+    Ok(())
+}
+
+/// Compares two serialized values without a schema. This is the order used for canonical
+/// collections (sorted map keys and sets, see `serde::serialize_canonical`). It matches the
+/// ordering of the schema (`compare`) for the common types:
+///
+///  - Integers and floats are compared by value; booleans: false < true.
+///  - Binaries and unicode text are compared lexicographically (bytes).
+///  - Options: absent < present; present values are compared by their value.
+///  - Sequences (and maps; maps are sequences of key-value sequences) are compared
+///    lexicographically element by element; if all common elements are equal, the shorter
+///    sequence is less.
+///  - Enums are compared by ordinal first and then like sequences (by their values).
+///
+/// Values of different major types are compared by their major type.
+pub fn compare(data1: &[u8], data2: &[u8]) -> Result<Ordering, LqError> {
+    let mut reader1: SliceReader = data1.into();
+    let mut reader2: SliceReader = data2.into();
+    compare_next(&mut reader1, &mut reader2)
+}
+
+/// Compares the next values. Note: Readers are not positioned after the value if the values
+/// are not equal.
+fn compare_next<'a>(
+    r1: &mut SliceReader<'a>,
+    r2: &mut SliceReader<'a>,
+) -> Result<Ordering, LqError> {
+    let kind1 = kind(r1.peek_header_byte()?.major_type());
+    let kind2 = kind(r2.peek_header_byte()?.major_type());
+    if kind1 != kind2 {
+        return Ok(kind1.id().cmp(&kind2.id()));
+    }
+    match kind1 {
+        TYPE_BOOL_FALSE => Ok(Bool::de_serialize(r1)?.cmp(&Bool::de_serialize(r2)?)),
+        TYPE_UINT => Ok(UInt128::de_serialize(r1)?.cmp(&UInt128::de_serialize(r2)?)),
+        TYPE_SINT => Ok(SInt128::de_serialize(r1)?.cmp(&SInt128::de_serialize(r2)?)),
+        TYPE_FLOAT => match (Float::de_serialize(r1)?, Float::de_serialize(r2)?) {
+            (Float::F32(value1), Float::F32(value2)) => {
+                Ok(F32Ext::from(value1).cmp(&F32Ext::from(value2)))
+            }
+            (Float::F64(value1), Float::F64(value2)) => {
+                Ok(F64Ext::from(value1).cmp(&F64Ext::from(value2)))
+            }
+            (Float::F32(_), Float::F64(_)) => Ok(Ordering::Less),
+            (Float::F64(_), Float::F32(_)) => Ok(Ordering::Greater),
+        },
+        TYPE_BINARY => Ok(Binary::de_serialize(r1)?.cmp(Binary::de_serialize(r2)?)),
+        TYPE_UNICODE => {
+            Ok(UncheckedUnicode::de_serialize(r1)?.cmp(UncheckedUnicode::de_serialize(r2)?))
+        }
+        TYPE_OPTION => match (Presence::de_serialize(r1)?, Presence::de_serialize(r2)?) {
+            (Presence::Absent, Presence::Absent) => Ok(Ordering::Equal),
+            (Presence::Present, Presence::Present) => compare_next(r1, r2),
+            (Presence::Absent, Presence::Present) => Ok(Ordering::Less),
+            (Presence::Present, Presence::Absent) => Ok(Ordering::Greater),
+        },
+        TYPE_SEQ => {
+            let length1 = SeqHeader::de_serialize(r1)?.length();
+            let length2 = SeqHeader::de_serialize(r2)?.length();
+            compare_n(r1, r2, length1, length2)
+        }
+        TYPE_ENUM_0 => {
+            let header1 = EnumHeader::de_serialize(r1)?;
+            let header2 = EnumHeader::de_serialize(r2)?;
+            let ordinal_cmp = header1.ordinal().cmp(&header2.ordinal());
+            if ordinal_cmp != Ordering::Equal {
+                Ok(ordinal_cmp)
+            } else {
+                compare_n(
+                    r1,
+                    r2,
+                    header1.number_of_values(),
+                    header2.number_of_values(),
+                )
+            }
+        }
+        _ => LqError::err_new(lq_msg!(
+            "Unable to compare values of unknown major type {:?}.",
+            kind1
+        )),
+    }
+}
+
+/// Major types that are compared the same way are mapped to the same kind (booleans to
+/// `TYPE_BOOL_FALSE`, enums to `TYPE_ENUM_0`).
+fn kind(major_type: MajorType) -> MajorType {
+    match major_type {
+        TYPE_BOOL_TRUE => TYPE_BOOL_FALSE,
+        TYPE_ENUM_1 | TYPE_ENUM_2 | TYPE_ENUM_3 | TYPE_ENUM_N => TYPE_ENUM_0,
+        other => other,
+    }
+}
+
+/// Lexicographic compare of `length1` / `length2` values.
+fn compare_n<'a>(
+    r1: &mut SliceReader<'a>,
+    r2: &mut SliceReader<'a>,
+    length1: u32,
+    length2: u32,
+) -> Result<Ordering, LqError> {
+    for _ in 0..length1.min(length2) {
+        let cmp = compare_next(r1, r2)?;
+        if cmp != Ordering::Equal {
+            return Ok(cmp);
+        }
+    }
+    Ok(length1.cmp(&length2))
+}
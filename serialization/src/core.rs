@@ -1,7 +1,11 @@
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use enum_repr::EnumRepr;
-use varuint::*;
 
+use crate::len_writer::LenWriter;
+use crate::limits::ReadLimitState;
+use crate::varint;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
 use liquesco_common::error::LqError;
 
 /// The major type can be within 0-24 (inclusive).
@@ -67,60 +71,66 @@ pub trait Serializer {
     fn serialize<W: LqWriter>(writer: &mut W, item: &Self::Item) -> Result<(), LqError>;
 }
 
-pub trait LqWriter: std::io::Write + Sized {
+pub trait LqWriter: Sized {
     fn write_u8(&mut self, data: u8) -> Result<(), LqError>;
     fn write_slice(&mut self, buf: &[u8]) -> Result<(), LqError>;
 
+    /// Returns `Some` if this writer only counts the bytes (see `LenWriter`). Serializers can
+    /// use this to compute lengths instead of buffering data.
+    fn as_len_writer(&mut self) -> Option<&mut LenWriter> {
+        None
+    }
+
     fn write_varint_u64(&mut self, value: u64) -> Result<(), LqError> {
-        Ok(WriteVarint::<u64>::write_varint(self, value).map(|_| {})?)
+        varint::write(self, value)
     }
 
     fn write_varint_u32(&mut self, value: u32) -> Result<(), LqError> {
-        Ok(WriteVarint::<u32>::write_varint(self, value).map(|_| {})?)
+        varint::write(self, u64::from(value))
     }
 
     fn write_u16(&mut self, data: u16) -> Result<(), LqError> {
-        Ok(WriteBytesExt::write_u16::<LittleEndian>(self, data)?)
+        self.write_slice(&data.to_le_bytes())
     }
 
     fn write_u32(&mut self, data: u32) -> Result<(), LqError> {
-        Ok(WriteBytesExt::write_u32::<LittleEndian>(self, data)?)
+        self.write_slice(&data.to_le_bytes())
     }
 
     fn write_u64(&mut self, data: u64) -> Result<(), LqError> {
-        Ok(WriteBytesExt::write_u64::<LittleEndian>(self, data)?)
+        self.write_slice(&data.to_le_bytes())
     }
 
     fn write_u128(&mut self, data: u128) -> Result<(), LqError> {
-        Ok(WriteBytesExt::write_u128::<LittleEndian>(self, data)?)
+        self.write_slice(&data.to_le_bytes())
     }
 
     fn write_i8(&mut self, data: i8) -> Result<(), LqError> {
-        Ok(WriteBytesExt::write_i8(self, data)?)
+        self.write_slice(&data.to_le_bytes())
     }
 
     fn write_i16(&mut self, data: i16) -> Result<(), LqError> {
-        Ok(WriteBytesExt::write_i16::<LittleEndian>(self, data)?)
+        self.write_slice(&data.to_le_bytes())
     }
 
     fn write_i32(&mut self, data: i32) -> Result<(), LqError> {
-        Ok(WriteBytesExt::write_i32::<LittleEndian>(self, data)?)
+        self.write_slice(&data.to_le_bytes())
     }
 
     fn write_i64(&mut self, data: i64) -> Result<(), LqError> {
-        Ok(WriteBytesExt::write_i64::<LittleEndian>(self, data)?)
+        self.write_slice(&data.to_le_bytes())
     }
 
     fn write_i128(&mut self, data: i128) -> Result<(), LqError> {
-        Ok(WriteBytesExt::write_i128::<LittleEndian>(self, data)?)
+        self.write_slice(&data.to_le_bytes())
     }
 
     fn write_f32(&mut self, data: f32) -> Result<(), LqError> {
-        Ok(WriteBytesExt::write_f32::<LittleEndian>(self, data)?)
+        self.write_slice(&data.to_bits().to_le_bytes())
     }
 
     fn write_f64(&mut self, data: f64) -> Result<(), LqError> {
-        Ok(WriteBytesExt::write_f64::<LittleEndian>(self, data)?)
+        self.write_slice(&data.to_bits().to_le_bytes())
     }
 
     fn write_header_byte(&mut self, header: HeaderByte) -> Result<(), LqError> {
@@ -134,88 +144,147 @@ pub trait LqWriter: std::io::Write + Sized {
     ) -> Result<(), LqError> {
         let self_len = content_description.self_length;
         let number_of_embedded_values = content_description.number_of_embedded_items;
-        if number_of_embedded_values == 0 {
-            let marker = match self_len {
-                0 => ContentInfo::Len0,
-                1 => ContentInfo::Len1,
-                2 => ContentInfo::Len2,
-                4 => ContentInfo::Len4,
-                8 => ContentInfo::Len8,
-                16 => ContentInfo::Len16,
-                _ => ContentInfo::VarInt,
-            };
-            self.write_header_byte(HeaderByte::new(marker, major_type))?;
-            if marker == ContentInfo::VarInt {
-                LqWriter::write_varint_u64(self, self_len)?;
+        let content_info = content_description.canonical_content_info();
+        self.write_header_byte(HeaderByte::new(content_info, major_type))?;
+        match content_info {
+            ContentInfo::VarInt => LqWriter::write_varint_u64(self, self_len),
+            ContentInfo::ContainerVarIntEmpty => self.write_varint_u32(number_of_embedded_values),
+            ContentInfo::ContainerVarIntVarInt => {
+                self.write_varint_u32(number_of_embedded_values)?;
+                self.write_varint_u64(self_len)
             }
-            Result::Ok(())
-        } else if self_len == 0 && number_of_embedded_values == 1 {
-            self.write_header_byte(HeaderByte::new(ContentInfo::ContainerOneEmpty, major_type))
-        } else if self_len == 0 && number_of_embedded_values == 2 {
-            self.write_header_byte(HeaderByte::new(ContentInfo::ContainerTwoEmpty, major_type))
-        } else if self_len == 1 && number_of_embedded_values == 1 {
-            self.write_header_byte(HeaderByte::new(ContentInfo::ContainerOneOne, major_type))
-        } else if self_len == 0 {
-            self.write_header_byte(HeaderByte::new(
-                ContentInfo::ContainerVarIntEmpty,
-                major_type,
-            ))?;
-            self.write_varint_u32(number_of_embedded_values)
-        } else {
-            self.write_header_byte(HeaderByte::new(
-                ContentInfo::ContainerVarIntVarInt,
-                major_type,
-            ))?;
-            self.write_varint_u32(number_of_embedded_values)?;
-            self.write_varint_u64(self_len)
+            _ => Result::Ok(()),
         }
     }
 }
 
 /// A `LqWriter` that writes into a `Vec<u8>`.
+#[cfg(feature = "alloc")]
 pub trait ToVecLqWriter: LqWriter {
     /// Finishes the writer and returns the written data as `Vec<u8>`.
     fn into_vec(self) -> Vec<u8>;
 }
 
-pub trait LqReader<'a>: std::io::Read {
+pub trait LqReader<'a> {
     fn peek_u8(&self) -> Result<u8, LqError>;
     fn read_u8(&mut self) -> Result<u8, LqError>;
     fn read_slice(&mut self, len: usize) -> Result<&'a [u8], LqError>;
 
+    /// Reads exactly `buf.len()` bytes into given buffer. Readers should override this if
+    /// `read_slice` is expensive (e.g. if it has to copy the data).
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<(), LqError> {
+        let slice = self.read_slice(buf.len())?;
+        buf.copy_from_slice(slice);
+        Result::Ok(())
+    }
+
+    /// Reads `len` bytes and passes them to given function. In contrast to `read_slice` the
+    /// bytes only have to be valid while the function is called. Readers that do not hold all
+    /// data in memory (like `IoReader`) can implement this without copying.
+    fn read_slice_with<Ret, F>(&mut self, len: usize, function: F) -> Result<Ret, LqError>
+    where
+        F: FnOnce(&[u8]) -> Result<Ret, LqError>,
+        Self: Sized,
+    {
+        let slice = self.read_slice(len)?;
+        function(slice)
+    }
+
     /// creates a clone that shares the underlying buffer but
     /// has an independent read offset (cursor).
     fn clone(&self) -> Self
     where
         Self: Sized;
 
+    /// The read offset (number of bytes read since the start of the data); `None` if the
+    /// reader does not know the offset. This is used for error reports.
+    fn read_offset(&self) -> Option<u64> {
+        None
+    }
+
+    /// If this returns true, de-serializers only accept canonical encodings: Every value
+    /// has one single possible representation; everything else is rejected (see
+    /// `canonical::CanonicalReader`). This is false by default.
+    fn canonical_only(&self) -> bool {
+        false
+    }
+
+    /// The read limits (see `limits::LimitedReader`); `None` if there are no limits (this is
+    /// the default).
+    fn limit_state(&mut self) -> Option<&mut ReadLimitState> {
+        None
+    }
+
+    /// Increases the nesting depth (see `limit_state`).
+    fn limit_enter(&mut self) -> Result<(), LqError> {
+        if let Some(state) = self.limit_state() {
+            state.enter()
+        } else {
+            Result::Ok(())
+        }
+    }
+
+    /// Decreases the nesting depth (see `limit_enter`).
+    fn limit_exit(&mut self) {
+        if let Some(state) = self.limit_state() {
+            state.exit()
+        }
+    }
+
+    /// Accounts for `number_of_bytes` allocated bytes (see `limit_state`).
+    fn limit_allocate(&mut self, number_of_bytes: u64) -> Result<(), LqError> {
+        if let Some(state) = self.limit_state() {
+            state.allocate(number_of_bytes)
+        } else {
+            Result::Ok(())
+        }
+    }
+
     fn peek_header_byte(&self) -> Result<HeaderByte, LqError> {
         let value = self.peek_u8()?;
         Result::Ok(HeaderByte::from_u8(value))
     }
 
     fn read_varint_u32(&mut self) -> Result<u32, LqError> {
-        Ok(ReadVarint::<u32>::read_varint(self)?)
+        let value = self.read_varint_u64()?;
+        u32::try_from(value).map_err(|_| {
+            LqError::new(lq_msg!(
+                "Invalid var int: Expecting a 32 bit var int; got {}.",
+                value
+            ))
+        })
     }
 
     fn read_varint_u64(&mut self) -> Result<u64, LqError> {
-        Ok(ReadVarint::<u64>::read_varint(self)?)
+        let (value, len) = varint::read(self)?;
+        if self.canonical_only() {
+            require_canonical_varint(varint::len(value), len)?;
+        }
+        Ok(value)
     }
 
     fn read_u16(&mut self) -> Result<u16, LqError> {
-        Ok(ReadBytesExt::read_u16::<LittleEndian>(self)?)
+        let mut buf = [0u8; 2];
+        self.read_into(&mut buf)?;
+        Ok(u16::from_le_bytes(buf))
     }
 
     fn read_u32(&mut self) -> Result<u32, LqError> {
-        Ok(ReadBytesExt::read_u32::<LittleEndian>(self)?)
+        let mut buf = [0u8; 4];
+        self.read_into(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
     }
 
     fn read_u64(&mut self) -> Result<u64, LqError> {
-        Ok(ReadBytesExt::read_u64::<LittleEndian>(self)?)
+        let mut buf = [0u8; 8];
+        self.read_into(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
     }
 
     fn read_u128(&mut self) -> Result<u128, LqError> {
-        Ok(ReadBytesExt::read_u128::<LittleEndian>(self)?)
+        let mut buf = [0u8; 16];
+        self.read_into(&mut buf)?;
+        Ok(u128::from_le_bytes(buf))
     }
 
     fn read_header_byte(&mut self) -> Result<HeaderByte, LqError> {
@@ -224,31 +293,31 @@ pub trait LqReader<'a>: std::io::Read {
     }
 
     fn read_i8(&mut self) -> Result<i8, LqError> {
-        Ok(ReadBytesExt::read_i8(self)?)
+        Ok(LqReader::read_u8(self)? as i8)
     }
 
     fn read_i16(&mut self) -> Result<i16, LqError> {
-        Ok(ReadBytesExt::read_i16::<LittleEndian>(self)?)
+        Ok(self.read_u16()? as i16)
     }
 
     fn read_i32(&mut self) -> Result<i32, LqError> {
-        Ok(ReadBytesExt::read_i32::<LittleEndian>(self)?)
+        Ok(self.read_u32()? as i32)
     }
 
     fn read_i64(&mut self) -> Result<i64, LqError> {
-        Ok(ReadBytesExt::read_i64::<LittleEndian>(self)?)
+        Ok(self.read_u64()? as i64)
     }
 
     fn read_i128(&mut self) -> Result<i128, LqError> {
-        Ok(ReadBytesExt::read_i128::<LittleEndian>(self)?)
+        Ok(self.read_u128()? as i128)
     }
 
     fn read_f32(&mut self) -> Result<f32, LqError> {
-        Ok(ReadBytesExt::read_f32::<LittleEndian>(self)?)
+        Ok(f32::from_bits(self.read_u32()?))
     }
 
     fn read_f64(&mut self) -> Result<f64, LqError> {
-        Ok(ReadBytesExt::read_f64::<LittleEndian>(self)?)
+        Ok(f64::from_bits(self.read_u64()?))
     }
 
     fn read_expect_content_description(
@@ -260,16 +329,18 @@ pub trait LqReader<'a>: std::io::Read {
         let content_description = self.read_content_description_given_header_byte(type_header)?;
 
         if content_description.number_of_embedded_items != number_of_embedded_values {
-            return LqError::err_new(format!(
+            return LqError::err_new(lq_msg!(
                 "Expecting to have {:?} embedded values for this type; have {:?} embedded values.",
-                number_of_embedded_values, content_description.number_of_embedded_items
+                number_of_embedded_values,
+                content_description.number_of_embedded_items
             ));
         }
 
         if content_description.self_length != self_len {
-            return LqError::err_new(format!(
+            return LqError::err_new(lq_msg!(
                 "Expecting to a length of {:?} bytes but have a length of {:?} bytes.",
-                self_len, content_description.self_length
+                self_len,
+                content_description.self_length
             ));
         }
 
@@ -285,7 +356,7 @@ pub trait LqReader<'a>: std::io::Read {
         &mut self,
         header: HeaderByte,
     ) -> Result<ContentDescription, LqError> {
-        match header.content_info() {
+        let content_description = match header.content_info() {
             ContentInfo::Len0 => Result::Ok(ContentDescription {
                 number_of_embedded_items: 0,
                 self_length: 0,
@@ -348,51 +419,75 @@ pub trait LqReader<'a>: std::io::Read {
                 "Cannot decode content description: Got the reserved content info 
                 (must not be found; this is reserved for future extensions).",
             ),
+        }?;
+
+        if let Some(state) = self.limit_state() {
+            state.check_items(content_description.number_of_embedded_items)?;
+        }
+
+        if self.canonical_only() {
+            let canonical_content_info = content_description.canonical_content_info();
+            if header.content_info() != canonical_content_info {
+                return LqError::err_new(lq_msg!(
+                    "Non-canonical content description: {:?} has to be encoded as {:?}; \
+                     got {:?}.",
+                    content_description,
+                    canonical_content_info,
+                    header.content_info()
+                ));
+            }
         }
+        Result::Ok(content_description)
     }
 
     /// Skips a type and all embedded items.
+    ///
+    /// Skipping is iterative (not recursive): Deeply nested data cannot overflow the stack
+    /// (this is also why the nesting depth limit does not apply to skipping). The limits for
+    /// the number of items and the binary length do apply (see `limit_state`).
     fn skip(&mut self) -> Result<(), LqError> {
-        let header = self.read_header_byte()?;
-        let content_description = self.read_content_description_given_header_byte(header)?;
-        // first skip "myself"
-        let self_length = content_description.self_length;
-        if self_length > 0 {
-            self.skip_bytes_u64(content_description.self_length)?;
-        }
-        // then skip all embedded values
-        let number_of_embedded_values = content_description.number_of_embedded_items;
-        if number_of_embedded_values > 0 {
-            for _ in 0..number_of_embedded_values {
-                self.skip()?;
-            }
-        }
-        Result::Ok(())
+        self.skip_n_values_u64(1)
     }
 
     /// Same as `skip` but can skip multiple values.
     fn skip_n_values(&mut self, number_of_values: usize) -> Result<(), LqError> {
-        for _ in 0..number_of_values {
-            self.skip()?;
-        }
-        Result::Ok(())
+        self.skip_n_values_u64(u64::try_from(number_of_values)?)
     }
 
     /// Same as `skip` but can skip multiple values.
     fn skip_n_values_u32(&mut self, number_of_values: u32) -> Result<(), LqError> {
-        for _ in 0..number_of_values {
-            self.skip()?;
+        self.skip_n_values_u64(u64::from(number_of_values))
+    }
+
+    /// Same as `skip` but can skip multiple values.
+    fn skip_n_values_u64(&mut self, number_of_values: u64) -> Result<(), LqError> {
+        // work counter: number of values still to be skipped (embedded values included).
+        let mut remaining = number_of_values;
+        while remaining > 0 {
+            remaining -= 1;
+            let header = self.read_header_byte()?;
+            let content_description = self.read_content_description_given_header_byte(header)?;
+            // first skip "myself"
+            let self_length = content_description.self_length;
+            if let Some(state) = self.limit_state() {
+                // not allocated but skipped values must not bypass the limit
+                state.check_binary_len(self_length)?;
+            }
+            if self_length > 0 {
+                self.skip_bytes_u64(self_length)?;
+            }
+            // then all embedded values
+            remaining += u64::from(content_description.number_of_embedded_items);
         }
         Result::Ok(())
     }
 
     fn skip_bytes(&mut self, number_of_bytes: usize) -> Result<(), LqError> {
-        for _ in 0..number_of_bytes {
-            self.read_u8()?;
-        }
-        Result::Ok(())
+        self.skip_bytes_u64(u64::try_from(number_of_bytes)?)
     }
 
+    /// Skips given number of bytes. The default implementation reads byte by byte; readers
+    /// should override this if they can skip in bulk.
     fn skip_bytes_u64(&mut self, number_of_bytes: u64) -> Result<(), LqError> {
         for _ in 0..number_of_bytes {
             self.read_u8()?;
@@ -451,6 +546,34 @@ impl ContentDescription {
         self.number_of_embedded_items
     }
 
+    /// The content info used to encode this content description. There's only one valid
+    /// (canonical) content info for every content description.
+    pub fn canonical_content_info(&self) -> ContentInfo {
+        let self_len = self.self_length;
+        let number_of_embedded_values = self.number_of_embedded_items;
+        if number_of_embedded_values == 0 {
+            match self_len {
+                0 => ContentInfo::Len0,
+                1 => ContentInfo::Len1,
+                2 => ContentInfo::Len2,
+                4 => ContentInfo::Len4,
+                8 => ContentInfo::Len8,
+                16 => ContentInfo::Len16,
+                _ => ContentInfo::VarInt,
+            }
+        } else if self_len == 0 && number_of_embedded_values == 1 {
+            ContentInfo::ContainerOneEmpty
+        } else if self_len == 0 && number_of_embedded_values == 2 {
+            ContentInfo::ContainerTwoEmpty
+        } else if self_len == 1 && number_of_embedded_values == 1 {
+            ContentInfo::ContainerOneOne
+        } else if self_len == 0 {
+            ContentInfo::ContainerVarIntEmpty
+        } else {
+            ContentInfo::ContainerVarIntVarInt
+        }
+    }
+
     pub fn set_number_of_embedded_items(&mut self, number_of_embedded_values: u32) {
         self.number_of_embedded_items = number_of_embedded_values;
     }
@@ -484,3 +607,16 @@ impl Default for ContentDescription {
         }
     }
 }
+
+fn require_canonical_varint(canonical_len: usize, len: usize) -> Result<(), LqError> {
+    if canonical_len != len {
+        LqError::err_new(lq_msg!(
+            "Non-canonical var int: Var int has been encoded using {} bytes; the canonical \
+             encoding takes {} bytes.",
+            len,
+            canonical_len
+        ))
+    } else {
+        Result::Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+//! Base64 (standard alphabet, with padding; RFC 4648).
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub(crate) fn encode(data: &[u8], target: &mut String) {
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let bits = u32::from(bytes[0]) << 16 | u32::from(bytes[1]) << 8 | u32::from(bytes[2]);
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let sextet = (bits >> (18 - index * 6)) & 0x3f;
+                target.push(char::from(ALPHABET[sextet as usize]));
+            } else {
+                target.push('=');
+            }
+        }
+    }
+}
+
+/// Decodes base64; returns `None` if the text is not valid base64.
+pub(crate) fn decode(text: &str) -> Option<Vec<u8>> {
+    let bytes = text.as_bytes();
+    if !bytes.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut result = Vec::with_capacity(bytes.len() / 4 * 3);
+    for (chunk_index, chunk) in bytes.chunks(4).enumerate() {
+        let last_chunk = chunk_index == bytes.len() / 4 - 1;
+        let padding = chunk.iter().rev().take_while(|chr| **chr == b'=').count();
+        if padding > 2 || (padding > 0 && !last_chunk) {
+            return None;
+        }
+        let mut bits = 0u32;
+        for chr in &chunk[0..4 - padding] {
+            let sextet = ALPHABET.iter().position(|candidate| candidate == chr)?;
+            bits = bits << 6 | sextet as u32;
+        }
+        bits <<= 6 * padding as u32;
+        let decoded = [(bits >> 16) as u8, (bits >> 8) as u8, bits as u8];
+        result.extend_from_slice(&decoded[0..3 - padding]);
+    }
+    Some(result)
+}
@@ -0,0 +1,174 @@
+use crate::diagnostic::base64;
+use crate::diagnostic::BinaryNotation;
+use crate::diagnostic::FormatOptions;
+use crate::types::float::Float;
+use crate::value::Value;
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Write;
+use core::ops::Deref;
+use liquesco_common::error::LqError;
+
+pub(crate) fn format_value(
+    target: &mut String,
+    value: &Value,
+    options: &FormatOptions,
+) -> Result<(), LqError> {
+    Formatter {
+        target,
+        options,
+        depth: 0,
+    }
+    .value(value, 0)
+}
+
+struct Formatter<'a> {
+    target: &'a mut String,
+    options: &'a FormatOptions,
+    /// Current nesting depth.
+    depth: u32,
+}
+
+impl<'a> Formatter<'a> {
+    fn value(&mut self, value: &Value, level: usize) -> Result<(), LqError> {
+        // writing to a string never fails
+        match value {
+            Value::Bool(true) => self.target.push_str("true"),
+            Value::Bool(false) => self.target.push_str("false"),
+            Value::UInt(value) => write!(self.target, "{}", value).unwrap(),
+            Value::SInt(value) => write!(self.target, "{:+}", value).unwrap(),
+            Value::Float(value) => self.float(*value),
+            Value::Unicode(value) => self.unicode(value),
+            Value::Binary(value) => self.binary(value),
+            Value::Option(None) => self.target.push_str("none"),
+            Value::Option(Some(present)) => {
+                self.target.push_str("some(");
+                self.nested(|formatter| formatter.value(present.deref(), level))?;
+                self.target.push(')');
+            }
+            Value::Seq(values) => {
+                let inline = depth(value) <= self.options.max_inline_depth;
+                self.target.push('[');
+                self.nested(|formatter| formatter.values(values, level, inline))?;
+                self.target.push(']');
+            }
+            Value::Enum(variant) => {
+                write!(self.target, "#{}", variant.ordinal()).unwrap();
+                if !variant.values().is_empty() {
+                    let inline = depth(value) <= self.options.max_inline_depth;
+                    self.target.push('(');
+                    self.nested(|formatter| formatter.values(variant.values(), level, inline))?;
+                    self.target.push(')');
+                }
+            }
+        }
+        Result::Ok(())
+    }
+
+    /// Formats nested values (checks the nesting depth).
+    fn nested<F>(&mut self, format: F) -> Result<(), LqError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), LqError>,
+    {
+        if self.depth >= self.options.max_depth {
+            return LqError::err_new(format!(
+                "Unable to format the value: Values are nested too deeply (maximum depth is {}).",
+                self.options.max_depth
+            ));
+        }
+        self.depth += 1;
+        let result = format(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn values(&mut self, values: &[Value], level: usize, inline: bool) -> Result<(), LqError> {
+        if values.is_empty() {
+            return Result::Ok(());
+        }
+        for (index, value) in values.iter().enumerate() {
+            if inline {
+                if index > 0 {
+                    self.target.push_str(", ");
+                }
+            } else {
+                if index > 0 {
+                    self.target.push(',');
+                }
+                self.new_line(level + 1);
+            }
+            self.value(value, level + 1)?;
+        }
+        if !inline {
+            self.new_line(level);
+        }
+        Result::Ok(())
+    }
+
+    fn new_line(&mut self, level: usize) {
+        self.target.push('\n');
+        for _ in 0..level * self.options.indent {
+            self.target.push(' ');
+        }
+    }
+
+    fn float(&mut self, value: Float) {
+        match value {
+            Float::F32(value) if value.is_nan() && value.to_bits() != f32::NAN.to_bits() => {
+                write!(self.target, "f32(0x{:08x})", value.to_bits()).unwrap()
+            }
+            Float::F64(value) if value.is_nan() && value.to_bits() != f64::NAN.to_bits() => {
+                write!(self.target, "f64(0x{:016x})", value.to_bits()).unwrap()
+            }
+            Float::F32(value) if value.is_nan() => self.target.push_str("nan_f32"),
+            Float::F64(value) if value.is_nan() => self.target.push_str("nan_f64"),
+            // debug formatting is the shortest representation that round-trips
+            Float::F32(value) => write!(self.target, "{:?}_f32", value).unwrap(),
+            Float::F64(value) => write!(self.target, "{:?}_f64", value).unwrap(),
+        }
+    }
+
+    fn unicode(&mut self, value: &str) {
+        self.target.push('"');
+        for chr in value.chars() {
+            match chr {
+                '"' => self.target.push_str("\\\""),
+                '\\' => self.target.push_str("\\\\"),
+                '\n' => self.target.push_str("\\n"),
+                '\r' => self.target.push_str("\\r"),
+                '\t' => self.target.push_str("\\t"),
+                '\0' => self.target.push_str("\\0"),
+                chr if chr.is_control() => write!(self.target, "\\u{{{:x}}}", chr as u32).unwrap(),
+                chr => self.target.push(chr),
+            }
+        }
+        self.target.push('"');
+    }
+
+    fn binary(&mut self, value: &[u8]) {
+        match self.options.binary {
+            BinaryNotation::Hex => {
+                self.target.push_str("h'");
+                for byte in value {
+                    write!(self.target, "{:02x}", byte).unwrap();
+                }
+            }
+            BinaryNotation::Base64 => {
+                self.target.push_str("b64'");
+                base64::encode(value, self.target);
+            }
+        }
+        self.target.push('\'');
+    }
+}
+
+/// Nesting depth of containers: 0 for scalars, 1 for containers only containing scalars.
+/// Options are transparent.
+fn depth(value: &Value) -> usize {
+    match value {
+        Value::Option(Some(present)) => depth(present),
+        Value::Seq(values) => 1 + values.iter().map(depth).max().unwrap_or(0),
+        Value::Enum(variant) => 1 + variant.values().iter().map(depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
@@ -0,0 +1,144 @@
+//! Diagnostic notation: A human readable text notation for liquesco data (like CBOR's
+//! diagnostic notation). Use this to inspect data without a schema (e.g. when debugging
+//! captured payloads); the notation can be parsed back to the binary data (see `parse`).
+//!
+//! | Type       | Notation                                  | Remarks                           |
+//! |------------|-------------------------------------------|-----------------------------------|
+//! | Bool       | `true`, `false`                           |                                   |
+//! | UInt       | `0`, `42`                                 | Unsigned integers have no sign.   |
+//! | SInt       | `+0`, `+42`, `-42`                        | Signed integers always have a sign.|
+//! | Float      | `1.5_f32`, `-0.0_f64`, `inf_f32`, `-inf_f64`, `nan_f64` | The width is explicit. |
+//! |            | `f32(0x7fc00001)`, `f64(0x...)`           | Raw bits (used for NaN payloads). |
+//! | Unicode    | `"text"`                                  | Escapes: `\"`, `\\`, `\n`, `\r`, `\t`, `\0`, `\u{7f}`. |
+//! | Binary     | `h'0aff'`, `b64'Cv8='`                    | Hex or base64 (standard, padded). |
+//! | Option     | `none`, `some(42)`                        |                                   |
+//! | Seq        | `[]`, `[1, 2, 3]`                         |                                   |
+//! | Enum       | `#0`, `#1(42)`, `#2("a", +5)`             | Ordinal and the values (if any).  |
+//!
+//! Whitespace is not significant; elements are separated by `,` (a trailing comma is
+//! allowed); `//` starts a comment (until the end of the line).
+//!
+//! Parsing the notation always produces the canonical encoding. So `parse(format(data))`
+//! returns exactly `data`; data that is not canonically encoded (e.g. headers that are
+//! longer than necessary) cannot be formatted (see `format_with`).
+//!
+//! Parsing and formatting are recursive; both fail if the nesting depth (sequences, enums
+//! with values and present options) exceeds a maximum (`DEFAULT_MAX_DEPTH` by default).
+//!
+//! ```text
+//! [
+//!   "Demo",
+//!   42,
+//!   some(1.8_f32),
+//!   #1(
+//!     [+1, -1],
+//!     h'cafe'
+//!   )
+//! ]
+//! ```
+
+mod base64;
+mod format;
+mod parse;
+
+use crate::core::DeSerializer;
+use crate::core::Serializer;
+use crate::core::ToVecLqWriter;
+use crate::limits::LimitedReader;
+use crate::limits::ReadLimits;
+use crate::slice_reader::SliceReader;
+use crate::value::Value;
+use crate::vec_writer::VecWriter;
+use alloc::string::String;
+use alloc::vec::Vec;
+use liquesco_common::error::LqError;
+
+pub use crate::diagnostic::parse::parse_value;
+pub use crate::diagnostic::parse::parse_value_with;
+
+/// Default maximum nesting depth for parsing and formatting (same as `ReadLimits`).
+pub const DEFAULT_MAX_DEPTH: u32 = 128;
+
+/// How binaries are written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryNotation {
+    /// `h'0aff'`
+    Hex,
+    /// `b64'Cv8='`
+    Base64,
+}
+
+/// Options for formatting data using the diagnostic notation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Number of spaces per indentation level.
+    pub indent: usize,
+    /// Containers (sequences, enums) with at most this number of nesting levels are written
+    /// on a single line: 0 writes every element on its own line; 1 writes containers only
+    /// containing scalars on a single line.
+    pub max_inline_depth: usize,
+    pub binary: BinaryNotation,
+    /// Maximum nesting depth (sequences, enums with values and present options).
+    pub max_depth: u32,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            indent: 2,
+            max_inline_depth: 1,
+            binary: BinaryNotation::Hex,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+/// Formats the data (a single value) using the default options.
+pub fn format(data: &[u8]) -> Result<String, LqError> {
+    format_with(data, &FormatOptions::default())
+}
+
+/// Formats the data (a single value). Returns an error if the data is invalid, if there's
+/// more data after the value, if the data is nested too deeply or if the data is not
+/// canonically encoded (the notation cannot express the encoding; parsing it would not
+/// return the same data).
+pub fn format_with(data: &[u8], options: &FormatOptions) -> Result<String, LqError> {
+    let limits = ReadLimits {
+        max_depth: options.max_depth,
+        ..ReadLimits::unlimited()
+    };
+    let mut reader = LimitedReader::new(SliceReader::from(data), limits);
+    let value = Value::de_serialize(&mut reader)?;
+    reader.into_inner().finish()?;
+
+    if serialize(&value)? != data {
+        return LqError::err_new(
+            "The data is not canonically encoded (e.g. a header is longer than \
+             necessary); the diagnostic notation can only express canonically encoded data.",
+        );
+    }
+    format_value(&value, options)
+}
+
+/// Formats a value. Returns an error if the value is nested too deeply.
+pub fn format_value(value: &Value, options: &FormatOptions) -> Result<String, LqError> {
+    let mut result = String::new();
+    format::format_value(&mut result, value, options)?;
+    Result::Ok(result)
+}
+
+/// Parses the diagnostic notation and returns the (canonically encoded) data.
+pub fn parse(text: &str) -> Result<Vec<u8>, LqError> {
+    serialize(&parse_value(text)?)
+}
+
+/// Like `parse` but with given maximum nesting depth.
+pub fn parse_with(text: &str, max_depth: u32) -> Result<Vec<u8>, LqError> {
+    serialize(&parse_value_with(text, max_depth)?)
+}
+
+fn serialize(value: &Value) -> Result<Vec<u8>, LqError> {
+    let mut writer = VecWriter::default();
+    Value::serialize(&mut writer, value)?;
+    Result::Ok(writer.into_vec())
+}
@@ -0,0 +1,331 @@
+use crate::diagnostic::base64;
+use crate::diagnostic::DEFAULT_MAX_DEPTH;
+use crate::types::float::Float;
+use crate::value::Value;
+use crate::value::ValueSeq;
+use crate::value::ValueVariant;
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use liquesco_common::error::LqError;
+
+/// Parses the diagnostic notation (see module documentation).
+pub fn parse_value(text: &str) -> Result<Value<'static>, LqError> {
+    parse_value_with(text, DEFAULT_MAX_DEPTH)
+}
+
+/// Parses the diagnostic notation; fails if values are nested deeper than `max_depth`.
+pub fn parse_value_with(text: &str, max_depth: u32) -> Result<Value<'static>, LqError> {
+    let mut parser = Parser {
+        text,
+        offset: 0,
+        depth: 0,
+        max_depth,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.offset < text.len() {
+        return parser.err("Expecting the end of the text (there's more after the value)");
+    }
+    Result::Ok(value)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    offset: usize,
+    /// Current nesting depth.
+    depth: u32,
+    max_depth: u32,
+}
+
+impl<'a> Parser<'a> {
+    fn value(&mut self) -> Result<Value<'static>, LqError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"') => self.unicode(),
+            Some('[') => {
+                self.offset += 1;
+                let values = self.nested(|parser| parser.values(']'))?;
+                Result::Ok(Value::Seq(ValueSeq::Owned(values)))
+            }
+            Some('#') => self.enumeration(),
+            Some(_) => self.word_value(),
+            None => self.err("Expecting a value; got the end of the text"),
+        }
+    }
+
+    /// Values separated by `,` until `end`.
+    fn values(&mut self, end: char) -> Result<Vec<Value<'static>>, LqError> {
+        let mut values = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(end) {
+                self.offset += 1;
+                return Result::Ok(values);
+            }
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.offset += 1,
+                Some(chr) if chr == end => {}
+                _ => return self.err(&format!("Expecting `,` or `{}`", end)),
+            }
+        }
+    }
+
+    /// Parses nested values (checks the nesting depth).
+    fn nested<T, F>(&mut self, parse: F) -> Result<T, LqError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, LqError>,
+    {
+        if self.depth >= self.max_depth {
+            return self.err(&format!(
+                "Values are nested too deeply (maximum depth is {})",
+                self.max_depth
+            ));
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn enumeration(&mut self) -> Result<Value<'static>, LqError> {
+        self.offset += 1;
+        let digits = self.take_while(|chr| chr.is_ascii_digit());
+        let ordinal = match digits.parse::<u32>() {
+            Result::Ok(ordinal) => ordinal,
+            Result::Err(_) => return self.err("Expecting an enum ordinal (`#0`, `#1`, ...)"),
+        };
+        let values = if self.peek() == Some('(') {
+            self.offset += 1;
+            self.nested(|parser| parser.values(')'))?
+        } else {
+            Vec::new()
+        };
+        Result::Ok(Value::Enum(ValueVariant::new_values(
+            ordinal,
+            if values.is_empty() {
+                ValueSeq::Empty
+            } else {
+                ValueSeq::Owned(values)
+            },
+        )))
+    }
+
+    fn word_value(&mut self) -> Result<Value<'static>, LqError> {
+        let start = self.offset;
+        let word = self.take_while(|chr| chr.is_ascii_alphanumeric() || "_.+-".contains(chr));
+        match word {
+            "" => {
+                self.offset = start;
+                self.err("Expecting a value")
+            }
+            "true" => Result::Ok(Value::Bool(true)),
+            "false" => Result::Ok(Value::Bool(false)),
+            "none" => Result::Ok(Value::Option(None)),
+            "some" => {
+                self.expect('(')?;
+                let present = self.nested(Self::value)?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                Result::Ok(Value::Option(Some(present.into())))
+            }
+            "h" if self.peek() == Some('\'') => {
+                let hex = self.quoted_binary()?;
+                match decode_hex(hex) {
+                    Some(binary) => Result::Ok(Value::Binary(Cow::Owned(binary))),
+                    None => self.err_at(start, "Invalid hex binary"),
+                }
+            }
+            "b64" if self.peek() == Some('\'') => {
+                let text = self.quoted_binary()?;
+                match base64::decode(text) {
+                    Some(binary) => Result::Ok(Value::Binary(Cow::Owned(binary))),
+                    None => self.err_at(start, "Invalid base64 binary"),
+                }
+            }
+            "f32" | "f64" if self.peek() == Some('(') => {
+                self.offset += 1;
+                self.skip_whitespace();
+                let bits_start = self.offset;
+                let bits = self.take_while(|chr| chr.is_ascii_alphanumeric());
+                let bits = bits
+                    .strip_prefix("0x")
+                    .map(|hex| u64::from_str_radix(hex, 16));
+                let float = match (word, bits) {
+                    ("f32", Some(Result::Ok(bits))) => u32::try_from(bits)
+                        .ok()
+                        .map(|bits| Float::F32(f32::from_bits(bits))),
+                    ("f64", Some(Result::Ok(bits))) => Some(Float::F64(f64::from_bits(bits))),
+                    _ => None,
+                };
+                match float {
+                    Some(float) => {
+                        self.skip_whitespace();
+                        self.expect(')')?;
+                        Result::Ok(Value::Float(float))
+                    }
+                    None => self.err_at(bits_start, "Expecting the float bits (e.g. `0x7fc00000`)"),
+                }
+            }
+            word => match parse_number(word) {
+                Some(value) => Result::Ok(value),
+                None => self.err_at(start, &format!("Unknown value `{}`", word)),
+            },
+        }
+    }
+
+    fn unicode(&mut self) -> Result<Value<'static>, LqError> {
+        self.offset += 1;
+        let mut result = String::new();
+        loop {
+            let chr = match self.next() {
+                Some(chr) => chr,
+                None => return self.err("Unterminated text (missing `\"`)"),
+            };
+            match chr {
+                '"' => return Result::Ok(Value::Unicode(Cow::Owned(result))),
+                '\\' => {
+                    let escaped = match self.next() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('0') => '\0',
+                        Some('u') => self.unicode_escape()?,
+                        _ => return self.err("Invalid escape sequence"),
+                    };
+                    result.push(escaped);
+                }
+                chr => result.push(chr),
+            }
+        }
+    }
+
+    /// `\u{7f}` (the `\u` has already been read).
+    fn unicode_escape(&mut self) -> Result<char, LqError> {
+        self.expect('{')?;
+        let hex = self.take_while(|chr| chr.is_ascii_hexdigit());
+        let chr = u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(core::char::from_u32);
+        match chr {
+            Some(chr) => {
+                self.expect('}')?;
+                Result::Ok(chr)
+            }
+            None => self.err("Invalid unicode escape sequence"),
+        }
+    }
+
+    /// The content of `'...'`.
+    fn quoted_binary(&mut self) -> Result<&'a str, LqError> {
+        self.offset += 1;
+        let content = self.take_while(|chr| chr != '\'');
+        self.expect('\'')?;
+        Result::Ok(content)
+    }
+
+    fn skip_whitespace(&mut self) {
+        loop {
+            self.take_while(char::is_whitespace);
+            if self.text[self.offset..].starts_with("//") {
+                self.take_while(|chr| chr != '\n');
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), LqError> {
+        if self.peek() == Some(expected) {
+            self.offset += expected.len_utf8();
+            Result::Ok(())
+        } else {
+            self.err(&format!("Expecting `{}`", expected))
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.offset..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let chr = self.peek()?;
+        self.offset += chr.len_utf8();
+        Some(chr)
+    }
+
+    fn take_while<F: Fn(char) -> bool>(&mut self, predicate: F) -> &'a str {
+        let start = self.offset;
+        let rest = &self.text[start..];
+        let len = rest
+            .char_indices()
+            .find(|(_, chr)| !predicate(*chr))
+            .map(|(index, _)| index)
+            .unwrap_or_else(|| rest.len());
+        self.offset += len;
+        &self.text[start..start + len]
+    }
+
+    fn err<T>(&self, msg: &str) -> Result<T, LqError> {
+        self.err_at(self.offset, msg)
+    }
+
+    fn err_at<T>(&self, offset: usize, msg: &str) -> Result<T, LqError> {
+        let before = &self.text[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|chr| *chr != '\n').count() + 1;
+        LqError::err_new(format!(
+            "Invalid diagnostic notation at line {}, column {}: {}.",
+            line, column, msg
+        ))
+    }
+}
+
+/// `42` (uint), `+42` / `-42` (sint), `1.5_f32` / `nan_f64` (float).
+fn parse_number(word: &str) -> Option<Value<'static>> {
+    if let Some(float) = word.strip_suffix("_f32") {
+        return parse_float::<f32>(float).map(|value| Value::Float(Float::F32(value)));
+    }
+    if let Some(float) = word.strip_suffix("_f64") {
+        return parse_float::<f64>(float).map(|value| Value::Float(Float::F64(value)));
+    }
+    if word.starts_with('+') || word.starts_with('-') {
+        if !word[1..].bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        word.parse::<i128>().ok().map(Value::SInt)
+    } else {
+        if !word.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        word.parse::<u128>().ok().map(Value::UInt)
+    }
+}
+
+fn parse_float<F: core::str::FromStr>(text: &str) -> Option<F> {
+    match text {
+        "nan" => "NaN".parse().ok(),
+        // Rust accepts these; we don't (only `nan`, `inf` and `-inf`)
+        "NaN" | "+nan" | "-nan" | "infinity" | "+infinity" | "-infinity" => None,
+        text => text.parse().ok(),
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let pair = core::str::from_utf8(pair).ok()?;
+            u8::from_str_radix(pair, 16).ok()
+        })
+        .collect()
+}
@@ -0,0 +1,320 @@
+use crate::core::LqReader;
+use liquesco_common::error::LqError;
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::rc::Rc;
+use typed_arena::Arena;
+
+/// Arena that holds the slices returned by `IoReader::read_slice`. Since data from a stream
+/// is not resident in memory, slices have to be copied somewhere where they live as long
+/// as the lifetime of the reader; this is the arena. The arena only grows when slices are
+/// requested (e.g. when de-serializing binaries or unicode text); skipping or reading
+/// scalar values does not allocate in the arena.
+///
+/// Note: The arena is never trimmed; every slice returned stays alive until the arena is
+/// dropped. When reading a long stream of values, use a new arena (and reader) per value
+/// or prefer `read_slice_with` (does not use the arena).
+pub type SliceArena = Arena<u8>;
+
+/// Default maximum number of bytes buffered by `IoReader` (16 MiB).
+pub const DEFAULT_MAX_BUFFER_SIZE: usize = 16 * 1024 * 1024;
+
+/// Number of bytes read from the underlying `Read` at once (if the buffer limit allows).
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+
+/// A `LqReader` on top of any `std::io::Read` (files, pipes, sockets).
+///
+/// Data is buffered in a window. The window starts at the read offset of the reader
+/// that's furthest behind - so `clone()` can be used to create a checkpoint: As long as the
+/// clone is alive, data from the checkpoint on is retained and the clone can re-read it. Data
+/// nobody can read anymore is discarded. The window is bounded (see `with_max_buffer_size`);
+/// reading fails if a checkpoint would require the window to grow beyond that limit.
+///
+/// Note: Clones share the underlying `Read` and buffer; they are cheap to create but are not
+/// thread safe. Slices returned by `read_slice` are copied to the `SliceArena` and are kept
+/// until the arena is dropped - the arena is not bounded by `with_max_buffer_size`.
+pub struct IoReader<'a, R: Read> {
+    shared: Rc<RefCell<Shared<R>>>,
+    arena: &'a SliceArena,
+    slot: usize,
+    offset: u64,
+}
+
+struct Shared<R> {
+    read: R,
+    /// Buffered data; the first byte in the buffer is at offset `buffer_start` of the stream.
+    buffer: Vec<u8>,
+    buffer_start: u64,
+    max_buffer_size: usize,
+    /// The read offsets of all living readers (index is the reader's slot). `None` is a
+    /// free slot.
+    offsets: Vec<Option<u64>>,
+}
+
+impl<'a, R: Read> IoReader<'a, R> {
+    /// Creates a new reader reading from given `read`. Slices returned by `read_slice` are
+    /// allocated in given arena.
+    pub fn new(read: R, arena: &'a SliceArena) -> Self {
+        let shared = Shared {
+            read,
+            buffer: Vec::new(),
+            buffer_start: 0,
+            max_buffer_size: DEFAULT_MAX_BUFFER_SIZE,
+            offsets: vec![Some(0)],
+        };
+        Self {
+            shared: Rc::new(RefCell::new(shared)),
+            arena,
+            slot: 0,
+            offset: 0,
+        }
+    }
+
+    /// The maximum number of bytes to buffer. Note: This is shared by all clones.
+    pub fn with_max_buffer_size(self, max_buffer_size: usize) -> Self {
+        self.shared.borrow_mut().max_buffer_size = max_buffer_size;
+        self
+    }
+
+    /// The number of bytes this reader has consumed so far (read offset).
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Makes sure the reader has been read completely and there's no additional data.
+    pub fn finish(&self) -> Result<(), LqError> {
+        let mut shared = self.shared.borrow_mut();
+        if shared.fill(self.slot, self.offset, 1)? {
+            LqError::err_new(
+                "There's additional data not read from any. The any data must have been consumed
+            entirely (for security reasons).",
+            )
+        } else {
+            Result::Ok(())
+        }
+    }
+
+    /// Makes sure `len` bytes starting at the current read offset are buffered.
+    #[inline]
+    fn ensure(&self, len: usize) -> Result<(), LqError> {
+        // fast path: data is already buffered (this also works while the buffer is lent
+        // out, see `read_slice_with`).
+        if let Ok(shared) = self.shared.try_borrow() {
+            let buffer_end = shared.buffer_start + u64::try_from(shared.buffer.len())?;
+            if self.offset >= shared.buffer_start && self.offset + u64::try_from(len)? <= buffer_end
+            {
+                return Result::Ok(());
+            }
+        }
+
+        let mut shared = self.shared.try_borrow_mut().map_err(|_| {
+            LqError::new(
+                "Unable to fill the buffer of the reader: The buffer is currently lent out \
+                 (nested call to `read_slice_with` on data that has not yet been buffered).",
+            )
+        })?;
+        if shared.fill(self.slot, self.offset, len)? {
+            Result::Ok(())
+        } else {
+            LqError::err_new("End of reader")
+        }
+    }
+
+    /// Index of the current read offset within the buffer (only valid after `ensure`).
+    #[inline]
+    fn buffer_index(&self, shared: &Shared<R>) -> usize {
+        (self.offset - shared.buffer_start) as usize
+    }
+}
+
+impl<R: Read> Shared<R> {
+    /// Makes sure `len` bytes starting at `offset` are within the buffer. Returns `false` if
+    /// the stream ends before.
+    fn fill(&mut self, slot: usize, offset: u64, len: usize) -> Result<bool, LqError> {
+        self.offsets[slot] = Some(offset);
+        let end = offset + u64::try_from(len)?;
+        let buffer_end = self.buffer_start + u64::try_from(self.buffer.len())?;
+        if end <= buffer_end {
+            return Result::Ok(true);
+        }
+        if offset < self.buffer_start {
+            return LqError::err_new(format!(
+                "Data at offset {} has already been discarded from the buffer (buffer starts \
+                 at offset {}).",
+                offset, self.buffer_start
+            ));
+        }
+
+        self.discard_unused();
+
+        let required = usize::try_from(end - self.buffer_start)?;
+        if required > self.max_buffer_size {
+            return LqError::err_new(format!(
+                "Unable to buffer {} bytes; the maximum buffer size is {} bytes. Note: All \
+                 data from the oldest living clone (checkpoint) on has to be buffered.",
+                required, self.max_buffer_size
+            ));
+        }
+
+        let wanted = required
+            .max(self.buffer.len() + READ_CHUNK_SIZE)
+            .min(self.max_buffer_size);
+        while self.buffer.len() < required {
+            let filled = self.buffer.len();
+            self.buffer.resize(wanted, 0);
+            let result = self.read.read(&mut self.buffer[filled..]);
+            match result {
+                Ok(0) => {
+                    self.buffer.truncate(filled);
+                    return Result::Ok(false);
+                }
+                Ok(number_of_bytes) => self.buffer.truncate(filled + number_of_bytes),
+                Err(ref err) if err.kind() == std::io::ErrorKind::Interrupted => {
+                    self.buffer.truncate(filled)
+                }
+                Err(err) => {
+                    self.buffer.truncate(filled);
+                    return Result::Err(err.into());
+                }
+            }
+        }
+        Result::Ok(true)
+    }
+
+    /// Removes data from the buffer no living reader can read anymore.
+    fn discard_unused(&mut self) {
+        let min_offset = self.offsets.iter().filter_map(|offset| *offset).min();
+        if let Some(min_offset) = min_offset {
+            if min_offset > self.buffer_start {
+                let buffer_len = self.buffer.len() as u64;
+                let to_discard = (min_offset - self.buffer_start).min(buffer_len) as usize;
+                self.buffer.drain(..to_discard);
+                self.buffer_start += to_discard as u64;
+            }
+        }
+    }
+}
+
+impl<'a, R: Read> LqReader<'a> for IoReader<'a, R> {
+    #[inline]
+    fn read_offset(&self) -> Option<u64> {
+        Some(self.offset)
+    }
+
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, LqError> {
+        self.ensure(1)?;
+        let shared = self.shared.borrow();
+        Result::Ok(shared.buffer[self.buffer_index(&shared)])
+    }
+
+    #[inline]
+    fn read_u8(&mut self) -> Result<u8, LqError> {
+        let value = self.peek_u8()?;
+        self.offset += 1;
+        Result::Ok(value)
+    }
+
+    /// Note: The slice is copied to the arena.
+    fn read_slice(&mut self, len: usize) -> Result<&'a [u8], LqError> {
+        self.ensure(len)?;
+        let shared = self.shared.borrow();
+        let index = self.buffer_index(&shared);
+        let data = &shared.buffer[index..index + len];
+        let value = self.arena.alloc_extend(data.iter().cloned());
+        self.offset += u64::try_from(len)?;
+        Result::Ok(value)
+    }
+
+    /// Note: Does not copy the data to the arena.
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<(), LqError> {
+        let len = buf.len();
+        self.ensure(len)?;
+        let shared = self.shared.borrow();
+        let index = self.buffer_index(&shared);
+        buf.copy_from_slice(&shared.buffer[index..index + len]);
+        self.offset += u64::try_from(len)?;
+        Result::Ok(())
+    }
+
+    /// Note: Does not copy the data to the arena; the function gets a slice of the buffer.
+    fn read_slice_with<Ret, F>(&mut self, len: usize, function: F) -> Result<Ret, LqError>
+    where
+        F: FnOnce(&[u8]) -> Result<Ret, LqError>,
+    {
+        self.ensure(len)?;
+        let result = {
+            let shared = self.shared.borrow();
+            let index = self.buffer_index(&shared);
+            function(&shared.buffer[index..index + len])?
+        };
+        self.offset += u64::try_from(len)?;
+        Result::Ok(result)
+    }
+
+    /// Skips in chunks (data is not copied to the arena).
+    fn skip_bytes_u64(&mut self, number_of_bytes: u64) -> Result<(), LqError> {
+        let max_chunk = READ_CHUNK_SIZE
+            .min(self.shared.borrow().max_buffer_size)
+            .max(1);
+        let mut remaining = number_of_bytes;
+        while remaining > 0 {
+            let chunk = usize::try_from(remaining.min(max_chunk as u64))?;
+            self.ensure(chunk)?;
+            self.offset += chunk as u64;
+            remaining -= chunk as u64;
+        }
+        Result::Ok(())
+    }
+
+    /// Creates a checkpoint: The clone can re-read the data from the current read offset
+    /// on. Data is retained as long as the clone lives.
+    fn clone(&self) -> Self {
+        let mut shared = self.shared.borrow_mut();
+        let free_slot = shared.offsets.iter().position(Option::is_none);
+        let slot = if let Some(free_slot) = free_slot {
+            shared.offsets[free_slot] = Some(self.offset);
+            free_slot
+        } else {
+            shared.offsets.push(Some(self.offset));
+            shared.offsets.len() - 1
+        };
+        Self {
+            shared: self.shared.clone(),
+            arena: self.arena,
+            slot,
+            offset: self.offset,
+        }
+    }
+}
+
+impl<'a, R: Read> Drop for IoReader<'a, R> {
+    fn drop(&mut self) {
+        if let Ok(mut shared) = self.shared.try_borrow_mut() {
+            shared.offsets[self.slot] = None;
+        }
+    }
+}
+
+/// Reads what's buffered (at most `buf.len()` bytes); returns `0` at the end of the stream.
+/// Note: Does not copy the data to the arena.
+impl<'a, R: Read> Read for IoReader<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Result::Ok(0);
+        }
+        let mut shared = self.shared.borrow_mut();
+        if !shared
+            .fill(self.slot, self.offset, 1)
+            .map_err(std::io::Error::other)?
+        {
+            return Result::Ok(0);
+        }
+        let index = self.buffer_index(&shared);
+        let len = buf.len().min(shared.buffer.len() - index);
+        buf[..len].copy_from_slice(&shared.buffer[index..index + len]);
+        self.offset += len as u64;
+        Result::Ok(len)
+    }
+}
@@ -0,0 +1,54 @@
+use crate::core::LqWriter;
+use core::convert::TryFrom;
+use liquesco_common::error::LqError;
+
+/// A `LqWriter` that does not write anything but counts the bytes. Use this to compute the
+/// exact serialized length of a value (e.g. to preallocate buffers or to enforce size limits).
+#[derive(Default)]
+pub struct LenWriter {
+    len: u64,
+}
+
+impl LenWriter {
+    /// The number of bytes written so far.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds bytes that have been counted elsewhere (e.g. by a nested `LenWriter`).
+    pub(crate) fn add(&mut self, len: u64) {
+        self.len += len;
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for LenWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.len += buf.len() as u64;
+        Result::Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Result::Ok(())
+    }
+}
+
+impl LqWriter for LenWriter {
+    fn write_u8(&mut self, _: u8) -> Result<(), LqError> {
+        self.len += 1;
+        Result::Ok(())
+    }
+
+    fn write_slice(&mut self, buf: &[u8]) -> Result<(), LqError> {
+        self.len += u64::try_from(buf.len())?;
+        Result::Ok(())
+    }
+
+    fn as_len_writer(&mut self) -> Option<&mut LenWriter> {
+        Some(self)
+    }
+}
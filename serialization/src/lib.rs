@@ -1,11 +1,41 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[macro_use]
+extern crate liquesco_common;
+
+#[cfg(feature = "alloc")]
 pub mod serde;
+#[cfg(feature = "alloc")]
 pub mod value;
 
+pub mod canonical;
 pub mod core;
+#[cfg(feature = "alloc")]
+pub mod diagnostic;
+#[cfg(feature = "std")]
+pub mod io_reader;
+pub mod len_writer;
+pub mod limits;
 pub mod slice_reader;
+pub mod slice_writer;
+mod varint;
+#[cfg(feature = "alloc")]
 pub mod vec_writer;
+#[cfg(feature = "std")]
 pub mod write_writer;
 
-pub(crate) mod major_types;
+pub mod major_types;
 
 pub mod types;
+
+#[cfg(feature = "derive")]
+pub use liquesco_derive::{LqDeserialize, LqSerialize};
+
+/// Not public API: Used by the code generated by the derive macros (`liquesco-derive`).
+#[doc(hidden)]
+pub mod __private {
+    pub use liquesco_common::error::LqError;
+    pub use liquesco_common::lq_msg;
+}
@@ -0,0 +1,239 @@
+use crate::core::LqReader;
+use liquesco_common::error::{Category, ErrMsg, LqError};
+
+/// Errors resulting from exceeded read limits.
+const CATEGORY: Category = Category::new("liquesco_limits");
+
+/// Returns the error category of errors resulting from exceeded read limits (see
+/// `ReadLimits`).
+pub const fn limits_error_category() -> Category {
+    CATEGORY
+}
+
+/// Limits for reading (de-serializing) untrusted data. Without limits a few bytes of
+/// malicious input can make the de-serializer allocate lots of memory (a sequence declaring
+/// billions of elements) or overflow the stack (deeply nested sequences).
+///
+/// Limits are honoured by every reader wrapped in a `LimitedReader`: by `Value`, the native
+/// types, the serde de-serializer and `LqReader::skip` (skipping is iterative and does not
+/// allocate, so only the number of items and the length of single values are checked).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReadLimits {
+    /// Maximum nesting depth (sequences, enums with values and present options).
+    pub max_depth: u32,
+    /// Maximum number of embedded items a single value can declare (e.g. the number of
+    /// elements of a sequence).
+    pub max_items: u32,
+    /// Maximum number of bytes that can be allocated in total while reading. This is an
+    /// estimate: Binaries and texts count with their length; `Value`s and the elements of
+    /// sequences and maps with their in-memory size (`size_of`; the serde de-serializer also
+    /// counts fields of structures, since it cannot tell whether the elements are collected).
+    /// Memory allocated by the embedded values themselves (e.g. a `Box`) is not counted.
+    pub max_allocation: u64,
+    /// Maximum length (in bytes) of a single binary or unicode text.
+    pub max_binary_len: u64,
+}
+
+impl ReadLimits {
+    /// No limits.
+    pub fn unlimited() -> Self {
+        Self {
+            max_depth: u32::MAX,
+            max_items: u32::MAX,
+            max_allocation: u64::MAX,
+            max_binary_len: u64::MAX,
+        }
+    }
+}
+
+impl Default for ReadLimits {
+    /// Defaults that should work for most use cases: Maximum depth of 128, 1M items per value,
+    /// allocation of 64 MiB and binaries / texts of 16 MiB.
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            max_items: 1024 * 1024,
+            max_allocation: 64 * 1024 * 1024,
+            max_binary_len: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// The limits and what has already been used (current depth; bytes allocated so far).
+#[derive(Clone, Debug)]
+pub struct ReadLimitState {
+    limits: ReadLimits,
+    depth: u32,
+    allocated: u64,
+}
+
+impl ReadLimitState {
+    pub fn new(limits: ReadLimits) -> Self {
+        Self {
+            limits,
+            depth: 0,
+            allocated: 0,
+        }
+    }
+
+    pub fn limits(&self) -> &ReadLimits {
+        &self.limits
+    }
+
+    /// Bytes allocated so far.
+    pub fn allocated(&self) -> u64 {
+        self.allocated
+    }
+
+    /// Enters an embedded value (increases the nesting depth).
+    pub fn enter(&mut self) -> Result<(), LqError> {
+        if self.depth >= self.limits.max_depth {
+            Err(limit_error(lq_msg!(
+                "Maximum nesting depth of {} exceeded.",
+                self.limits.max_depth
+            )))
+        } else {
+            self.depth += 1;
+            Ok(())
+        }
+    }
+
+    /// Leaves an embedded value (see `enter`).
+    pub fn exit(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    /// Checks the number of embedded items a value declares.
+    pub fn check_items(&self, number_of_items: u32) -> Result<(), LqError> {
+        if number_of_items > self.limits.max_items {
+            Err(limit_error(lq_msg!(
+                "Value declares {} embedded items; maximum is {}.",
+                number_of_items,
+                self.limits.max_items
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks the length of a binary or unicode text.
+    pub fn check_binary_len(&self, len: u64) -> Result<(), LqError> {
+        if len > self.limits.max_binary_len {
+            Err(limit_error(lq_msg!(
+                "Binary (or unicode text) has a length of {} bytes; maximum is {} bytes.",
+                len,
+                self.limits.max_binary_len
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Adds given number of bytes to the allocated bytes.
+    pub fn allocate(&mut self, number_of_bytes: u64) -> Result<(), LqError> {
+        let allocated = self.allocated.saturating_add(number_of_bytes);
+        if allocated > self.limits.max_allocation {
+            Err(limit_error(lq_msg!(
+                "Unable to allocate {} bytes: Maximum allocation of {} bytes exceeded ({} bytes \
+                 have already been allocated).",
+                number_of_bytes,
+                self.limits.max_allocation,
+                self.allocated
+            )))
+        } else {
+            self.allocated = allocated;
+            Ok(())
+        }
+    }
+}
+
+fn limit_error(msg: ErrMsg) -> LqError {
+    LqError::new(msg).with_category(CATEGORY)
+}
+
+/// Wraps a reader and enforces given `ReadLimits`.
+pub struct LimitedReader<R> {
+    reader: R,
+    state: ReadLimitState,
+}
+
+impl<R> LimitedReader<R> {
+    pub fn new(reader: R, limits: ReadLimits) -> Self {
+        Self {
+            reader,
+            state: ReadLimitState::new(limits),
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    pub fn state(&self) -> &ReadLimitState {
+        &self.state
+    }
+}
+
+impl<'a, R: LqReader<'a>> LqReader<'a> for LimitedReader<R> {
+    #[inline]
+    fn read_offset(&self) -> Option<u64> {
+        self.reader.read_offset()
+    }
+
+    #[inline]
+    fn peek_u8(&self) -> Result<u8, LqError> {
+        self.reader.peek_u8()
+    }
+
+    #[inline]
+    fn read_u8(&mut self) -> Result<u8, LqError> {
+        self.reader.read_u8()
+    }
+
+    #[inline]
+    fn read_slice(&mut self, len: usize) -> Result<&'a [u8], LqError> {
+        self.reader.read_slice(len)
+    }
+
+    #[inline]
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<(), LqError> {
+        self.reader.read_into(buf)
+    }
+
+    #[inline]
+    fn read_slice_with<Ret, F>(&mut self, len: usize, function: F) -> Result<Ret, LqError>
+    where
+        F: FnOnce(&[u8]) -> Result<Ret, LqError>,
+    {
+        self.reader.read_slice_with(len, function)
+    }
+
+    #[inline]
+    fn skip_bytes_u64(&mut self, number_of_bytes: u64) -> Result<(), LqError> {
+        self.reader.skip_bytes_u64(number_of_bytes)
+    }
+
+    fn clone(&self) -> Self {
+        Self {
+            reader: self.reader.clone(),
+            state: self.state.clone(),
+        }
+    }
+
+    #[inline]
+    fn canonical_only(&self) -> bool {
+        self.reader.canonical_only()
+    }
+
+    #[inline]
+    fn limit_state(&mut self) -> Option<&mut ReadLimitState> {
+        Some(&mut self.state)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> std::io::Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
@@ -1,8 +1,23 @@
 use crate::core::DeSerializer;
+use crate::major_types::TYPE_BINARY;
+use crate::major_types::TYPE_BOOL_FALSE;
+use crate::major_types::TYPE_BOOL_TRUE;
+use crate::major_types::TYPE_ENUM_0;
+use crate::major_types::TYPE_ENUM_1;
+use crate::major_types::TYPE_ENUM_2;
+use crate::major_types::TYPE_ENUM_3;
+use crate::major_types::TYPE_ENUM_N;
+use crate::major_types::TYPE_FLOAT;
+use crate::major_types::TYPE_OPTION;
+use crate::major_types::TYPE_SEQ;
+use crate::major_types::TYPE_SINT;
+use crate::major_types::TYPE_UINT;
+use crate::major_types::TYPE_UNICODE;
 use crate::serde::error::SLqError;
 use crate::types::binary::Binary;
 use crate::types::boolean::Bool;
 use crate::types::enumeration::EnumHeader;
+use crate::types::float::Float;
 use crate::types::float::Float32;
 use crate::types::float::Float64;
 use crate::types::option::Presence;
@@ -10,11 +25,12 @@ use crate::types::seq::SeqHeader;
 use crate::types::sint::{SInt128, SInt16, SInt32, SInt64, SInt8};
 use crate::types::uint::{UInt128, UInt16, UInt32, UInt64, UInt8};
 use crate::types::unicode::Unicode;
+use alloc::format;
+use core::convert::TryFrom;
+use core::marker::PhantomData;
 use liquesco_common::error::LqError;
 use serde::de::IntoDeserializer;
 use serde::de::Visitor;
-use std::convert::TryFrom;
-use std::marker::PhantomData;
 
 use crate::core::LqReader;
 
@@ -33,7 +49,7 @@ impl<'de, R: LqReader<'de>> Deserializer<'de, R> {
     }
 }
 
-type Result<Ok> = std::result::Result<Ok, SLqError>;
+type Result<Ok> = core::result::Result<Ok, SLqError>;
 
 impl<'de, 'a, R> serde::Deserializer<'de> for &'a mut Deserializer<'de, R>
 where
@@ -41,11 +57,71 @@ where
 {
     type Error = SLqError;
 
-    fn deserialize_any<V>(self, _: V) -> Result<V::Value>
-    where
-        V: Visitor<'de>,
-    {
-        Err(LqError::new("This is not a self-describing format. Operation not supported").into())
+    /// Every value carries its major type in the header byte, so values can be de-serialized
+    /// without knowing the type in advance:
+    ///
+    ///  - Absent options are visited as unit (serde treats unit as `None` when
+    ///    de-serializing options); present options as some.
+    ///  - Integers are visited as 64 bit integers (128 bit if they do not fit).
+    ///  - Floats are visited as 64 bit floats (this is lossless; 32 bit targets accept them).
+    ///  - Sequences are visited as sequences (note: maps are sequences of key-value
+    ///    sequences).
+    ///  - Enums are visited as a map with one single entry: The key is the ordinal, the
+    ///    value is unit (no values), the value itself (one value) or a sequence of values.
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let major_type = self.reader.peek_header_byte()?.major_type();
+        match major_type {
+            TYPE_BOOL_FALSE | TYPE_BOOL_TRUE => self.deserialize_bool(visitor),
+            TYPE_OPTION => match Presence::de_serialize(&mut self.reader)? {
+                Presence::Absent => visitor.visit_unit(),
+                Presence::Present => self.nested(|deserializer| visitor.visit_some(deserializer)),
+            },
+            TYPE_SEQ => self.deserialize_seq(visitor),
+            TYPE_BINARY => {
+                let value = Binary::de_serialize(&mut self.reader)?;
+                visitor.visit_borrowed_bytes(value)
+            }
+            TYPE_UNICODE => self.deserialize_str(visitor),
+            TYPE_UINT => {
+                let value = UInt128::de_serialize(&mut self.reader)?;
+                if let Ok(value) = u64::try_from(value) {
+                    visitor.visit_u64(value)
+                } else {
+                    visitor.visit_u128(value)
+                }
+            }
+            TYPE_SINT => {
+                let value = SInt128::de_serialize(&mut self.reader)?;
+                if let Ok(value) = i64::try_from(value) {
+                    visitor.visit_i64(value)
+                } else {
+                    visitor.visit_i128(value)
+                }
+            }
+            TYPE_FLOAT => match Float::de_serialize(&mut self.reader)? {
+                Float::F32(value) => visitor.visit_f64(f64::from(value)),
+                Float::F64(value) => visitor.visit_f64(value),
+            },
+            TYPE_ENUM_0 | TYPE_ENUM_1 | TYPE_ENUM_2 | TYPE_ENUM_3 | TYPE_ENUM_N => {
+                let enum_header = EnumHeader::de_serialize(&mut self.reader)?;
+                let number_of_values = usize::try_from(enum_header.number_of_values())?;
+                self.nested(|deserializer| {
+                    visitor.visit_map(EnumMapAccessStruct {
+                        deserializer,
+                        ordinal: Option::Some(enum_header.ordinal()),
+                        number_of_values,
+                    })
+                })
+            }
+            _ => Err(LqError::new(format!(
+                "Unable to de-serialize value with unknown major type {:?}.",
+                major_type
+            ))
+            .into()),
+        }
     }
 
     fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
@@ -158,7 +234,7 @@ where
         V: Visitor<'de>,
     {
         let value = UInt32::de_serialize(&mut self.reader)?;
-        let maybe_char = std::char::from_u32(value);
+        let maybe_char = core::char::from_u32(value);
         if let Some(chr) = maybe_char {
             visitor.visit_char(chr)
         } else {
@@ -183,6 +259,7 @@ where
         V: Visitor<'de>,
     {
         let value = Unicode::de_serialize(&mut self.reader)?;
+        self.reader.limit_allocate(u64::try_from(value.len())?)?;
         visitor.visit_string(value.into())
     }
 
@@ -199,6 +276,7 @@ where
         V: Visitor<'de>,
     {
         let value = Binary::de_serialize(&mut self.reader)?;
+        self.reader.limit_allocate(u64::try_from(value.len())?)?;
         visitor.visit_byte_buf(value.into())
     }
 
@@ -209,7 +287,7 @@ where
         let value = Presence::de_serialize(&mut self.reader)?;
         match value {
             Presence::Absent => visitor.visit_none(),
-            Presence::Present => visitor.visit_some(self),
+            Presence::Present => self.nested(|deserializer| visitor.visit_some(deserializer)),
         }
     }
 
@@ -277,9 +355,11 @@ where
         let list_header = SeqHeader::de_serialize(&mut self.reader)?;
         let usize_list_header = usize::try_from(list_header.length())?;
 
-        visitor.visit_map(MapAccessStruct {
-            deserializer: self,
-            items_left: usize_list_header,
+        self.nested(|deserializer| {
+            visitor.visit_map(MapAccessStruct {
+                deserializer,
+                items_left: usize_list_header,
+            })
         })
     }
 
@@ -310,11 +390,13 @@ where
         })
     }
 
-    fn deserialize_identifier<V>(self, _: V) -> Result<V::Value>
+    /// There are no dedicated identifiers in this format; identifiers are whatever has been
+    /// serialized (usually ordinals or text).
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        Err(LqError::new("This format has no identifiers. Operation not supported").into())
+        self.deserialize_any(visitor)
     }
 
     fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
@@ -348,7 +430,7 @@ where
         let number_of_values = enum_header.number_of_values();
         self.input_data_len = usize::try_from(number_of_values)?;
 
-        let val: std::result::Result<_, Self::Error> =
+        let val: core::result::Result<_, Self::Error> =
             seed.deserialize(ordinal.into_deserializer());
         Ok((val?, self))
     }
@@ -367,7 +449,9 @@ impl<'de, 'a, 'b: 'a, R: LqReader<'de> + 'b> serde::de::VariantAccess<'de>
     where
         T: serde::de::DeserializeSeed<'de>,
     {
-        let value = serde::de::DeserializeSeed::deserialize(seed, &mut *self.deserializer)?;
+        let value = self
+            .deserializer
+            .nested(|deserializer| serde::de::DeserializeSeed::deserialize(seed, deserializer))?;
         let to_skip = self.input_data_len - 1;
         if to_skip > 0 {
             self.deserializer.reader.skip_n_values(to_skip)?;
@@ -433,12 +517,26 @@ where
 
         let to_skip = real_len - to_read;
 
-        visitor.visit_seq(SeqAccessStruct {
-            deserializer: self,
-            remaining_len: to_read,
-            to_skip,
+        self.nested(|deserializer| {
+            visitor.visit_seq(SeqAccessStruct {
+                deserializer,
+                remaining_len: to_read,
+                to_skip,
+            })
         })
     }
+
+    /// Reads embedded values (increases the nesting depth, see `LqReader::limit_enter`).
+    #[inline]
+    fn nested<Ret, F>(&mut self, function: F) -> Result<Ret>
+    where
+        F: FnOnce(&mut Self) -> Result<Ret>,
+    {
+        self.reader.limit_enter()?;
+        let result = function(self)?;
+        self.reader.limit_exit();
+        Ok(result)
+    }
 }
 
 struct SeqAccessStruct<'a, 'de, R: LqReader<'de> + 'a> {
@@ -458,6 +556,10 @@ impl<'de, 'a, 'b: 'a, R: LqReader<'de> + 'b> serde::de::SeqAccess<'de>
     {
         if self.remaining_len > 0 {
             self.remaining_len -= 1;
+            // the visitor might collect the elements (e.g. in a `Vec`)
+            self.deserializer
+                .reader
+                .limit_allocate(core::mem::size_of::<T::Value>() as u64)?;
             let value = serde::de::DeserializeSeed::deserialize(seed, &mut *self.deserializer)?;
 
             // we skip here, since there's no guarantee we're called again by serde
@@ -493,17 +595,22 @@ impl<'de, 'a, 'b: 'a, R: LqReader<'de> + 'b> serde::de::MapAccess<'de>
             // Now a pair of key+value starts... so the list _always_ has to have a length of 2
             let list_header = SeqHeader::de_serialize(&mut self.deserializer.reader)?;
             if list_header.length() != 2 {
-                return Err(LqError::new(
-                    format!("You're trying to deserialize a map. A map has to \
+                return Err(LqError::new(format!(
+                    "You're trying to deserialize a map. A map has to \
                 be a list of entries; every entry has to be a list of 2 items (key and value). So \
                 a map looks like this: [[key1; value1]; [key2; value2]; [key3; value3]; ...]. The \
                 input list I got does not have 2 items (key and value) for an entry, it has \
-                {:?} items.", list_header.length()),
-                )
+                {:?} items.",
+                    list_header.length()
+                ))
                 .into());
             }
 
             self.items_left -= 1;
+            // the visitor might collect the entries (e.g. in a `BTreeMap`)
+            self.deserializer
+                .reader
+                .limit_allocate(core::mem::size_of::<K::Value>() as u64)?;
             Ok(Some(seed.deserialize(&mut *self.deserializer)?))
         } else {
             Ok(None)
@@ -514,6 +621,9 @@ impl<'de, 'a, 'b: 'a, R: LqReader<'de> + 'b> serde::de::MapAccess<'de>
     where
         V: serde::de::DeserializeSeed<'de>,
     {
+        self.deserializer
+            .reader
+            .limit_allocate(core::mem::size_of::<V::Value>() as u64)?;
         Ok(seed.deserialize(&mut *self.deserializer)?)
     }
 
@@ -521,3 +631,78 @@ impl<'de, 'a, 'b: 'a, R: LqReader<'de> + 'b> serde::de::MapAccess<'de>
         Some(self.items_left)
     }
 }
+
+/// Presents an enum as a map with one single entry (see `deserialize_any`).
+struct EnumMapAccessStruct<'a, 'de, R: LqReader<'de> + 'a> {
+    deserializer: &'a mut Deserializer<'de, R>,
+    ordinal: Option<u32>,
+    number_of_values: usize,
+}
+
+impl<'de, 'a, R: LqReader<'de> + 'a> serde::de::MapAccess<'de> for EnumMapAccessStruct<'a, 'de, R> {
+    type Error = SLqError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: serde::de::DeserializeSeed<'de>,
+    {
+        if let Some(ordinal) = self.ordinal.take() {
+            let key: core::result::Result<_, Self::Error> =
+                seed.deserialize(ordinal.into_deserializer());
+            Ok(Some(key?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: serde::de::DeserializeSeed<'de>,
+    {
+        if self.number_of_values == 1 {
+            seed.deserialize(&mut *self.deserializer)
+        } else {
+            seed.deserialize(EnumValuesDeserializer {
+                deserializer: &mut *self.deserializer,
+                number_of_values: self.number_of_values,
+            })
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(if self.ordinal.is_some() { 1 } else { 0 })
+    }
+}
+
+/// The values of an enum variant with none or more than one value: Unit or a sequence.
+struct EnumValuesDeserializer<'a, 'de, R: LqReader<'de> + 'a> {
+    deserializer: &'a mut Deserializer<'de, R>,
+    number_of_values: usize,
+}
+
+impl<'de, 'a, R: LqReader<'de> + 'a> serde::Deserializer<'de>
+    for EnumValuesDeserializer<'a, 'de, R>
+{
+    type Error = SLqError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.number_of_values == 0 {
+            visitor.visit_unit()
+        } else {
+            self.deserializer.deserialize_seq_no_header(
+                Option::None,
+                self.number_of_values,
+                visitor,
+            )
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
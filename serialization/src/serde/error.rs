@@ -1,9 +1,10 @@
+use alloc::string::ToString;
+use core::fmt::Display;
+use core::num::TryFromIntError;
 use serde::de;
 use serde::ser;
-use std::fmt::Display;
-use std::num::TryFromIntError;
 
-use liquesco_common::error::{Category, LqError};
+use liquesco_common::error::{Category, LqError, DEFAULT_CATEGORY};
 
 /// Errors resulting from serde problems.
 pub(crate) const CATEGORY: Category = Category::new("liquesco_serde");
@@ -12,7 +13,7 @@ pub(crate) const CATEGORY: Category = Category::new("liquesco_serde");
 pub(crate) struct SLqError(LqError);
 
 impl Display for SLqError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
@@ -29,7 +30,7 @@ impl de::Error for SLqError {
     }
 }
 
-impl std::error::Error for SLqError {}
+impl serde::ser::StdError for SLqError {}
 
 impl From<TryFromIntError> for SLqError {
     fn from(value: TryFromIntError) -> Self {
@@ -44,8 +45,14 @@ impl From<SLqError> for LqError {
     }
 }
 
+/// Errors with the default category get the serde category; more specific categories (like
+/// the category for exceeded read limits) are kept.
 impl From<LqError> for SLqError {
     fn from(value: LqError) -> Self {
-        Self(value.with_category(CATEGORY))
+        if *value.category() == DEFAULT_CATEGORY {
+            Self(value.with_category(CATEGORY))
+        } else {
+            Self(value)
+        }
     }
 }
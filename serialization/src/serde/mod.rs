@@ -1,11 +1,17 @@
 use crate::core::LqReader;
 use crate::core::LqWriter;
 use crate::core::ToVecLqWriter;
+use crate::len_writer::LenWriter;
+use crate::limits::LimitedReader;
+use crate::limits::ReadLimits;
 use crate::serde::deserializer::Deserializer;
 use crate::serde::error::CATEGORY;
 use crate::serde::serializer::Serializer;
+use crate::serde::serializer::SET_MARKER;
 use crate::slice_reader::SliceReader;
+use crate::slice_writer::SliceWriter;
 use crate::vec_writer::VecWriter;
+use alloc::vec::Vec;
 use liquesco_common::error::{Category, LqError};
 use serde::ser;
 
@@ -34,6 +40,62 @@ pub fn serialize_to_vec<S: ser::Serialize>(value: S) -> Result<Vec<u8>, LqError>
     Ok(vec_writer.into_vec())
 }
 
+/// Serializes given item into given slice (does not allocate, unless the item contains
+/// sequences or maps with unknown length). Returns the number of bytes written; fails if the
+/// slice is too small (the error message contains the required size).
+#[inline]
+pub fn serialize_to_slice<S: ser::Serialize>(
+    slice: &mut [u8],
+    value: S,
+) -> Result<usize, LqError> {
+    let mut slice_writer = SliceWriter::from(slice);
+    serialize(&mut slice_writer, value)?;
+    slice_writer.finish()
+}
+
+/// The number of bytes given item occupies when serialized. Note: This is also the length of
+/// the canonical form. The data is not written anywhere (see `LenWriter`).
+#[inline]
+pub fn serialized_len<S: ser::Serialize>(value: S) -> Result<u64, LqError> {
+    let mut len_writer = LenWriter::default();
+    serialize(&mut len_writer, value)?;
+    Ok(len_writer.len())
+}
+
+/// Serializes given item in canonical form: Map entries are sorted by key (and sequences
+/// marked as set, see `serialize_set`, are sorted); so the result is deterministic (even for
+/// `HashMap` or `HashSet`) and satisfies the ordering required by the schema (`TMap`, sorted
+/// and unique `TSeq`). The order is defined by `canonical::compare`. Fails if a map contains
+/// duplicate keys or a set contains duplicate elements (according to that order).
+#[inline]
+pub fn serialize_canonical<W: LqWriter, S: ser::Serialize>(
+    writer: &mut W,
+    value: S,
+) -> Result<(), LqError> {
+    let mut serializer = Serializer::new_canonical(writer);
+    value.serialize(&mut serializer).map_err(|err| err.into())
+}
+
+/// Serializes given item in canonical form (see `serialize_canonical`) and returns the result
+/// as `Vec<u8>`.
+#[inline]
+pub fn serialize_to_vec_canonical<S: ser::Serialize>(value: S) -> Result<Vec<u8>, LqError> {
+    let mut vec_writer = VecWriter::default();
+    serialize_canonical(&mut vec_writer, value)?;
+    Ok(vec_writer.into_vec())
+}
+
+/// Marks a sequence as set: When serialized using `serialize_canonical` the elements are
+/// sorted (the non-canonical serializer just writes the sequence). Use it like this:
+/// `#[serde(serialize_with = "liquesco_serialization::serde::serialize_set")]`.
+pub fn serialize_set<T, S>(set: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: ?Sized + ser::Serialize,
+    S: ser::Serializer,
+{
+    serializer.serialize_newtype_struct(SET_MARKER, set)
+}
+
 /// De-serializes `T` using given reader. When an error occurs the state of the reader is
 /// undefined and should not longer be used.
 pub fn de_serialize<'de, T: serde::Deserialize<'de>, R: LqReader<'de>>(
@@ -50,3 +112,13 @@ pub fn de_serialize_from_slice<'de, T: serde::Deserialize<'de>>(
     let reader: SliceReader = slice.into();
     de_serialize(reader)
 }
+
+/// De-serializes `T` from given slice; fails if given limits are exceeded (use this for
+/// untrusted data).
+pub fn de_serialize_from_slice_with_limits<'de, T: serde::Deserialize<'de>>(
+    slice: &'de [u8],
+    limits: ReadLimits,
+) -> Result<T, LqError> {
+    let reader: SliceReader = slice.into();
+    de_serialize(LimitedReader::new(reader, limits))
+}
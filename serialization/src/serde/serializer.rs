@@ -1,5 +1,8 @@
+use crate::canonical::compare;
 use crate::core::LqWriter;
 use crate::core::Serializer as S;
+use crate::core::ToVecLqWriter;
+use crate::len_writer::LenWriter;
 use crate::serde::error::SLqError;
 use crate::types::binary::Binary;
 use crate::types::boolean::Bool;
@@ -11,32 +14,56 @@ use crate::types::seq::SeqHeader;
 use crate::types::sint::{SInt128, SInt16, SInt32, SInt64, SInt8};
 use crate::types::uint::{UInt128, UInt16, UInt32, UInt64, UInt8};
 use crate::types::unicode::Unicode;
+use crate::vec_writer::VecWriter;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
 use liquesco_common::error::LqError;
-use std::convert::TryFrom;
 
 use serde::ser;
 
+/// Name of the newtype struct that marks set-like sequences (see `serde::serialize_set`).
+pub(crate) const SET_MARKER: &str = "$liquesco::set";
+
 pub(crate) struct Serializer<'a, W: LqWriter> {
     writer: &'a mut W,
+    /// Canonical mode: map entries (and sequences marked as set) are sorted.
+    canonical: bool,
+    /// True if the next sequence is a set (only in canonical mode).
+    set: bool,
 }
 
 impl<'a, W: LqWriter> Serializer<'a, W> {
     pub(crate) fn new(writer: &'a mut W) -> Self {
-        Self { writer }
+        Self {
+            writer,
+            canonical: false,
+            set: false,
+        }
+    }
+
+    /// Creates a serializer that produces canonical output: Map entries are sorted by key
+    /// and sequences marked as set are sorted (see `canonical::compare`).
+    pub(crate) fn new_canonical(writer: &'a mut W) -> Self {
+        Self {
+            writer,
+            canonical: true,
+            set: false,
+        }
     }
 }
 
-type Result<Ok> = std::result::Result<Ok, SLqError>;
+type Result<Ok> = core::result::Result<Ok, SLqError>;
 
 impl<'a, W: LqWriter> ser::Serializer for &'a mut Serializer<'a, W> {
     type Ok = ();
     type Error = SLqError;
 
-    type SerializeSeq = Self;
+    type SerializeSeq = SeqSerializer<'a, W>;
     type SerializeTuple = Self;
     type SerializeTupleStruct = Self;
     type SerializeTupleVariant = Self;
-    type SerializeMap = Self;
+    type SerializeMap = SeqSerializer<'a, W>;
     type SerializeStruct = Self;
     type SerializeStructVariant = Self;
 
@@ -138,11 +165,20 @@ impl<'a, W: LqWriter> ser::Serializer for &'a mut Serializer<'a, W> {
     }
 
     // The same as the contained type
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
     where
         T: ?Sized + ser::Serialize,
     {
-        value.serialize(self)
+        if name == SET_MARKER && self.canonical {
+            let mut set_serializer = Serializer {
+                writer: self.writer,
+                canonical: true,
+                set: true,
+            };
+            value.serialize(&mut set_serializer)
+        } else {
+            value.serialize(self)
+        }
     }
 
     fn serialize_newtype_variant<T>(
@@ -161,13 +197,8 @@ impl<'a, W: LqWriter> ser::Serializer for &'a mut Serializer<'a, W> {
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
-        let present_len: usize = len.ok_or(LqError::new(
-            "Only supports sequences with computed length.",
-        ))?;
-        let u32_len = u32::try_from(present_len)?;
-        let list_header = SeqHeader::new(u32_len);
-        SeqHeader::serialize(self.writer, &list_header)?;
-        Ok(self)
+        let sorted = self.set;
+        SeqSerializer::new(self, len, sorted)
     }
 
     fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
@@ -201,16 +232,10 @@ impl<'a, W: LqWriter> ser::Serializer for &'a mut Serializer<'a, W> {
         Ok(self)
     }
 
+    /// A map is a sequence of key-value sequences: [[key1, value1][key2, value2]]
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
-        let present_len: usize = len.ok_or_else(|| {
-            LqError::new(
-                "Only supports maps with computed length. This can happen with structs #flatten.",
-            )
-        })?;
-        let u32_len = u32::try_from(present_len)?;
-        let list_header = SeqHeader::new(u32_len);
-        SeqHeader::serialize(self.writer, &list_header)?;
-        Ok(self)
+        let sorted = self.canonical;
+        SeqSerializer::new(self, len, sorted)
     }
 
     fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
@@ -234,7 +259,159 @@ impl<'a, W: LqWriter> ser::Serializer for &'a mut Serializer<'a, W> {
     }
 }
 
-impl<'a, W: LqWriter> ser::SerializeSeq for &'a mut Serializer<'a, W> {
+/// Serializes sequences and maps. If the length is known in advance, the header is written
+/// immediately and the elements are written directly to the writer. If the length is
+/// unknown, the elements are serialized to a buffer first; the header is written when the
+/// length is known (`end`). Both produce the same data. Sorted sequences (sets and maps in
+/// canonical mode) buffer each item separately; items are sorted in `end`. When only counting
+/// bytes (`LenWriter`), nothing is buffered: The items of sequences with unknown length are
+/// counted separately and added after the header.
+pub(crate) struct SeqSerializer<'a, W: LqWriter> {
+    serializer: &'a mut Serializer<'a, W>,
+    buffer: Buffer,
+    number_of_items: u32,
+}
+
+enum Buffer {
+    /// Length is known in advance; items are written directly.
+    None,
+    /// Length is not known in advance.
+    Unsorted(VecWriter),
+    /// Length is not known in advance; the writer only counts bytes.
+    Counted(LenWriter),
+    /// Items have to be sorted.
+    Sorted(Vec<SortItem>),
+}
+
+/// A set element (`value` is `None`) or a map entry.
+struct SortItem {
+    key: Vec<u8>,
+    value: Option<Vec<u8>>,
+}
+
+impl<'a, W: LqWriter> SeqSerializer<'a, W> {
+    fn new(
+        serializer: &'a mut Serializer<'a, W>,
+        len: Option<usize>,
+        sorted: bool,
+    ) -> Result<Self> {
+        let buffer = if sorted {
+            Buffer::Sorted(Vec::with_capacity(len.unwrap_or(0)))
+        } else if let Some(len) = len {
+            let u32_len = u32::try_from(len)?;
+            let list_header = SeqHeader::new(u32_len);
+            SeqHeader::serialize(serializer.writer, &list_header)?;
+            Buffer::None
+        } else if serializer.writer.as_len_writer().is_some() {
+            Buffer::Counted(LenWriter::default())
+        } else {
+            Buffer::Unsorted(VecWriter::default())
+        };
+        Ok(Self {
+            serializer,
+            buffer,
+            number_of_items: 0,
+        })
+    }
+
+    fn serialize_item<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        match &mut self.buffer {
+            Buffer::None => self.serializer.serialize_inner(value),
+            Buffer::Unsorted(buffer) => value.serialize(&mut Serializer {
+                writer: buffer,
+                canonical: self.serializer.canonical,
+                set: false,
+            }),
+            Buffer::Counted(len_writer) => value.serialize(&mut Serializer {
+                writer: len_writer,
+                canonical: self.serializer.canonical,
+                set: false,
+            }),
+            Buffer::Sorted(_) => {
+                Err(LqError::new("Sorted items cannot be written directly.").into())
+            }
+        }
+    }
+
+    fn serialize_to_vec<T>(&self, value: &T) -> Result<Vec<u8>>
+    where
+        T: ?Sized + ser::Serialize,
+    {
+        let mut writer = VecWriter::default();
+        value.serialize(&mut Serializer {
+            writer: &mut writer,
+            canonical: self.serializer.canonical,
+            set: false,
+        })?;
+        Ok(writer.into_vec())
+    }
+
+    fn add_item(&mut self) -> Result<()> {
+        self.number_of_items = self.number_of_items.checked_add(1).ok_or_else(|| {
+            LqError::new("Sequence is too long (maximum number of items is 2^32-1).")
+        })?;
+        Ok(())
+    }
+
+    fn end_seq(self) -> Result<()> {
+        match self.buffer {
+            Buffer::None => Ok(()),
+            Buffer::Unsorted(buffer) => {
+                let list_header = SeqHeader::new(self.number_of_items);
+                SeqHeader::serialize(self.serializer.writer, &list_header)?;
+                Ok(self.serializer.writer.write_slice(&buffer.into_vec())?)
+            }
+            Buffer::Counted(len_writer) => {
+                let list_header = SeqHeader::new(self.number_of_items);
+                SeqHeader::serialize(self.serializer.writer, &list_header)?;
+                if let Some(writer) = self.serializer.writer.as_len_writer() {
+                    writer.add(len_writer.len());
+                }
+                Ok(())
+            }
+            Buffer::Sorted(mut items) => {
+                let mut error: Option<LqError> = None;
+                items.sort_by(|item1, item2| {
+                    compare(&item1.key, &item2.key).unwrap_or_else(|err| {
+                        error.get_or_insert(err);
+                        Ordering::Equal
+                    })
+                });
+                if let Some(error) = error {
+                    return Err(error.into());
+                }
+                for window in items.windows(2) {
+                    if compare(&window[0].key, &window[1].key)? == Ordering::Equal {
+                        return Err(LqError::new(
+                            "Unable to serialize canonical map or set: Got duplicate keys \
+                             (map) or elements (set); keys and set elements must be unique.",
+                        )
+                        .into());
+                    }
+                }
+
+                let list_header = SeqHeader::new(self.number_of_items);
+                SeqHeader::serialize(self.serializer.writer, &list_header)?;
+                for item in items {
+                    if let Some(value) = item.value {
+                        // key-value is wrapped inside a list (length = 2).
+                        SeqHeader::serialize(self.serializer.writer, &SeqHeader::new(2))?;
+                        self.serializer.writer.write_slice(&item.key)?;
+                        self.serializer.writer.write_slice(&value)?;
+                    } else {
+                        self.serializer.writer.write_slice(&item.key)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a, W: LqWriter> ser::SerializeSeq for SeqSerializer<'a, W> {
     type Ok = ();
     type Error = SLqError;
 
@@ -242,12 +419,20 @@ impl<'a, W: LqWriter> ser::SerializeSeq for &'a mut Serializer<'a, W> {
     where
         T: ?Sized + ser::Serialize,
     {
-        self.serialize_inner(value)
+        self.add_item()?;
+        if let Buffer::Sorted(_) = self.buffer {
+            let key = self.serialize_to_vec(value)?;
+            if let Buffer::Sorted(items) = &mut self.buffer {
+                items.push(SortItem { key, value: None });
+            }
+            Ok(())
+        } else {
+            self.serialize_item(value)
+        }
     }
 
     fn end(self) -> Result<()> {
-        // nothing to do here
-        Ok(())
+        self.end_seq()
     }
 }
 
@@ -302,7 +487,7 @@ impl<'a, W: LqWriter> ser::SerializeTupleVariant for &'a mut Serializer<'a, W> {
     }
 }
 
-impl<'a, W: LqWriter> ser::SerializeMap for &'a mut Serializer<'a, W> {
+impl<'a, W: LqWriter> ser::SerializeMap for SeqSerializer<'a, W> {
     type Ok = ();
     type Error = SLqError;
 
@@ -310,22 +495,43 @@ impl<'a, W: LqWriter> ser::SerializeMap for &'a mut Serializer<'a, W> {
     where
         T: ?Sized + ser::Serialize,
     {
+        self.add_item()?;
         // key-value is wrapped inside a list (length = 2).
         let list_header = SeqHeader::new(2);
-        SeqHeader::serialize(self.writer, &list_header)?;
-        self.serialize_inner(key)
+        match &mut self.buffer {
+            Buffer::None => SeqHeader::serialize(self.serializer.writer, &list_header)?,
+            Buffer::Unsorted(buffer) => SeqHeader::serialize(buffer, &list_header)?,
+            Buffer::Counted(len_writer) => SeqHeader::serialize(len_writer, &list_header)?,
+            Buffer::Sorted(_) => {
+                let key = self.serialize_to_vec(key)?;
+                if let Buffer::Sorted(items) = &mut self.buffer {
+                    items.push(SortItem { key, value: None });
+                }
+                return Ok(());
+            }
+        }
+        self.serialize_item(key)
     }
 
     fn serialize_value<T>(&mut self, value: &T) -> Result<()>
     where
         T: ?Sized + ser::Serialize,
     {
-        self.serialize_inner(value)
+        if let Buffer::Sorted(_) = self.buffer {
+            let value = self.serialize_to_vec(value)?;
+            if let Buffer::Sorted(items) = &mut self.buffer {
+                if let Some(item) = items.last_mut() {
+                    item.value = Some(value);
+                }
+            }
+            Ok(())
+        } else {
+            self.serialize_item(value)
+        }
     }
 
     fn end(self) -> Result<()> {
-        // nothing to do
-        Ok(())
+        self.end_seq()
     }
 }
 
@@ -371,6 +577,8 @@ impl<'a, W: LqWriter> Serializer<'a, W> {
     {
         let mut new_self = Serializer {
             writer: self.writer,
+            canonical: self.canonical,
+            set: false,
         };
         value.serialize(&mut new_self)
     }
@@ -1,7 +1,8 @@
 use crate::core::LqReader;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
 use liquesco_common::error::LqError;
-use std::io::Read;
-use std::io::Write;
 
 /// Can be used to get a `LqReader` from a slice of `u8`. It contains the slice and an
 /// offset (cursor). It's cheap to clone, since embedded data won't be cloned, only the
@@ -17,6 +18,7 @@ impl<'a> From<&'a [u8]> for SliceReader<'a> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a> From<&'a Vec<u8>> for SliceReader<'a> {
     fn from(data: &'a Vec<u8>) -> Self {
         SliceReader {
@@ -27,6 +29,11 @@ impl<'a> From<&'a Vec<u8>> for SliceReader<'a> {
 }
 
 impl<'a> SliceReader<'a> {
+    /// The number of bytes read so far (read offset).
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
     /// Makes sure the reader has been read completely and there's no additional data.
     pub fn finish(&self) -> Result<(), LqError> {
         if self.offset != self.data.len() {
@@ -41,6 +48,11 @@ impl<'a> SliceReader<'a> {
 }
 
 impl<'a> LqReader<'a> for SliceReader<'a> {
+    #[inline]
+    fn read_offset(&self) -> Option<u64> {
+        u64::try_from(self.offset).ok()
+    }
+
     #[inline]
     fn peek_u8(&self) -> Result<u8, LqError> {
         let len = self.data.len();
@@ -78,6 +90,18 @@ impl<'a> LqReader<'a> for SliceReader<'a> {
         }
     }
 
+    /// O(1): Just advances the offset.
+    fn skip_bytes_u64(&mut self, number_of_bytes: u64) -> Result<(), LqError> {
+        let remaining = self.data.len() - self.offset;
+        match usize::try_from(number_of_bytes) {
+            Ok(number_of_bytes) if number_of_bytes <= remaining => {
+                self.offset += number_of_bytes;
+                Result::Ok(())
+            }
+            _ => LqError::err_new("End of reader"),
+        }
+    }
+
     /// Note: This is a cheap operation since the binary data won't be cloned, only the offset.
     fn clone(&self) -> Self {
         Self {
@@ -87,8 +111,10 @@ impl<'a> LqReader<'a> for SliceReader<'a> {
     }
 }
 
-impl<'a> Read for SliceReader<'a> {
+#[cfg(feature = "std")]
+impl<'a> std::io::Read for SliceReader<'a> {
     fn read(&mut self, mut buf: &mut [u8]) -> std::io::Result<usize> {
+        use std::io::Write;
         let len = buf.len();
         let slice = self
             .read_slice(len)
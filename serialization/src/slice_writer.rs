@@ -0,0 +1,121 @@
+use crate::core::LqWriter;
+use core::any::Any;
+use liquesco_common::error::{Category, ErrData, LqError};
+
+/// Errors resulting from a slice that's too small.
+const CATEGORY: Category = Category::new("liquesco_buffer_too_small");
+
+/// Returns the error category of errors returned by `SliceWriter::finish` if the slice was
+/// too small.
+pub const fn buffer_too_small_error_category() -> Category {
+    CATEGORY
+}
+
+/// Attached to errors returned by `SliceWriter::finish` if the slice was too small (see
+/// `LqError::data`; requires the `alloc` feature).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    /// The number of bytes required to write all data.
+    pub required_len: usize,
+    /// The length of the slice.
+    pub len: usize,
+}
+
+impl ErrData for BufferTooSmall {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// A `LqWriter` that writes into a caller-provided slice (e.g. a buffer on the stack); does
+/// not allocate.
+///
+/// When the slice is too small, writing does not fail immediately; the writer just counts
+/// the bytes that did not fit. `finish` then returns an error that contains the required
+/// size (see also `required_len` and `BufferTooSmall`), so the caller can retry with a buffer
+/// that's large enough.
+pub struct SliceWriter<'a> {
+    data: &'a mut [u8],
+    /// Number of bytes written (or that would have been written if the slice was large
+    /// enough).
+    len: usize,
+}
+
+impl<'a> From<&'a mut [u8]> for SliceWriter<'a> {
+    fn from(data: &'a mut [u8]) -> Self {
+        SliceWriter { data, len: 0 }
+    }
+}
+
+impl<'a> SliceWriter<'a> {
+    /// The number of bytes written so far (only valid if `is_too_small` is false).
+    pub fn written(&self) -> usize {
+        self.len.min(self.data.len())
+    }
+
+    /// The number of bytes required to write all data (this is equal to `written` if the
+    /// slice was large enough).
+    pub fn required_len(&self) -> usize {
+        self.len
+    }
+
+    /// True if the data did not fit into the slice.
+    pub fn is_too_small(&self) -> bool {
+        self.len > self.data.len()
+    }
+
+    /// Returns the number of bytes written or an error if the slice was too small. The error
+    /// has the category `buffer_too_small_error_category` and (with the `alloc` feature)
+    /// `BufferTooSmall` data.
+    pub fn finish(&self) -> Result<usize, LqError> {
+        if self.is_too_small() {
+            let err = LqError::new(lq_msg!(
+                "Buffer too small: {} bytes are required but the buffer only has {} bytes.",
+                self.len,
+                self.data.len()
+            ))
+            .with_category(CATEGORY);
+            #[cfg(feature = "alloc")]
+            let err = err.with_data(BufferTooSmall {
+                required_len: self.len,
+                len: self.data.len(),
+            });
+            Result::Err(err)
+        } else {
+            Result::Ok(self.len)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> std::io::Write for SliceWriter<'a> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_slice(buf).map_err(std::io::Error::other)?;
+        Result::Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Result::Ok(())
+    }
+}
+
+impl<'a> LqWriter for SliceWriter<'a> {
+    #[inline]
+    fn write_u8(&mut self, data: u8) -> Result<(), LqError> {
+        if let Some(target) = self.data.get_mut(self.len) {
+            *target = data;
+        }
+        self.len += 1;
+        Result::Ok(())
+    }
+
+    #[inline]
+    fn write_slice(&mut self, buf: &[u8]) -> Result<(), LqError> {
+        let end = self.len + buf.len();
+        if let Some(target) = self.data.get_mut(self.len..end) {
+            target.copy_from_slice(buf);
+        }
+        self.len = end;
+        Result::Ok(())
+    }
+}
@@ -4,12 +4,30 @@ use crate::core::LqWriter;
 use crate::core::Serializer;
 use crate::major_types::TYPE_BINARY;
 use crate::types::common_binary::binary_read;
+use crate::types::common_binary::binary_read_with;
 use crate::types::common_binary::binary_write;
 use liquesco_common::error::LqError;
 
 /// Arbitrary binary data.
 pub struct Binary;
 
+impl Binary {
+    /// De-serializes the binary and passes it to given function. Unlike `de_serialize` this
+    /// does not require the binary to live for `'a` (see `LqReader::read_slice_with`).
+    pub fn de_serialize_with<'a, R, Ret, F>(reader: &mut R, function: F) -> Result<Ret, LqError>
+    where
+        R: LqReader<'a>,
+        F: FnOnce(&[u8]) -> Result<Ret, LqError>,
+    {
+        binary_read_with(reader, |id, bin| {
+            if id != TYPE_BINARY {
+                return LqError::err_new("Type is not binary data");
+            }
+            function(bin)
+        })
+    }
+}
+
 impl<'a> DeSerializer<'a> for Binary {
     type Item = &'a [u8];
 
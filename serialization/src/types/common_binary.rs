@@ -2,8 +2,8 @@ use crate::core::ContentDescription;
 use crate::core::LqReader;
 use crate::core::LqWriter;
 use crate::core::MajorType;
+use core::convert::TryFrom;
 use liquesco_common::error::LqError;
-use std::convert::TryFrom;
 
 #[inline]
 pub(crate) fn binary_write<W: LqWriter>(
@@ -16,7 +16,7 @@ pub(crate) fn binary_write<W: LqWriter>(
     let mut content_description = ContentDescription::default();
     content_description.set_self_length(bin_len_as_u64);
     writer.write_content_description(major_type, &content_description)?;
-    writer.write_all(data)?;
+    writer.write_slice(data)?;
     Result::Ok(())
 }
 
@@ -24,12 +24,31 @@ pub(crate) fn binary_write<W: LqWriter>(
 pub(crate) fn binary_read<'a, R: LqReader<'a>>(
     reader: &mut R,
 ) -> Result<(MajorType, &'a [u8]), LqError> {
+    let (major_type, usize_len) = binary_read_header(reader)?;
+    let read_result = reader.read_slice(usize_len)?;
+    Result::Ok((major_type, read_result))
+}
+
+/// Same as `binary_read` but uses `LqReader::read_slice_with` (the binary does not have to
+/// live for `'a`).
+#[inline]
+pub(crate) fn binary_read_with<'a, R, Ret, F>(reader: &mut R, function: F) -> Result<Ret, LqError>
+where
+    R: LqReader<'a>,
+    F: FnOnce(MajorType, &[u8]) -> Result<Ret, LqError>,
+{
+    let (major_type, usize_len) = binary_read_header(reader)?;
+    reader.read_slice_with(usize_len, |slice| function(major_type, slice))
+}
+
+#[inline]
+fn binary_read_header<'a, R: LqReader<'a>>(reader: &mut R) -> Result<(MajorType, usize), LqError> {
     let header = reader.read_header_byte()?;
     let content_description = reader.read_content_description_given_header_byte(header)?;
     let len = content_description.self_length();
     // binaries can never contain embedded values
     if content_description.number_of_embedded_items() != 0 {
-        return LqError::err_new(format!(
+        return LqError::err_new(lq_msg!(
             "Binary types can never contain embedded values. Got {:?} \
              embedded values. Major type {:?} (is this the correct major type?); Also make sure \
              you don't serde binaries as sequence of u8 (see serde documentation).",
@@ -38,7 +57,10 @@ pub(crate) fn binary_read<'a, R: LqReader<'a>>(
         ));
     }
 
+    if let Some(state) = reader.limit_state() {
+        state.check_binary_len(len)?;
+    }
+
     let usize_len = usize::try_from(len)?;
-    let read_result = reader.read_slice(usize_len)?;
-    Result::Ok((header.major_type(), read_result))
+    Result::Ok((header.major_type(), usize_len))
 }
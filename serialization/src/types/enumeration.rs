@@ -8,6 +8,7 @@ use crate::core::ContentDescription;
 use crate::core::DeSerializer;
 use crate::core::LqReader;
 use crate::core::LqWriter;
+use crate::core::MajorType;
 use crate::core::Serializer;
 use liquesco_common::error::LqError;
 
@@ -63,7 +64,7 @@ impl<'a> DeSerializer<'a> for EnumHeader {
             _ => {
                 // length has to be 0 here
                 if self_length != 0 {
-                    return LqError::err_new(format!(
+                    return LqError::err_new(lq_msg!(
                         "Expecting to have a self length of 0; have {:?}.",
                         self_length
                     ));
@@ -74,7 +75,7 @@ impl<'a> DeSerializer<'a> for EnumHeader {
                     TYPE_ENUM_2 => 2,
                     TYPE_ENUM_3 => 3,
                     n => {
-                        return LqError::err_new(format!(
+                        return LqError::err_new(lq_msg!(
                             "Not an enum type; \
                              wrong major type. Need one of the enum major types. Have {:?}.",
                             n
@@ -84,6 +85,21 @@ impl<'a> DeSerializer<'a> for EnumHeader {
             }
         };
 
+        if reader.canonical_only() {
+            let (canonical_major_type, canonical_self_len) = major_type_and_self_len(ordinal);
+            if canonical_major_type != major_type || canonical_self_len != self_length {
+                return LqError::err_new(lq_msg!(
+                    "Non-canonical enum header: Ordinal {} has been encoded using {:?} (self \
+                     length {}); canonical encoding uses {:?} (self length {}).",
+                    ordinal,
+                    major_type,
+                    self_length,
+                    canonical_major_type,
+                    canonical_self_len
+                ));
+            }
+        }
+
         Result::Ok(Self {
             ordinal,
             number_of_values: content_description.number_of_embedded_items(),
@@ -91,28 +107,25 @@ impl<'a> DeSerializer<'a> for EnumHeader {
     }
 }
 
+/// The major type and the self length used to encode given ordinal.
+fn major_type_and_self_len(ordinal: u32) -> (MajorType, u64) {
+    match ordinal {
+        0 => (TYPE_ENUM_0, 0),
+        1 => (TYPE_ENUM_1, 0),
+        2 => (TYPE_ENUM_2, 0),
+        3 => (TYPE_ENUM_3, 0),
+        n if n <= u32::from(u8::MAX) => (TYPE_ENUM_N, 1),
+        n if n <= u32::from(u16::MAX) => (TYPE_ENUM_N, 2),
+        _ => (TYPE_ENUM_N, 4),
+    }
+}
+
 impl<'a> Serializer for EnumHeader {
     type Item = Self;
 
     fn serialize<W: LqWriter>(writer: &mut W, item: &Self::Item) -> Result<(), LqError> {
         let ordinal: u32 = item.ordinal;
-        let major_type = match ordinal {
-            0 => TYPE_ENUM_0,
-            1 => TYPE_ENUM_1,
-            2 => TYPE_ENUM_2,
-            3 => TYPE_ENUM_3,
-            _ => TYPE_ENUM_N,
-        };
-
-        let self_len = if major_type == TYPE_ENUM_N {
-            match ordinal {
-                n if n <= u32::from(std::u8::MAX) => 1,
-                n if n <= u32::from(std::u16::MAX) => 2,
-                _ => 4,
-            }
-        } else {
-            0
-        };
+        let (major_type, self_len) = major_type_and_self_len(ordinal);
 
         // write header
         let mut content_description = ContentDescription::default();
@@ -123,8 +136,8 @@ impl<'a> Serializer for EnumHeader {
         // depending on the ordinal we also have to write the ordinal
         if major_type == TYPE_ENUM_N {
             match ordinal {
-                n if n <= u32::from(std::u8::MAX) => writer.write_u8(ordinal as u8),
-                n if n <= u32::from(std::u16::MAX) => writer.write_u16(ordinal as u16),
+                n if n <= u32::from(u8::MAX) => writer.write_u8(ordinal as u8),
+                n if n <= u32::from(u16::MAX) => writer.write_u16(ordinal as u16),
                 _ => writer.write_u32(ordinal),
             }?;
         }
@@ -6,11 +6,11 @@ use crate::core::LqReader;
 use crate::core::LqWriter;
 use crate::core::Serializer;
 use crate::major_types::TYPE_FLOAT;
+use core::convert::TryFrom;
+use core::fmt::{Display, Error, Formatter};
+use core::hash::Hash;
+use core::hash::Hasher;
 use liquesco_common::error::LqError;
-use std::convert::TryFrom;
-use std::fmt::{Display, Error, Formatter};
-use std::hash::Hash;
-use std::hash::Hasher;
 
 /// 32 bit or 64 bit float.
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -36,7 +36,7 @@ impl Float {
         if let Float::F32(f32_value) = self {
             Result::Ok(*f32_value)
         } else {
-            LqError::err_new(format!(
+            LqError::err_new(lq_msg!(
                 "Given value is a float 64 - want a float 32; \
                  value {:?}",
                 self
@@ -48,7 +48,7 @@ impl Float {
         if let Float::F64(f64_value) = self {
             Result::Ok(*f64_value)
         } else {
-            LqError::err_new(format!(
+            LqError::err_new(lq_msg!(
                 "Given value is a float 32 - want a float 64; \
                  value {:?}",
                 self
@@ -178,7 +178,7 @@ fn begin_de_serialize<'a, R: LqReader<'a>>(reader: &mut R) -> Result<Type, LqErr
     match content_description.self_length() {
         4 => Result::Ok(Type::F32),
         8 => Result::Ok(Type::F64),
-        n => LqError::err_new(format!(
+        n => LqError::err_new(lq_msg!(
             "Float has invalid number of bytes ({:?}); supported \
              floats are float32 (4 bytes) and float64 (8 bytes).",
             n
@@ -2,6 +2,7 @@ pub mod binary;
 pub mod boolean;
 pub mod enumeration;
 pub mod float;
+pub mod native;
 pub mod option;
 pub mod seq;
 pub mod sint;
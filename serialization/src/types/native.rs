@@ -0,0 +1,265 @@
+//! De-/serializers for Rust's own types: The types are their own `Serializer` / `DeSerializer`
+//! (`Item = Self`). The encoding is the same as the one used by serde (see `crate::serde`); this
+//! is what the `LqSerialize` / `LqDeserialize` derive macros use for the fields.
+
+use crate::core::DeSerializer;
+use crate::core::LqReader;
+use crate::core::LqWriter;
+use crate::core::Serializer;
+use crate::types::binary::Binary;
+use crate::types::boolean::Bool;
+use crate::types::float::Float32;
+use crate::types::float::Float64;
+use crate::types::option::Presence;
+use crate::types::sint::SInt128;
+use crate::types::sint::SInt16;
+use crate::types::sint::SInt32;
+use crate::types::sint::SInt64;
+use crate::types::sint::SInt8;
+use crate::types::uint::UInt128;
+use crate::types::uint::UInt16;
+use crate::types::uint::UInt32;
+use crate::types::uint::UInt64;
+use crate::types::uint::UInt8;
+use crate::types::unicode::Unicode;
+use liquesco_common::error::LqError;
+
+#[cfg(feature = "alloc")]
+use crate::types::seq::SeqHeader;
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::convert::TryFrom;
+
+/// Implements `Serializer` and `DeSerializer` for a type by delegating to the given marker type.
+macro_rules! delegate {
+    ($ty:ty, $marker:ty) => {
+        impl<'a> DeSerializer<'a> for $ty {
+            type Item = $ty;
+
+            fn de_serialize<R: LqReader<'a>>(reader: &mut R) -> Result<Self::Item, LqError> {
+                <$marker>::de_serialize(reader)
+            }
+        }
+
+        impl Serializer for $ty {
+            type Item = $ty;
+
+            fn serialize<W: LqWriter>(writer: &mut W, item: &Self::Item) -> Result<(), LqError> {
+                <$marker>::serialize(writer, item)
+            }
+        }
+    };
+}
+
+delegate!(bool, Bool);
+delegate!(u8, UInt8);
+delegate!(u16, UInt16);
+delegate!(u32, UInt32);
+delegate!(u64, UInt64);
+delegate!(u128, UInt128);
+delegate!(i8, SInt8);
+delegate!(i16, SInt16);
+delegate!(i32, SInt32);
+delegate!(i64, SInt64);
+delegate!(i128, SInt128);
+delegate!(f32, Float32);
+delegate!(f64, Float64);
+
+/// Text borrowed from the reader.
+impl<'de: 'a, 'a> DeSerializer<'de> for &'a str {
+    type Item = &'a str;
+
+    fn de_serialize<R: LqReader<'de>>(reader: &mut R) -> Result<Self::Item, LqError> {
+        Unicode::de_serialize(reader)
+    }
+}
+
+impl<'a> Serializer for &'a str {
+    type Item = &'a str;
+
+    fn serialize<W: LqWriter>(writer: &mut W, item: &Self::Item) -> Result<(), LqError> {
+        Unicode::serialize(writer, item)
+    }
+}
+
+/// Binary borrowed from the reader.
+impl<'de: 'a, 'a> DeSerializer<'de> for &'a [u8] {
+    type Item = &'a [u8];
+
+    fn de_serialize<R: LqReader<'de>>(reader: &mut R) -> Result<Self::Item, LqError> {
+        Binary::de_serialize(reader)
+    }
+}
+
+impl<'a> Serializer for &'a [u8] {
+    type Item = &'a [u8];
+
+    fn serialize<W: LqWriter>(writer: &mut W, item: &Self::Item) -> Result<(), LqError> {
+        Binary::serialize(writer, item)
+    }
+}
+
+impl<'a, T: DeSerializer<'a, Item = T>> DeSerializer<'a> for Option<T> {
+    type Item = Self;
+
+    fn de_serialize<R: LqReader<'a>>(reader: &mut R) -> Result<Self::Item, LqError> {
+        match Presence::de_serialize(reader)? {
+            Presence::Absent => Result::Ok(Option::None),
+            Presence::Present => {
+                reader.limit_enter()?;
+                let value = T::de_serialize(reader)?;
+                reader.limit_exit();
+                Result::Ok(Option::Some(value))
+            }
+        }
+    }
+}
+
+impl<T: Serializer<Item = T>> Serializer for Option<T> {
+    type Item = Self;
+
+    fn serialize<W: LqWriter>(writer: &mut W, item: &Self::Item) -> Result<(), LqError> {
+        match item {
+            Option::None => Presence::serialize(writer, &Presence::Absent),
+            Option::Some(value) => {
+                Presence::serialize(writer, &Presence::Present)?;
+                T::serialize(writer, value)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> DeSerializer<'a> for String {
+    type Item = Self;
+
+    fn de_serialize<R: LqReader<'a>>(reader: &mut R) -> Result<Self::Item, LqError> {
+        let value = Unicode::de_serialize(reader)?;
+        reader.limit_allocate(value.len() as u64)?;
+        Result::Ok(String::from(value))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serializer for String {
+    type Item = Self;
+
+    fn serialize<W: LqWriter>(writer: &mut W, item: &Self::Item) -> Result<(), LqError> {
+        Unicode::serialize(writer, item)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: DeSerializer<'a, Item = T>> DeSerializer<'a> for Box<T> {
+    type Item = Self;
+
+    fn de_serialize<R: LqReader<'a>>(reader: &mut R) -> Result<Self::Item, LqError> {
+        reader.limit_allocate(core::mem::size_of::<T>() as u64)?;
+        Result::Ok(Box::new(T::de_serialize(reader)?))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Serializer<Item = T>> Serializer for Box<T> {
+    type Item = Self;
+
+    fn serialize<W: LqWriter>(writer: &mut W, item: &Self::Item) -> Result<(), LqError> {
+        T::serialize(writer, item)
+    }
+}
+
+/// The number of elements to pre-allocate at most. The number of elements is declared by
+/// the data - we do not want to allocate lots of memory for data claiming to contain
+/// billions of elements (but actually does not).
+#[cfg(feature = "alloc")]
+const MAX_PRE_ALLOCATION: usize = 1024;
+
+/// A vector is a sequence of its elements.
+#[cfg(feature = "alloc")]
+impl<'a, T: DeSerializer<'a, Item = T>> DeSerializer<'a> for Vec<T> {
+    type Item = Self;
+
+    fn de_serialize<R: LqReader<'a>>(reader: &mut R) -> Result<Self::Item, LqError> {
+        let length = SeqHeader::de_serialize(reader)?.length();
+        let element_size = core::mem::size_of::<T>() as u64;
+        reader.limit_allocate(u64::from(length).saturating_mul(element_size))?;
+        let mut values = Vec::with_capacity(usize::try_from(length)?.min(MAX_PRE_ALLOCATION));
+        reader.limit_enter()?;
+        for _ in 0..length {
+            values.push(T::de_serialize(reader)?);
+        }
+        reader.limit_exit();
+        Result::Ok(values)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Serializer<Item = T>> Serializer for Vec<T> {
+    type Item = Self;
+
+    fn serialize<W: LqWriter>(writer: &mut W, item: &Self::Item) -> Result<(), LqError> {
+        SeqHeader::serialize(writer, &SeqHeader::new(u32::try_from(item.len())?))?;
+        for value in item {
+            T::serialize(writer, value)?;
+        }
+        Result::Ok(())
+    }
+}
+
+/// A map is a sequence of entries; each entry is a sequence of key and value. Entries with
+/// more or less than two items and duplicate keys are rejected.
+#[cfg(feature = "alloc")]
+impl<'a, K, V> DeSerializer<'a> for BTreeMap<K, V>
+where
+    K: DeSerializer<'a, Item = K> + Ord,
+    V: DeSerializer<'a, Item = V>,
+{
+    type Item = Self;
+
+    fn de_serialize<R: LqReader<'a>>(reader: &mut R) -> Result<Self::Item, LqError> {
+        let length = SeqHeader::de_serialize(reader)?.length();
+        let entry_size = core::mem::size_of::<(K, V)>() as u64;
+        reader.limit_allocate(u64::from(length).saturating_mul(entry_size))?;
+        let mut map = BTreeMap::new();
+        reader.limit_enter()?;
+        for index in 0..length {
+            let entry = SeqHeader::de_serialize(reader)?;
+            if entry.length() != 2 {
+                return LqError::err_new(lq_msg!(
+                    "Every map entry must be a sequence of key and value (two items); the \
+                     entry at index {} has {} items.",
+                    index,
+                    entry.length()
+                ));
+            }
+            reader.limit_enter()?;
+            let key = K::de_serialize(reader)?;
+            let value = V::de_serialize(reader)?;
+            reader.limit_exit();
+            if map.insert(key, value).is_some() {
+                return LqError::err_new(lq_msg!(
+                    "The map contains a duplicate key (entry at index {}).",
+                    index
+                ));
+            }
+        }
+        reader.limit_exit();
+        Result::Ok(map)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Serializer<Item = K>, V: Serializer<Item = V>> Serializer for BTreeMap<K, V> {
+    type Item = Self;
+
+    fn serialize<W: LqWriter>(writer: &mut W, item: &Self::Item) -> Result<(), LqError> {
+        SeqHeader::serialize(writer, &SeqHeader::new(u32::try_from(item.len())?))?;
+        for (key, value) in item {
+            SeqHeader::serialize(writer, &SeqHeader::new(2))?;
+            K::serialize(writer, key)?;
+            V::serialize(writer, value)?;
+        }
+        Result::Ok(())
+    }
+}
@@ -28,7 +28,7 @@ impl<'a> DeSerializer<'a> for Presence {
             return LqError::err_new("Given type is not the option type");
         }
         if content_description.self_length() != 0 {
-            return LqError::err_new(format!(
+            return LqError::err_new(lq_msg!(
                 "Option types must have a self length of 0 (this value has a self 
             length of {:?})",
                 content_description.self_length()
@@ -38,7 +38,7 @@ impl<'a> DeSerializer<'a> for Presence {
         match content_description.number_of_embedded_items() {
             0 => Result::Ok(Presence::Absent),
             1 => Result::Ok(Presence::Present),
-            n => LqError::err_new(format!(
+            n => LqError::err_new(lq_msg!(
                 "Invalid option type (option types need to have 0 or 1 
             embedded item(s)). This value has {:?} embedded items.",
                 n
@@ -6,7 +6,6 @@ use crate::core::LqReader;
 use crate::core::LqWriter;
 use crate::core::Serializer;
 use liquesco_common::error::LqError;
-use std::convert::TryFrom;
 
 /// A sequence has n embedded items. It's not required that the embedded items are of the same
 /// type: So it's also possible to use the sequence for structs and tuples.
@@ -26,12 +25,15 @@ impl SeqHeader {
         self.length
     }
 
+    /// Starts reading a struct with `wanted_number_of_items` fields. The data may contain more
+    /// items than wanted (fields added by a newer version); these are skipped on `finish`.
     pub fn begin(&self, wanted_number_of_items: u32) -> Result<SeqRead, LqError> {
-        if wanted_number_of_items < self.length {
-            LqError::err_new(format!(
-                "Expecting to have a struct with at least {:?} fields; 
-            have {:?} fields.",
-                wanted_number_of_items, self.length
+        if self.length < wanted_number_of_items {
+            LqError::err_new(lq_msg!(
+                "Expecting to have a struct with at least {:?} fields; \
+                 have {:?} fields.",
+                wanted_number_of_items,
+                self.length
             ))
         } else {
             Result::Ok(SeqRead {
@@ -63,7 +65,7 @@ pub struct SeqRead {
 impl SeqRead {
     pub fn finish<'a, R: LqReader<'a>>(self, reader: &mut R) -> Result<(), LqError> {
         let fields_to_skip = self.actual_number_of_items - self.wanted_number_of_items;
-        reader.skip_n_values(usize::try_from(fields_to_skip)?)
+        reader.skip_n_values_u32(fields_to_skip)
     }
 }
 
@@ -73,7 +75,7 @@ impl<'a> DeSerializer<'a> for SeqHeader {
     fn de_serialize<Reader: LqReader<'a>>(reader: &mut Reader) -> Result<Self::Item, LqError> {
         let type_header = reader.read_header_byte()?;
         if type_header.major_type() != TYPE_SEQ {
-            return LqError::err_new(format!(
+            return LqError::err_new(lq_msg!(
                 "Got something that's not a sequence (major type \
                  {:?}). Got major type {:?}.",
                 TYPE_SEQ,
@@ -82,7 +84,7 @@ impl<'a> DeSerializer<'a> for SeqHeader {
         }
         let content_description = reader.read_content_description_given_header_byte(type_header)?;
         if content_description.self_length() != 0 {
-            return LqError::err_new(format!(
+            return LqError::err_new(lq_msg!(
                 "Lists always have a self length of 0. This 'list' has a self 
             length of {:?}",
                 content_description.self_length()
@@ -4,8 +4,8 @@ use crate::core::LqReader;
 use crate::core::LqWriter;
 use crate::core::Serializer;
 use crate::major_types::TYPE_SINT;
+use core::convert::TryFrom;
 use liquesco_common::error::LqError;
-use std::convert::TryFrom;
 
 /// A 128 bit signed integer.
 pub struct SInt128;
@@ -18,7 +18,7 @@ impl<'a> DeSerializer<'a> for SInt128 {
         let content_description = reader.read_content_description_given_header_byte(type_header)?;
 
         if type_header.major_type() != TYPE_SINT {
-            return LqError::err_new(format!(
+            return LqError::err_new(lq_msg!(
                 "Given type is not a signed integer type; \
                  major type is {:?}.",
                 type_header.major_type()
@@ -28,15 +28,38 @@ impl<'a> DeSerializer<'a> for SInt128 {
             return LqError::err_new("Integer types must not contain embedded values.");
         }
 
-        match content_description.self_length() {
-            0 => Result::Ok(0),
-            1 => Result::Ok(i128::from(reader.read_i8()?)),
-            2 => Result::Ok(i128::from(reader.read_i16()?)),
-            4 => Result::Ok(i128::from(reader.read_i32()?)),
-            8 => Result::Ok(i128::from(reader.read_i64()?)),
-            16 => reader.read_i128(),
-            _ => LqError::err_new("Invalid length for signed integer type"),
+        let value = match content_description.self_length() {
+            0 => 0,
+            1 => i128::from(reader.read_i8()?),
+            2 => i128::from(reader.read_i16()?),
+            4 => i128::from(reader.read_i32()?),
+            8 => i128::from(reader.read_i64()?),
+            16 => reader.read_i128()?,
+            _ => return LqError::err_new("Invalid length for signed integer type"),
+        };
+
+        if reader.canonical_only() && content_description.self_length() != canonical_len(value) {
+            return LqError::err_new(lq_msg!(
+                "Non-canonical signed integer: {} has been encoded using {} bytes; \
+                 canonical encoding uses {} bytes.",
+                value,
+                content_description.self_length(),
+                canonical_len(value)
+            ));
         }
+        Result::Ok(value)
+    }
+}
+
+/// The number of bytes used to encode given value.
+fn canonical_len(value: i128) -> u64 {
+    match value {
+        0 => 0,
+        n if n >= i128::from(i8::MIN) && n <= i128::from(i8::MAX) => 1,
+        n if n >= i128::from(i16::MIN) && n <= i128::from(i16::MAX) => 2,
+        n if n >= i128::from(i32::MIN) && n <= i128::from(i32::MAX) => 4,
+        n if n >= i128::from(i64::MIN) && n <= i128::from(i64::MAX) => 8,
+        _ => 16,
     }
 }
 
@@ -47,28 +70,28 @@ impl Serializer for SInt128 {
         let deref_item = *item;
         match deref_item {
             0 => writer.write_content_description(TYPE_SINT, &ContentDescription::default()),
-            n if n >= i128::from(std::i8::MIN) && n <= i128::from(std::i8::MAX) => {
+            n if n >= i128::from(i8::MIN) && n <= i128::from(i8::MAX) => {
                 writer.write_content_description(
                     TYPE_SINT,
                     &ContentDescription::new_self_length(1),
                 )?;
                 writer.write_i8(deref_item as i8)
             }
-            n if n >= i128::from(std::i16::MIN) && n <= i128::from(std::i16::MAX) => {
+            n if n >= i128::from(i16::MIN) && n <= i128::from(i16::MAX) => {
                 writer.write_content_description(
                     TYPE_SINT,
                     &ContentDescription::new_self_length(2),
                 )?;
                 writer.write_i16(deref_item as i16)
             }
-            n if n >= i128::from(std::i32::MIN) && n <= i128::from(std::i32::MAX) => {
+            n if n >= i128::from(i32::MIN) && n <= i128::from(i32::MAX) => {
                 writer.write_content_description(
                     TYPE_SINT,
                     &ContentDescription::new_self_length(4),
                 )?;
                 writer.write_i32(deref_item as i32)
             }
-            n if n >= i128::from(std::i64::MIN) && n <= i128::from(std::i64::MAX) => {
+            n if n >= i128::from(i64::MIN) && n <= i128::from(i64::MAX) => {
                 writer.write_content_description(
                     TYPE_SINT,
                     &ContentDescription::new_self_length(8),
@@ -4,8 +4,8 @@ use crate::core::LqReader;
 use crate::core::LqWriter;
 use crate::core::Serializer;
 use crate::major_types::TYPE_UINT;
+use core::convert::TryFrom;
 use liquesco_common::error::LqError;
-use std::convert::TryFrom;
 
 /// 128 bit unsigned integer.
 pub struct UInt128;
@@ -18,7 +18,7 @@ impl<'a> DeSerializer<'a> for UInt128 {
         let content_description = reader.read_content_description_given_header_byte(type_header)?;
 
         if type_header.major_type() != TYPE_UINT {
-            return LqError::err_new(format!(
+            return LqError::err_new(lq_msg!(
                 "Given type is not an unsigned integer type. \
                  Major type is {:?}.",
                 type_header.major_type()
@@ -28,15 +28,38 @@ impl<'a> DeSerializer<'a> for UInt128 {
             return LqError::err_new("Integer types must not contain embedded values.");
         }
 
-        match content_description.self_length() {
-            0 => Result::Ok(0),
-            1 => Result::Ok(u128::from(reader.read_u8()?)),
-            2 => Result::Ok(u128::from(reader.read_u16()?)),
-            4 => Result::Ok(u128::from(reader.read_u32()?)),
-            8 => Result::Ok(u128::from(reader.read_u64()?)),
-            16 => Result::Ok(reader.read_u128()?),
-            _ => LqError::err_new("Invalid length for unsigned integer type"),
+        let value = match content_description.self_length() {
+            0 => 0,
+            1 => u128::from(reader.read_u8()?),
+            2 => u128::from(reader.read_u16()?),
+            4 => u128::from(reader.read_u32()?),
+            8 => u128::from(reader.read_u64()?),
+            16 => reader.read_u128()?,
+            _ => return LqError::err_new("Invalid length for unsigned integer type"),
+        };
+
+        if reader.canonical_only() && content_description.self_length() != canonical_len(value) {
+            return LqError::err_new(lq_msg!(
+                "Non-canonical unsigned integer: {} has been encoded using {} bytes; \
+                 canonical encoding uses {} bytes.",
+                value,
+                content_description.self_length(),
+                canonical_len(value)
+            ));
         }
+        Result::Ok(value)
+    }
+}
+
+/// The number of bytes used to encode given value.
+fn canonical_len(value: u128) -> u64 {
+    match value {
+        0 => 0,
+        n if n <= u128::from(u8::MAX) => 1,
+        n if n <= u128::from(u16::MAX) => 2,
+        n if n <= u128::from(u32::MAX) => 4,
+        n if n <= u128::from(u64::MAX) => 8,
+        _ => 16,
     }
 }
 
@@ -47,28 +70,28 @@ impl Serializer for UInt128 {
         let deref_item = *item;
         match deref_item {
             0 => writer.write_content_description(TYPE_UINT, &ContentDescription::default()),
-            n if n >= u128::from(std::u8::MIN) && n <= u128::from(std::u8::MAX) => {
+            n if n >= u128::from(u8::MIN) && n <= u128::from(u8::MAX) => {
                 writer.write_content_description(
                     TYPE_UINT,
                     &ContentDescription::new_self_length(1),
                 )?;
                 writer.write_u8(deref_item as u8)
             }
-            n if n >= u128::from(std::u16::MIN) && n <= u128::from(std::u16::MAX) => {
+            n if n >= u128::from(u16::MIN) && n <= u128::from(u16::MAX) => {
                 writer.write_content_description(
                     TYPE_UINT,
                     &ContentDescription::new_self_length(2),
                 )?;
                 writer.write_u16(deref_item as u16)
             }
-            n if n >= u128::from(std::u32::MIN) && n <= u128::from(std::u32::MAX) => {
+            n if n >= u128::from(u32::MIN) && n <= u128::from(u32::MAX) => {
                 writer.write_content_description(
                     TYPE_UINT,
                     &ContentDescription::new_self_length(4),
                 )?;
                 writer.write_u32(deref_item as u32)
             }
-            n if n >= u128::from(std::u64::MIN) && n <= u128::from(std::u64::MAX) => {
+            n if n >= u128::from(u64::MIN) && n <= u128::from(u64::MAX) => {
                 writer.write_content_description(
                     TYPE_UINT,
                     &ContentDescription::new_self_length(8),
@@ -4,9 +4,10 @@ use crate::core::LqWriter;
 use crate::core::Serializer;
 use crate::major_types::TYPE_UNICODE;
 use crate::types::common_binary::binary_read;
+use crate::types::common_binary::binary_read_with;
 use crate::types::common_binary::binary_write;
+use core::str::from_utf8;
 use liquesco_common::error::LqError;
-use std::str::from_utf8;
 
 /// Unicode (UTF-8) text.
 pub struct Unicode;
@@ -17,7 +18,7 @@ impl<'a> DeSerializer<'a> for Unicode {
     fn de_serialize<R: LqReader<'a>>(reader: &mut R) -> Result<Self::Item, LqError> {
         let (id, read_result) = binary_read(reader)?;
         if id != TYPE_UNICODE {
-            return LqError::err_new(format!("Type is not utf8 data, id is {:?}", id));
+            return LqError::err_new(lq_msg!("Type is not utf8 data, id is {:?}", id));
         }
         let maybe_str = from_utf8(read_result);
         match maybe_str {
@@ -40,13 +41,31 @@ impl Serializer for Unicode {
 /// to read ASCII text (in the case of ASCII text the schema will perform validity check).
 pub struct UncheckedUnicode;
 
+impl UncheckedUnicode {
+    /// De-serializes the unchecked unicode and passes it to given function. Unlike
+    /// `de_serialize` this does not require the data to live for `'a` (see
+    /// `LqReader::read_slice_with`).
+    pub fn de_serialize_with<'a, R, Ret, F>(reader: &mut R, function: F) -> Result<Ret, LqError>
+    where
+        R: LqReader<'a>,
+        F: FnOnce(&[u8]) -> Result<Ret, LqError>,
+    {
+        binary_read_with(reader, |id, bytes| {
+            if id != TYPE_UNICODE {
+                return LqError::err_new(lq_msg!("Type is not utf8 data, id is {:?}", id));
+            }
+            function(bytes)
+        })
+    }
+}
+
 impl<'a> DeSerializer<'a> for UncheckedUnicode {
     type Item = &'a [u8];
 
     fn de_serialize<R: LqReader<'a>>(reader: &mut R) -> Result<Self::Item, LqError> {
         let (id, read_result) = binary_read(reader)?;
         if id != TYPE_UNICODE {
-            return LqError::err_new(format!("Type is not utf8 data, id is {:?}", id));
+            return LqError::err_new(lq_msg!("Type is not utf8 data, id is {:?}", id));
         }
         Result::Ok(read_result)
     }
@@ -3,10 +3,12 @@ use crate::core::LqReader;
 use crate::core::LqWriter;
 use crate::core::Serializer;
 use crate::types::binary::Binary;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+use core::convert::TryFrom;
+use core::fmt::Error;
+use core::fmt::Formatter;
 use liquesco_common::error::LqError;
-use serde::export::fmt::Error;
-use serde::export::Formatter;
-use std::convert::TryFrom;
 
 /// 16 byte Universally Unique Identifier (UUID) according to RFC 4122. Does not specify which
 /// variant is allowed. Does not validate: Any 16 byte binary is allowed.
@@ -18,17 +20,18 @@ impl<'a> DeSerializer<'a> for Uuid {
 
     fn de_serialize<R: LqReader<'a>>(reader: &mut R) -> Result<Self::Item, LqError> {
         // it's just a normal binary
-        let binary = Binary::de_serialize(reader)?;
-        let mut uuid_bytes: [u8; 16] = [0; 16];
-        let src_len = binary.len();
-        if src_len != 16 {
-            return LqError::err_new(format!(
-                "Invalid length of UUID (need to be 16 bytes; have {:?} bytes)",
-                src_len
-            ));
-        }
-        uuid_bytes.clone_from_slice(binary);
-        Result::Ok(Uuid(uuid_bytes))
+        Binary::de_serialize_with(reader, |binary| {
+            let mut uuid_bytes: [u8; 16] = [0; 16];
+            let src_len = binary.len();
+            if src_len != 16 {
+                return LqError::err_new(lq_msg!(
+                    "Invalid length of UUID (need to be 16 bytes; have {:?} bytes)",
+                    src_len
+                ));
+            }
+            uuid_bytes.clone_from_slice(binary);
+            Result::Ok(Uuid(uuid_bytes))
+        })
     }
 }
 
@@ -41,7 +44,7 @@ impl TryFrom<&[u8]> for Uuid {
             uuid_bytes.clone_from_slice(value);
             Ok(Uuid(uuid_bytes))
         } else {
-            Err(LqError::new(format!(
+            Err(LqError::new(lq_msg!(
                 "Given binary for uuid has invalid length (need 16 bytes), \
                  have {} bytes",
                 value.len()
@@ -63,10 +66,11 @@ impl Uuid {
         &self.0
     }
 
+    #[cfg(feature = "alloc")]
     pub fn as_hex_string(&self) -> String {
         let mut result = String::with_capacity(self.0.len() * 2);
         for byte in &self.0 {
-            result = result + &format!("{:x}", byte);
+            result = result + &alloc::format!("{:x}", byte);
         }
         result
     }
@@ -117,6 +121,6 @@ impl<'de> serde::de::Visitor<'de> for UuidVisitor {
     where
         E: serde::de::Error,
     {
-        Uuid::try_from(v).map_err(|lq_err| E::custom(format!("{:?}", lq_err)))
+        Uuid::try_from(v).map_err(|lq_err| E::custom(format_args!("{:?}", lq_err)))
     }
 }
@@ -1,10 +1,12 @@
 mod value_fmt;
 mod value_into;
+pub mod view;
 
 use crate::core::DeSerializer;
 use crate::core::LqReader;
 use crate::core::LqWriter;
 use crate::core::Serializer;
+use crate::len_writer::LenWriter;
 use crate::major_types::TYPE_BINARY;
 use crate::major_types::TYPE_BOOL_FALSE;
 use crate::major_types::TYPE_BOOL_TRUE;
@@ -28,11 +30,15 @@ use crate::types::seq::SeqHeader;
 use crate::types::sint::SInt128;
 use crate::types::uint::UInt128;
 use crate::types::unicode::Unicode;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::ops::Deref;
 use liquesco_common::error::LqError;
-use std::convert::TryFrom;
-use std::ops::Deref;
 
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 /// All values this serialization format knows.
 ///
@@ -158,9 +164,13 @@ impl<'a> DeSerializer<'a> for Value<'a> {
             TYPE_OPTION => {
                 let presence = Presence::de_serialize(reader)?;
                 match presence {
-                    Presence::Present => Value::Option(Option::Some(ValueRef::Boxed(Box::new(
-                        Value::de_serialize(reader)?,
-                    )))),
+                    Presence::Present => {
+                        reader.limit_allocate(VALUE_SIZE)?;
+                        reader.limit_enter()?;
+                        let value = Value::de_serialize(reader)?;
+                        reader.limit_exit();
+                        Value::Option(Option::Some(ValueRef::Boxed(Box::new(value))))
+                    }
                     Presence::Absent => Value::Option(Option::None),
                 }
             }
@@ -170,12 +180,7 @@ impl<'a> DeSerializer<'a> for Value<'a> {
                 if length == 0 {
                     Value::Seq(ValueSeq::Empty)
                 } else {
-                    let usize_length = usize::try_from(length)?;
-                    let mut vec = Vec::with_capacity(usize_length);
-                    for _ in 0..length {
-                        vec.push(Value::de_serialize(reader)?);
-                    }
-                    Value::Seq(ValueSeq::Owned(vec))
+                    Value::Seq(ValueSeq::Owned(de_serialize_values(reader, length)?))
                 }
             }
             TYPE_BINARY => {
@@ -191,14 +196,9 @@ impl<'a> DeSerializer<'a> for Value<'a> {
                 let number_of_values = enum_header.number_of_values();
                 if number_of_values > 0 {
                     // de-serialize data
-                    let usize_number_of_values = usize::try_from(number_of_values)?;
-                    let mut values = Vec::with_capacity(usize_number_of_values);
-                    for _ in 0..number_of_values {
-                        values.push(Value::de_serialize(reader)?);
-                    }
                     Value::Enum(ValueVariant {
                         ordinal: enum_header.ordinal(),
-                        values: ValueSeq::Owned(values),
+                        values: ValueSeq::Owned(de_serialize_values(reader, number_of_values)?),
                     })
                 } else {
                     Value::Enum(ValueVariant {
@@ -227,6 +227,30 @@ impl<'a> DeSerializer<'a> for Value<'a> {
     }
 }
 
+/// Size of one value (used to compute allocations, see `LqReader::limit_allocate`).
+const VALUE_SIZE: u64 = core::mem::size_of::<Value<'static>>() as u64;
+
+/// The number of values to pre-allocate at most. The number of values is declared by
+/// the data - we do not want to allocate lots of memory for data claiming to contain
+/// billions of values (but actually does not).
+const MAX_PRE_ALLOCATION: usize = 1024;
+
+/// De-serializes `number_of_values` embedded values.
+fn de_serialize_values<'a, Reader: LqReader<'a>>(
+    reader: &mut Reader,
+    number_of_values: u32,
+) -> Result<Vec<Value<'a>>, LqError> {
+    reader.limit_allocate(u64::from(number_of_values) * VALUE_SIZE)?;
+    let usize_number_of_values = usize::try_from(number_of_values)?;
+    let mut values = Vec::with_capacity(usize_number_of_values.min(MAX_PRE_ALLOCATION));
+    reader.limit_enter()?;
+    for _ in 0..number_of_values {
+        values.push(Value::de_serialize(reader)?);
+    }
+    reader.limit_exit();
+    Result::Ok(values)
+}
+
 impl<'a> Serializer for Value<'a> {
     type Item = Self;
 
@@ -273,4 +297,14 @@ impl<'a> Serializer for Value<'a> {
     }
 }
 
+impl<'a> Value<'a> {
+    /// The number of bytes this value occupies when serialized (computed without serializing
+    /// the value into memory).
+    pub fn serialized_len(&self) -> Result<u64, LqError> {
+        let mut writer = LenWriter::default();
+        Value::serialize(&mut writer, self)?;
+        Result::Ok(writer.len())
+    }
+}
+
 const EMPTY_VALUE_VEC: &[Value<'static>] = &[];
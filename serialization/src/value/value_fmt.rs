@@ -1,6 +1,6 @@
 use crate::value::Value;
-use std::fmt::{Display, Error, Formatter};
-use std::ops::Deref;
+use core::fmt::{Display, Error, Formatter};
+use core::ops::Deref;
 
 impl<'a> Display for Value<'a> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
@@ -4,10 +4,14 @@ use crate::value::Value;
 use crate::value::ValueRef;
 use crate::value::ValueSeq;
 use crate::value::ValueVariant;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
 use liquesco_common::error::LqError;
-use std::convert::TryFrom;
 
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 impl From<bool> for Value<'static> {
     fn from(value: bool) -> Self {
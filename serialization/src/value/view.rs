@@ -0,0 +1,239 @@
+use crate::core::ContentDescription;
+use crate::core::DeSerializer;
+use crate::core::HeaderByte;
+use crate::core::LqReader;
+use crate::core::MajorType;
+use crate::major_types::TYPE_OPTION;
+use crate::major_types::TYPE_SEQ;
+use crate::slice_reader::SliceReader;
+use crate::types::binary::Binary;
+use crate::types::boolean::Bool;
+use crate::types::enumeration::EnumHeader;
+use crate::types::float::Float;
+use crate::types::sint::SInt128;
+use crate::types::uint::UInt128;
+use crate::types::unicode::Unicode;
+use crate::value::Value;
+use alloc::format;
+use core::convert::TryFrom;
+use liquesco_common::error::LqError;
+
+/// A lazy, zero-copy view of a single serialized value.
+///
+/// In contrast to `Value` nothing is decoded in advance: The view just references the bytes
+/// of the value. Embedded values (elements of a sequence, enum variant values or the value
+/// of a present option) can be accessed by index; siblings are skipped (using
+/// `LqReader::skip`) without being decoded. Use this to pluck single fields out of large
+/// records.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ValueView<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> ValueView<'a> {
+    /// Creates a view of given data. The data must contain exactly one value.
+    pub fn new(data: &'a [u8]) -> Result<Self, LqError> {
+        let mut reader: SliceReader = data.into();
+        let view = Self::read(data, &mut reader)?;
+        reader.finish()?;
+        Ok(view)
+    }
+
+    /// Creates a view of the first value in given data and returns the remaining data (the
+    /// data after the value).
+    pub fn new_prefix(data: &'a [u8]) -> Result<(Self, &'a [u8]), LqError> {
+        let mut reader: SliceReader = data.into();
+        let view = Self::read(data, &mut reader)?;
+        Ok((view, &data[reader.offset()..]))
+    }
+
+    /// Reads one value from given reader (the reader must read from `data`).
+    fn read(data: &'a [u8], reader: &mut SliceReader<'a>) -> Result<Self, LqError> {
+        let start = reader.offset();
+        reader.skip()?;
+        let end = reader.offset();
+        Ok(Self {
+            data: &data[start..end],
+        })
+    }
+
+    /// The serialized bytes of this value (including all embedded values).
+    pub fn as_slice(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// The header byte of this value.
+    pub fn header_byte(&self) -> HeaderByte {
+        HeaderByte::from_u8(self.data[0])
+    }
+
+    /// The major type of this value.
+    pub fn major_type(&self) -> MajorType {
+        self.header_byte().major_type()
+    }
+
+    /// The content description (self length and number of embedded items) of this value.
+    pub fn content_description(&self) -> Result<ContentDescription, LqError> {
+        self.reader().read_content_description()
+    }
+
+    /// Returns the number of embedded values. This is the number of elements for sequences,
+    /// the number of values for enum variants and 0 or 1 for options.
+    pub fn number_of_embedded_items(&self) -> Result<u32, LqError> {
+        Ok(self.content_description()?.number_of_embedded_items())
+    }
+
+    /// Returns the embedded value at given index (see `number_of_embedded_items`). Returns
+    /// `None` if there's no embedded value with that index. Values before the requested one
+    /// are skipped, not decoded.
+    pub fn embedded(&self, index: u32) -> Result<Option<ValueView<'a>>, LqError> {
+        let mut reader = self.reader();
+        let content_description = reader.read_content_description()?;
+        if index >= content_description.number_of_embedded_items() {
+            return Ok(None);
+        }
+        reader.skip_bytes_u64(content_description.self_length())?;
+        reader.skip_n_values_u32(index)?;
+        Ok(Some(Self::read(self.data, &mut reader)?))
+    }
+
+    /// Follows the given path of indexes (see `embedded`). An empty path returns this value.
+    pub fn embedded_path(&self, path: &[u32]) -> Result<Option<ValueView<'a>>, LqError> {
+        let mut current = *self;
+        for index in path {
+            if let Some(embedded) = current.embedded(*index)? {
+                current = embedded;
+            } else {
+                return Ok(None);
+            }
+        }
+        Ok(Some(current))
+    }
+
+    /// Iterates all embedded values.
+    pub fn iter(&self) -> Result<ValueViewIter<'a>, LqError> {
+        let mut reader = self.reader();
+        let content_description = reader.read_content_description()?;
+        reader.skip_bytes_u64(content_description.self_length())?;
+        Ok(ValueViewIter {
+            data: self.data,
+            reader,
+            remaining: content_description.number_of_embedded_items(),
+        })
+    }
+
+    /// The number of elements if this is a sequence.
+    pub fn seq_len(&self) -> Result<u32, LqError> {
+        self.require_major_type(TYPE_SEQ, "sequence")?;
+        self.number_of_embedded_items()
+    }
+
+    /// Returns the element at given index if this is a sequence.
+    pub fn seq_element(&self, index: u32) -> Result<Option<ValueView<'a>>, LqError> {
+        self.require_major_type(TYPE_SEQ, "sequence")?;
+        self.embedded(index)
+    }
+
+    /// Returns the present value if this is an option (`None` if absent).
+    pub fn option_value(&self) -> Result<Option<ValueView<'a>>, LqError> {
+        self.require_major_type(TYPE_OPTION, "option")?;
+        self.embedded(0)
+    }
+
+    /// Returns the enum header (ordinal and number of values) if this is an enum.
+    pub fn enum_header(&self) -> Result<EnumHeader, LqError> {
+        EnumHeader::de_serialize(&mut self.reader())
+    }
+
+    /// Returns the variant value at given index if this is an enum.
+    pub fn variant_value(&self, index: u32) -> Result<Option<ValueView<'a>>, LqError> {
+        self.enum_header()?;
+        self.embedded(index)
+    }
+
+    pub fn as_bool(&self) -> Result<bool, LqError> {
+        Bool::de_serialize(&mut self.reader())
+    }
+
+    pub fn as_uint(&self) -> Result<u128, LqError> {
+        UInt128::de_serialize(&mut self.reader())
+    }
+
+    pub fn as_sint(&self) -> Result<i128, LqError> {
+        SInt128::de_serialize(&mut self.reader())
+    }
+
+    pub fn as_float(&self) -> Result<Float, LqError> {
+        Float::de_serialize(&mut self.reader())
+    }
+
+    /// Returns the text; borrowed from the underlying data.
+    pub fn as_unicode(&self) -> Result<&'a str, LqError> {
+        Unicode::de_serialize(&mut self.reader())
+    }
+
+    /// Returns the binary; borrowed from the underlying data.
+    pub fn as_binary(&self) -> Result<&'a [u8], LqError> {
+        Binary::de_serialize(&mut self.reader())
+    }
+
+    /// Decodes this value entirely.
+    pub fn to_value(&self) -> Result<Value<'a>, LqError> {
+        Value::de_serialize(&mut self.reader())
+    }
+
+    fn reader(&self) -> SliceReader<'a> {
+        self.data.into()
+    }
+
+    fn require_major_type(&self, major_type: MajorType, name: &str) -> Result<(), LqError> {
+        if self.major_type() != major_type {
+            LqError::err_new(format!(
+                "Value is not a {} (major type {:?}); it has major type {:?}.",
+                name,
+                major_type,
+                self.major_type()
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for ValueView<'a> {
+    type Error = LqError;
+
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+/// Iterates the embedded values of a `ValueView` (see `ValueView::iter`).
+pub struct ValueViewIter<'a> {
+    data: &'a [u8],
+    reader: SliceReader<'a>,
+    remaining: u32,
+}
+
+impl<'a> Iterator for ValueViewIter<'a> {
+    type Item = Result<ValueView<'a>, LqError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            None
+        } else {
+            self.remaining -= 1;
+            let result = ValueView::read(self.data, &mut self.reader);
+            if result.is_err() {
+                // no need to continue
+                self.remaining = 0;
+            }
+            Some(result)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+}
@@ -0,0 +1,79 @@
+//! Variable length unsigned integers (var ints).
+//!
+//! The first byte determines the length: 0-240 is the value itself; 241-247 are two bytes
+//! (240 to 2031); 248 are three bytes (2032 to 67567); 249 to 254 are followed by 3 to 8
+//! bytes containing the value (little endian).
+
+use crate::core::LqReader;
+use crate::core::LqWriter;
+use liquesco_common::error::LqError;
+
+/// The maximum number of bytes a `u64` var int occupies.
+const MAX_LEN: usize = 9;
+
+/// The number of bytes the canonical encoding of given value occupies.
+pub(crate) fn len(value: u64) -> usize {
+    if value <= 240 {
+        1
+    } else if value <= 2031 {
+        2
+    } else if value <= 67567 {
+        3
+    } else {
+        // 249 is followed by 3 bytes, 250 by 4 bytes ... 254 by 8 bytes.
+        let value_len = 8 - (value.leading_zeros() / 8) as usize;
+        value_len.max(3) + 1
+    }
+}
+
+pub(crate) fn write<W: LqWriter>(writer: &mut W, value: u64) -> Result<(), LqError> {
+    let mut buf = [0u8; MAX_LEN];
+    let len = len(value);
+    match len {
+        1 => buf[0] = value as u8,
+        2 => {
+            buf[0] = ((value - 240) / 256 + 241) as u8;
+            buf[1] = ((value - 240) % 256) as u8;
+        }
+        3 => {
+            buf[0] = 248;
+            buf[1] = ((value - 2032) / 256) as u8;
+            buf[2] = ((value - 2032) % 256) as u8;
+        }
+        _ => {
+            buf[0] = 245 + len as u8;
+            buf[1..len].copy_from_slice(&value.to_le_bytes()[0..len - 1]);
+        }
+    }
+    writer.write_slice(&buf[0..len])
+}
+
+/// Reads a var int; returns the value and the number of bytes read (see `len` for the
+/// canonical length).
+pub(crate) fn read<'a, R: LqReader<'a> + ?Sized>(reader: &mut R) -> Result<(u64, usize), LqError> {
+    let first = reader.read_u8()?;
+    match first {
+        0..=240 => Result::Ok((u64::from(first), 1)),
+        241..=247 => {
+            let second = reader.read_u8()?;
+            let value = 240 + 256 * (u64::from(first) - 241) + u64::from(second);
+            Result::Ok((value, 2))
+        }
+        248 => {
+            let mut buf = [0u8; 2];
+            reader.read_into(&mut buf)?;
+            let value = 2032 + 256 * u64::from(buf[0]) + u64::from(buf[1]);
+            Result::Ok((value, 3))
+        }
+        249..=254 => {
+            let value_len = usize::from(first - 246);
+            let mut buf = [0u8; 8];
+            reader.read_into(&mut buf[0..value_len])?;
+            Result::Ok((u64::from_le_bytes(buf), value_len + 1))
+        }
+        _ => LqError::err_new(lq_msg!(
+            "Invalid var int: First byte is {}; this is not a valid 64 bit var int.",
+            first
+        )),
+    }
+}
@@ -1,5 +1,6 @@
 use crate::core::LqWriter;
 use crate::core::ToVecLqWriter;
+use alloc::vec::Vec;
 use liquesco_common::error::LqError;
 
 /// Implements the `LqWriter` that writes into a `Vec<u8>`.
@@ -13,6 +14,7 @@ impl Default for VecWriter {
     }
 }
 
+#[cfg(feature = "std")]
 impl<'a> std::io::Write for VecWriter {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         self.data.extend_from_slice(buf);
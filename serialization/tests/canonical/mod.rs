@@ -0,0 +1,109 @@
+use liquesco_serialization::canonical::check_canonical;
+use liquesco_serialization::canonical::CanonicalReader;
+use liquesco_serialization::core::DeSerializer;
+use liquesco_serialization::core::Serializer;
+use liquesco_serialization::core::ToVecLqWriter;
+use liquesco_serialization::slice_reader::SliceReader;
+use liquesco_serialization::types::enumeration::EnumHeader;
+use liquesco_serialization::types::uint::UInt128;
+use liquesco_serialization::value::Value;
+use liquesco_serialization::value::ValueVariant;
+use liquesco_serialization::vec_writer::VecWriter;
+
+fn is_canonical(data: &[u8]) -> bool {
+    let mut reader: SliceReader = data.into();
+    check_canonical(&mut reader).is_ok() && reader.finish().is_ok()
+}
+
+#[test]
+fn serialized_values_are_canonical() {
+    let values: Vec<Value<'static>> = vec![
+        0u32.into(),
+        255u32.into(),
+        256u32.into(),
+        u128::MAX.into(),
+        (-129i32).into(),
+        i64::MIN.into(),
+        true.into(),
+        1.5f32.into(),
+        "hello".into(),
+        vec![7u8; 1000].into(),
+        Option::<Value<'static>>::None.into(),
+        Value::Enum(ValueVariant::new(2, 1u32.into())),
+        Value::Enum(ValueVariant::new(300, Value::from("x"))),
+        vec![Value::from(1u32); 500].into(),
+    ];
+    for value in values {
+        let mut writer = VecWriter::default();
+        Value::serialize(&mut writer, &value).unwrap();
+        let data = writer.into_vec();
+        assert!(is_canonical(&data), "Not canonical: {:?}", value);
+    }
+}
+
+#[test]
+fn uint_not_minimal_width() {
+    // 5 encoded using 8 bytes (TYPE_UINT, Len8)
+    let data = [6 * 13 + 4, 5, 0, 0, 0, 0, 0, 0, 0];
+    assert!(!is_canonical(&data));
+
+    // the non-strict mode accepts it...
+    let mut reader: SliceReader = (&data[..]).into();
+    assert_eq!(5, UInt128::de_serialize(&mut reader).unwrap());
+    // ...the strict mode does not
+    let mut reader = CanonicalReader::new(SliceReader::from(&data[..]));
+    assert!(UInt128::de_serialize(&mut reader).is_err());
+}
+
+#[test]
+fn enum_ordinal_not_using_short_form() {
+    // ordinal 2 encoded as TYPE_ENUM_N (Len1)
+    let data = [13 * 13 + 1, 2];
+    assert!(!is_canonical(&data));
+
+    let mut reader: SliceReader = (&data[..]).into();
+    assert_eq!(2, EnumHeader::de_serialize(&mut reader).unwrap().ordinal());
+    let mut reader = CanonicalReader::new(SliceReader::from(&data[..]));
+    assert!(EnumHeader::de_serialize(&mut reader).is_err());
+}
+
+#[test]
+fn overlong_varint() {
+    // binary with 240 bytes; the length (240) has been encoded using 2 bytes.
+    let mut data = vec![4 * 13 + 6, 241, 0];
+    data.extend_from_slice(&[0u8; 240]);
+    assert!(!is_canonical(&data));
+
+    // canonical: length encoded using one byte
+    let mut data = vec![4 * 13 + 6, 240];
+    data.extend_from_slice(&[0u8; 240]);
+    assert!(is_canonical(&data));
+}
+
+#[test]
+fn content_info_not_shortest() {
+    // sequence with one element (false); number of elements encoded as varint
+    let data = [3 * 13 + 10, 1, 0];
+    assert!(!is_canonical(&data));
+    // canonical: ContainerOneEmpty
+    let data = [3 * 13 + 7, 0];
+    assert!(is_canonical(&data));
+}
+
+#[test]
+fn nested_non_canonical_value() {
+    // sequence of two: false and 5 (encoded using 2 bytes)
+    let data = [3 * 13 + 8, 0, 6 * 13 + 2, 5, 0];
+    assert!(!is_canonical(&data));
+}
+
+#[test]
+fn reader_positioned_after_value() {
+    // true, false
+    let data = [13, 0];
+    let mut reader: SliceReader = (&data[..]).into();
+    check_canonical(&mut reader).unwrap();
+    assert_eq!(1, reader.offset());
+    check_canonical(&mut reader).unwrap();
+    reader.finish().unwrap();
+}
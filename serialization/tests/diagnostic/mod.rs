@@ -0,0 +1,207 @@
+use liquesco_serialization::core::Serializer;
+use liquesco_serialization::core::ToVecLqWriter;
+use liquesco_serialization::diagnostic::format;
+use liquesco_serialization::diagnostic::format_value;
+use liquesco_serialization::diagnostic::format_with;
+use liquesco_serialization::diagnostic::parse;
+use liquesco_serialization::diagnostic::parse_value;
+use liquesco_serialization::diagnostic::parse_with;
+use liquesco_serialization::diagnostic::BinaryNotation;
+use liquesco_serialization::diagnostic::FormatOptions;
+use liquesco_serialization::types::float::Float;
+use liquesco_serialization::value::Value;
+use liquesco_serialization::value::ValueSeq;
+use liquesco_serialization::value::ValueVariant;
+use liquesco_serialization::vec_writer::VecWriter;
+use std::borrow::Cow;
+
+fn serialize(value: &Value) -> Vec<u8> {
+    let mut writer = VecWriter::default();
+    Value::serialize(&mut writer, value).unwrap();
+    writer.into_vec()
+}
+
+fn assert_round_trip(value: Value<'static>, options: &FormatOptions) {
+    let data = serialize(&value);
+    let text = format_with(&data, options).unwrap();
+    assert_eq!(data, parse(&text).unwrap(), "Notation: {}", text);
+}
+
+fn sample() -> Value<'static> {
+    Value::Seq(ValueSeq::Owned(vec![
+        "Demo".into(),
+        Value::UInt(42),
+        Value::Option(Some(Value::Float(Float::F32(1.8)).into())),
+        Value::Enum(ValueVariant::new_values(
+            1,
+            ValueSeq::Owned(vec![
+                Value::Seq(ValueSeq::Owned(vec![Value::SInt(1), Value::SInt(-1)])),
+                Value::Binary(Cow::Owned(vec![0xca, 0xfe])),
+            ]),
+        )),
+    ]))
+}
+
+#[test]
+fn example_output() {
+    let text = format(&serialize(&sample())).unwrap();
+    assert_eq!(
+        "[\n  \"Demo\",\n  42,\n  some(1.8_f32),\n  #1(\n    [+1, -1],\n    h'cafe'\n  )\n]",
+        text
+    );
+}
+
+#[test]
+fn round_trip_all_types() {
+    let values = vec![
+        Value::Bool(true),
+        Value::Bool(false),
+        Value::UInt(0),
+        Value::UInt(u128::MAX),
+        Value::SInt(0),
+        Value::SInt(i128::MIN),
+        Value::SInt(i128::MAX),
+        Value::Float(Float::F32(-0.0)),
+        Value::Float(Float::F32(f32::INFINITY)),
+        Value::Float(Float::F64(f64::NEG_INFINITY)),
+        Value::Float(Float::F64(f64::NAN)),
+        Value::Float(Float::F64(1e300)),
+        Value::Float(Float::F32(f32::MIN_POSITIVE)),
+        // NaN with payload
+        Value::Float(Float::F32(f32::from_bits(0x7fc0_0001))),
+        Value::Float(Float::F64(f64::from_bits(0xfff8_0000_0000_0abc))),
+        "".into(),
+        "quote \" backslash \\ tab \t nul \0 del \u{7f} unicode äöü 😀".into(),
+        Value::Binary(Cow::Owned(vec![])),
+        Value::Binary(Cow::Owned((0..=255).collect())),
+        Value::Option(None),
+        Value::Seq(ValueSeq::Empty),
+        Value::Enum(ValueVariant::new_no_value(0)),
+        Value::Enum(ValueVariant::new_no_value(70_000)),
+        sample(),
+    ];
+    let options = [
+        FormatOptions::default(),
+        FormatOptions {
+            indent: 0,
+            max_inline_depth: 0,
+            binary: BinaryNotation::Base64,
+            ..FormatOptions::default()
+        },
+        FormatOptions {
+            indent: 4,
+            max_inline_depth: 10,
+            binary: BinaryNotation::Base64,
+            ..FormatOptions::default()
+        },
+    ];
+    for options in &options {
+        for value in &values {
+            assert_round_trip(value.clone(), options);
+        }
+    }
+}
+
+#[test]
+fn base64() {
+    let options = FormatOptions {
+        binary: BinaryNotation::Base64,
+        ..FormatOptions::default()
+    };
+    let binary = |data: &[u8]| format_value(&Value::Binary(Cow::Borrowed(data)), &options).unwrap();
+    assert_eq!("b64''", binary(b""));
+    assert_eq!("b64'Zg=='", binary(b"f"));
+    assert_eq!("b64'Zm8='", binary(b"fo"));
+    assert_eq!("b64'Zm9v'", binary(b"foo"));
+    assert_eq!("b64'Zm9vYmFy'", binary(b"foobar"));
+
+    assert!(parse("b64'Zg='").is_err());
+    assert!(parse("b64'Z=g='").is_err());
+}
+
+#[test]
+fn nan_payload_uses_raw_bits() {
+    let value = Value::Float(Float::F32(f32::from_bits(0x7fc0_0001)));
+    assert_eq!(
+        "f32(0x7fc00001)",
+        format_value(&value, &FormatOptions::default()).unwrap()
+    );
+    let value = Value::Float(Float::F32(f32::NAN));
+    assert_eq!(
+        "nan_f32",
+        format_value(&value, &FormatOptions::default()).unwrap()
+    );
+}
+
+#[test]
+fn parse_relaxed_syntax() {
+    let text = "// a comment\n[ 1 , +2,-3 , // another comment\n #2( \"a\" ,), ]";
+    let expected = Value::Seq(ValueSeq::Owned(vec![
+        Value::UInt(1),
+        Value::SInt(2),
+        Value::SInt(-3),
+        Value::Enum(ValueVariant::new(2, "a".into())),
+    ]));
+    assert_eq!(expected, parse_value(text).unwrap());
+}
+
+#[test]
+fn non_canonical_data_is_rejected() {
+    // 5 encoded using 8 bytes
+    let data = [6 * 13 + 4, 5, 0, 0, 0, 0, 0, 0, 0];
+    let err = format(&data).unwrap_err();
+    assert!(
+        err.msg().contains("not canonically encoded"),
+        "{}",
+        err.msg()
+    );
+
+    assert_eq!("5", format(&serialize(&Value::UInt(5))).unwrap());
+}
+
+#[test]
+fn max_depth() {
+    let nested = |depth: usize| {
+        let mut value = Value::UInt(1);
+        for _ in 0..depth {
+            value = Value::Option(Some(value.into()));
+        }
+        value
+    };
+    let options = FormatOptions {
+        max_depth: 3,
+        ..FormatOptions::default()
+    };
+    assert_eq!(
+        "some(some(some(1)))",
+        format_value(&nested(3), &options).unwrap()
+    );
+    assert!(format_value(&nested(4), &options).is_err());
+    assert!(format_with(&serialize(&nested(4)), &options).is_err());
+
+    assert!(parse_with("[#1(some(1))]", 3).is_ok());
+    assert!(parse_with("[#1(some([1]))]", 3).is_err());
+    // fails instead of overflowing the stack
+    assert!(parse(&"[".repeat(100_000)).is_err());
+    assert!(parse(&format!("{}{}", "[".repeat(100), "]".repeat(100))).is_ok());
+}
+
+#[test]
+fn format_rejects_trailing_data() {
+    assert!(format(&[0, 0]).is_err());
+}
+
+#[test]
+fn parse_errors_have_position() {
+    let err = |text: &str| format!("{:?}", parse(text).unwrap_err());
+    assert!(err("[1,\n  2,\n  x]").contains("line 3, column 3"));
+    assert!(err("[1 2]").contains("line 1, column 4"));
+    assert!(err("\"abc").contains("line 1, column 5"));
+    assert!(err("1 2").contains("line 1, column 3"));
+    assert!(err("h'abc'").contains("line 1, column 1"));
+    assert!(err("").contains("line 1, column 1"));
+    // sint needs a sign; uint must not have one
+    assert!(parse("1.5").is_err());
+    assert!(parse("1.5_f16").is_err());
+    assert!(parse("-1").is_ok());
+}
@@ -0,0 +1,124 @@
+use liquesco_serialization::core::DeSerializer;
+use liquesco_serialization::core::LqReader;
+use liquesco_serialization::core::Serializer;
+use liquesco_serialization::core::ToVecLqWriter;
+use liquesco_serialization::io_reader::{IoReader, SliceArena};
+use liquesco_serialization::value::Value;
+use liquesco_serialization::vec_writer::VecWriter;
+use std::io::Read;
+
+/// A `Read` that only returns one byte per call (to make sure buffering works correctly).
+struct OneByteRead<'a>(&'a [u8]);
+
+impl<'a> Read for OneByteRead<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.0.is_empty() || buf.is_empty() {
+            Ok(0)
+        } else {
+            buf[0] = self.0[0];
+            self.0 = &self.0[1..];
+            Ok(1)
+        }
+    }
+}
+
+fn sample_value() -> Value<'static> {
+    let list: Vec<Value<'static>> = vec![
+        "hello".into(),
+        Option::Some::<Value<'static>>("world".into()).into(),
+        8.into(),
+        (-345i32).into(),
+        true.into(),
+        vec![1u8, 2, 3, 4].into(),
+    ];
+    list.into()
+}
+
+fn serialize(value: &Value) -> Vec<u8> {
+    let mut writer = VecWriter::default();
+    Value::serialize(&mut writer, value).unwrap();
+    writer.into_vec()
+}
+
+#[test]
+fn read_value() {
+    let value = sample_value();
+    let data = serialize(&value);
+    let arena = SliceArena::new();
+    let mut reader = IoReader::new(OneByteRead(&data), &arena);
+    let de_serialized = Value::de_serialize(&mut reader).unwrap();
+    assert_eq!(value, de_serialized);
+    reader.finish().unwrap();
+}
+
+#[test]
+fn additional_data() {
+    let mut data = serialize(&sample_value());
+    data.push(0);
+    let arena = SliceArena::new();
+    let mut reader = IoReader::new(data.as_slice(), &arena);
+    Value::de_serialize(&mut reader).unwrap();
+    assert!(reader.finish().is_err());
+}
+
+#[test]
+fn clone_rewinds() {
+    let value = sample_value();
+    let data = serialize(&value);
+    let arena = SliceArena::new();
+    let mut reader = IoReader::new(OneByteRead(&data), &arena).with_max_buffer_size(64);
+    let mut checkpoint = reader.clone();
+    reader.skip().unwrap();
+    assert_eq!(data.len() as u64, reader.offset());
+    assert_eq!(0, checkpoint.offset());
+    let de_serialized = Value::de_serialize(&mut checkpoint).unwrap();
+    assert_eq!(value, de_serialized);
+}
+
+#[test]
+fn window_is_bounded() {
+    let value: Value = vec![7u8; 1000].into();
+    let data = serialize(&value);
+    let arena = SliceArena::new();
+
+    // without a checkpoint it's possible to skip data larger than the buffer
+    let mut reader = IoReader::new(data.as_slice(), &arena).with_max_buffer_size(16);
+    reader.skip().unwrap();
+    reader.finish().unwrap();
+
+    // ... but not when there's a checkpoint
+    let mut reader = IoReader::new(data.as_slice(), &arena).with_max_buffer_size(16);
+    let _checkpoint = reader.clone();
+    assert!(reader.skip().is_err());
+}
+
+#[test]
+fn dropped_clone_releases_data() {
+    let value: Value = vec![7u8; 1000].into();
+    let data = serialize(&value);
+    let arena = SliceArena::new();
+    let mut reader = IoReader::new(data.as_slice(), &arena).with_max_buffer_size(16);
+    {
+        let checkpoint = reader.clone();
+        assert_eq!(0, checkpoint.offset());
+    }
+    reader.skip().unwrap();
+    reader.finish().unwrap();
+}
+
+#[test]
+fn read_partial_and_to_end() {
+    let data = serialize(&sample_value());
+    let arena = SliceArena::new();
+    let mut reader = IoReader::new(OneByteRead(&data), &arena);
+    let mut buf = vec![0u8; data.len() + 10];
+    // returns what's available; does not fail on a short read
+    let len = reader.read(&mut buf).unwrap();
+    assert!(len > 0 && len <= data.len());
+    assert_eq!(&data[..len], &buf[..len]);
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(&data[len..], rest.as_slice());
+    assert_eq!(0, reader.read(&mut buf).unwrap());
+}
@@ -0,0 +1,121 @@
+use liquesco_serialization::core::Serializer;
+use liquesco_serialization::core::ToVecLqWriter;
+use liquesco_serialization::len_writer::LenWriter;
+use liquesco_serialization::serde::serialize_to_vec;
+use liquesco_serialization::serde::serialized_len;
+use liquesco_serialization::types::float::Float;
+use liquesco_serialization::value::Value;
+use liquesco_serialization::value::ValueSeq;
+use liquesco_serialization::value::ValueVariant;
+use liquesco_serialization::vec_writer::VecWriter;
+use std::collections::BTreeMap;
+
+fn assert_len(value: Value) {
+    let mut writer = VecWriter::default();
+    Value::serialize(&mut writer, &value).unwrap();
+    let data = writer.into_vec();
+    assert_eq!(
+        data.len() as u64,
+        value.serialized_len().unwrap(),
+        "{:?}",
+        value
+    );
+}
+
+#[test]
+fn value_len() {
+    assert_len(Value::Bool(true));
+    assert_len(Value::Option(None));
+    assert_len(Option::Some::<Value<'static>>(5u32.into()).into());
+    for int in &[0u128, 1, 255, 256, 65_536, u128::from(u64::MAX) + 1] {
+        assert_len(Value::UInt(*int));
+        assert_len(Value::SInt(-(*int as i128)));
+    }
+    assert_len(Value::Float(Float::F32(1.5)));
+    assert_len(Value::Float(Float::F64(1.5)));
+    // lengths around the varint boundaries (240; 2288)
+    for len in &[0usize, 1, 239, 240, 241, 2287, 2288, 70_000] {
+        assert_len("x".repeat(*len).into());
+        assert_len(Value::Seq(ValueSeq::Owned(vec![Value::Bool(false); *len])));
+    }
+    assert_len(Value::Enum(ValueVariant::new_no_value(0)));
+    assert_len(Value::Enum(ValueVariant::new(1000, Value::Bool(true))));
+    assert_len(Value::Enum(ValueVariant::new_values(
+        2,
+        ValueSeq::Owned(vec![Value::UInt(1), "text".into()]),
+    )));
+}
+
+#[test]
+fn serde_len() {
+    let mut map = BTreeMap::new();
+    for index in 0..300u32 {
+        map.insert(index, format!("value {}", index));
+    }
+    assert_eq!(
+        serialize_to_vec(&map).unwrap().len() as u64,
+        serialized_len(&map).unwrap()
+    );
+    // unknown length
+    let items = (0..1000u64).map(|index| index * index);
+    assert_eq!(
+        serialize_to_vec(CollectSeq(items.clone())).unwrap().len() as u64,
+        serialized_len(CollectSeq(items)).unwrap()
+    );
+    // unknown length (nested; maps)
+    let nested = CollectSeqs((0..300u64).map(|len| CollectSeq(0..len)));
+    assert_eq!(
+        serialize_to_vec(&nested).unwrap().len() as u64,
+        serialized_len(&nested).unwrap()
+    );
+    let entries = CollectMap((0..500u64).map(|index| (index, index * 1000)));
+    assert_eq!(
+        serialize_to_vec(&entries).unwrap().len() as u64,
+        serialized_len(&entries).unwrap()
+    );
+}
+
+#[test]
+fn counts_without_writing() {
+    let mut writer = LenWriter::default();
+    assert!(writer.is_empty());
+    Value::serialize(&mut writer, &vec![7u8; 1000].into()).unwrap();
+    assert_eq!(1003, writer.len());
+}
+
+/// Serializes an iterator with unknown length.
+struct CollectSeq<I>(I);
+
+impl<I> serde::Serialize for CollectSeq<I>
+where
+    I: Iterator<Item = u64> + Clone,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.clone().filter(|_| true))
+    }
+}
+
+/// Serializes an iterator of sequences with unknown length.
+struct CollectSeqs<I>(I);
+
+impl<I, J> serde::Serialize for CollectSeqs<I>
+where
+    I: Iterator<Item = CollectSeq<J>> + Clone,
+    J: Iterator<Item = u64> + Clone,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.clone().filter(|_| true))
+    }
+}
+
+/// Serializes a map with unknown length.
+struct CollectMap<I>(I);
+
+impl<I> serde::Serialize for CollectMap<I>
+where
+    I: Iterator<Item = (u64, u64)> + Clone,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.clone().filter(|_| true))
+    }
+}
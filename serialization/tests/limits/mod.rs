@@ -0,0 +1,181 @@
+use liquesco_common::error::LqError;
+use liquesco_serialization::core::DeSerializer;
+use liquesco_serialization::core::LqReader;
+use liquesco_serialization::limits::limits_error_category;
+use liquesco_serialization::limits::LimitedReader;
+use liquesco_serialization::limits::ReadLimits;
+use liquesco_serialization::serde::de_serialize_from_slice_with_limits;
+use liquesco_serialization::serde::serialize_to_vec;
+use liquesco_serialization::slice_reader::SliceReader;
+use liquesco_serialization::value::Value;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// `depth` nested sequences (each with one element); the innermost element is `false`.
+fn nested_sequences(depth: usize) -> Vec<u8> {
+    let mut data = vec![3 * 13 + 7; depth];
+    data.push(0);
+    data
+}
+
+fn value_with_limits(data: &[u8], limits: ReadLimits) -> Result<Value<'_>, LqError> {
+    let mut reader = LimitedReader::new(SliceReader::from(data), limits);
+    Value::de_serialize(&mut reader)
+}
+
+fn assert_limit_error<T: std::fmt::Debug>(result: Result<T, LqError>) {
+    let err = result.unwrap_err();
+    assert_eq!(&limits_error_category(), err.category(), "{:?}", err);
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Nested(Vec<Nested>);
+
+#[test]
+fn max_depth() {
+    let limits = ReadLimits {
+        max_depth: 100,
+        ..ReadLimits::default()
+    };
+
+    let data = nested_sequences(100);
+    value_with_limits(&data, limits).unwrap();
+    let mut reader = LimitedReader::new(SliceReader::from(&data), limits);
+    reader.skip().unwrap();
+
+    let data = nested_sequences(10_000);
+    assert_limit_error(value_with_limits(&data, limits));
+    // skipping is iterative: no depth limit required
+    let mut reader = LimitedReader::new(SliceReader::from(&data), limits);
+    reader.skip().unwrap();
+}
+
+#[test]
+fn max_depth_serde() {
+    let limits = ReadLimits {
+        max_depth: 10,
+        ..ReadLimits::default()
+    };
+    let mut nested = Nested(vec![]);
+    for _ in 0..9 {
+        nested = Nested(vec![nested]);
+    }
+    let data = serialize_to_vec(&nested).unwrap();
+    assert_eq!(
+        nested,
+        de_serialize_from_slice_with_limits::<Nested>(&data, limits).unwrap()
+    );
+
+    let nested = Nested(vec![nested]);
+    let data = serialize_to_vec(&nested).unwrap();
+    assert_limit_error(de_serialize_from_slice_with_limits::<Nested>(&data, limits));
+}
+
+#[test]
+fn max_items() {
+    // a sequence declaring u32::MAX elements (ContainerVarIntEmpty)
+    let data = [3 * 13 + 10, 0xFA, 0xFF, 0xFF, 0xFF, 0xFF, 0];
+    assert_limit_error(value_with_limits(&data, ReadLimits::default()));
+    assert_limit_error(de_serialize_from_slice_with_limits::<Vec<bool>>(
+        &data,
+        ReadLimits::default(),
+    ));
+    let mut reader = LimitedReader::new(SliceReader::from(&data[..]), ReadLimits::default());
+    assert_limit_error(reader.skip());
+
+    // no limits: fails too (end of data) but does not allocate the declared length
+    let mut reader = SliceReader::from(&data[..]);
+    assert!(Value::de_serialize(&mut reader).is_err());
+}
+
+#[test]
+fn max_allocation() {
+    let limits = ReadLimits {
+        max_allocation: 1000,
+        ..ReadLimits::default()
+    };
+    let data = serialize_to_vec(vec![1u8; 5]).unwrap();
+    value_with_limits(&data, limits).unwrap();
+    let data = serialize_to_vec(vec![1u8; 500]).unwrap();
+    assert_limit_error(value_with_limits(&data, limits));
+
+    let data = serialize_to_vec("a".repeat(999)).unwrap();
+    de_serialize_from_slice_with_limits::<String>(&data, limits).unwrap();
+    let data = serialize_to_vec("a".repeat(1001)).unwrap();
+    assert_limit_error(de_serialize_from_slice_with_limits::<String>(&data, limits));
+}
+
+#[test]
+fn max_allocation_serde_collections() {
+    let limits = ReadLimits {
+        max_allocation: 1000,
+        ..ReadLimits::default()
+    };
+    // 1 byte per element in the data but 8 bytes per element in memory
+    let data = serialize_to_vec(vec![1u64; 100]).unwrap();
+    de_serialize_from_slice_with_limits::<Vec<u64>>(&data, limits).unwrap();
+    let data = serialize_to_vec(vec![1u64; 200]).unwrap();
+    assert_limit_error(de_serialize_from_slice_with_limits::<Vec<u64>>(
+        &data, limits,
+    ));
+
+    let map: BTreeMap<u64, u64> = (0..100).map(|key| (key, key)).collect();
+    let data = serialize_to_vec(&map).unwrap();
+    assert_limit_error(de_serialize_from_slice_with_limits::<BTreeMap<u64, u64>>(
+        &data, limits,
+    ));
+}
+
+#[test]
+fn max_binary_len() {
+    let limits = ReadLimits {
+        max_binary_len: 10,
+        ..ReadLimits::default()
+    };
+    let data = serialize_to_vec("0123456789").unwrap();
+    value_with_limits(&data, limits).unwrap();
+    let data = serialize_to_vec("0123456789a").unwrap();
+    assert_limit_error(value_with_limits(&data, limits));
+    assert_limit_error(de_serialize_from_slice_with_limits::<&str>(&data, limits));
+}
+
+#[derive(Serialize)]
+struct WithText<'a> {
+    number: u8,
+    text: &'a str,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct WithoutText {
+    number: u8,
+}
+
+#[test]
+fn max_binary_len_skipped() {
+    let limits = ReadLimits {
+        max_binary_len: 10,
+        ..ReadLimits::default()
+    };
+    let data = serialize_to_vec("0123456789a").unwrap();
+    let mut reader = LimitedReader::new(SliceReader::from(&data), limits);
+    assert_limit_error(reader.skip());
+
+    // the text is an unknown (trailing) field: it's skipped
+    let data = serialize_to_vec(WithText {
+        number: 1,
+        text: "0123456789",
+    })
+    .unwrap();
+    assert_eq!(
+        WithoutText { number: 1 },
+        de_serialize_from_slice_with_limits::<WithoutText>(&data, limits).unwrap()
+    );
+    let data = serialize_to_vec(WithText {
+        number: 1,
+        text: "0123456789a",
+    })
+    .unwrap();
+    assert_limit_error(de_serialize_from_slice_with_limits::<WithoutText>(
+        &data, limits,
+    ));
+}
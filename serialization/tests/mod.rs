@@ -1,2 +1,10 @@
+pub mod canonical;
+pub mod diagnostic;
+pub mod io_reader;
+pub mod len_writer;
+pub mod limits;
 pub mod serde;
+pub mod skip;
+pub mod slice_writer;
 pub mod value;
+pub mod varint;
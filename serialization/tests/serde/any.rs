@@ -0,0 +1,132 @@
+use crate::serde::assert_serde;
+use liquesco_serialization::serde::{de_serialize_from_slice, serialize_to_vec};
+use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Circle {
+    radius: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum Shape {
+    Circle(Circle),
+    Pair(u32, bool),
+    Named(String),
+}
+
+#[test]
+fn untagged_enum() {
+    assert_serde(Shape::Circle(Circle { radius: 5 }));
+    assert_serde(Shape::Pair(5, true));
+    assert_serde(Shape::Named("triangle".to_string()));
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum MaybeNumber {
+    Number(Option<u32>),
+    Text(String),
+}
+
+#[test]
+fn untagged_options() {
+    assert_serde(MaybeNumber::Number(Some(3)));
+    assert_serde(MaybeNumber::Number(None));
+    assert_serde(MaybeNumber::Text("three".to_string()));
+}
+
+/// Something like `serde_json::Value`: Can hold every value.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+enum Dynamic {
+    Unit(()),
+    Bool(bool),
+    UInt(u64),
+    SInt(i64),
+    Float(f64),
+    Text(String),
+    Seq(Vec<Dynamic>),
+    Bytes(ByteBuf),
+    Enum(BTreeMap<u32, Dynamic>),
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Variants {
+    Unit,
+    NewType(u8),
+    Tuple(u8, String),
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Record {
+    flag: bool,
+    unsigned: u8,
+    signed: i32,
+    float: f32,
+    text: String,
+    bytes: ByteBuf,
+    list: Vec<u32>,
+    variants: Vec<Variants>,
+    none: Option<u16>,
+}
+
+fn enum_value(ordinal: u32, value: Dynamic) -> Dynamic {
+    let mut map = BTreeMap::new();
+    map.insert(ordinal, value);
+    Dynamic::Enum(map)
+}
+
+#[test]
+fn dynamic_value() {
+    let record = Record {
+        flag: true,
+        unsigned: 5,
+        signed: -7,
+        float: 1.5,
+        text: "text".to_string(),
+        bytes: ByteBuf::from(vec![0xff, 0]),
+        list: vec![1, 2],
+        variants: vec![
+            Variants::Unit,
+            Variants::NewType(3),
+            Variants::Tuple(4, "four".to_string()),
+        ],
+        none: None,
+    };
+    let data = serialize_to_vec(&record).unwrap();
+    let dynamic: Dynamic = de_serialize_from_slice(&data).unwrap();
+
+    let expected = Dynamic::Seq(vec![
+        Dynamic::Bool(true),
+        Dynamic::UInt(5),
+        Dynamic::SInt(-7),
+        Dynamic::Float(1.5),
+        Dynamic::Text("text".to_string()),
+        Dynamic::Bytes(ByteBuf::from(vec![0xff, 0])),
+        Dynamic::Seq(vec![Dynamic::UInt(1), Dynamic::UInt(2)]),
+        Dynamic::Seq(vec![
+            enum_value(0, Dynamic::Unit(())),
+            enum_value(1, Dynamic::UInt(3)),
+            enum_value(
+                2,
+                Dynamic::Seq(vec![Dynamic::UInt(4), Dynamic::Text("four".to_string())]),
+            ),
+        ]),
+        Dynamic::Unit(()),
+    ]);
+    assert_eq!(expected, dynamic);
+}
+
+#[test]
+fn large_integers() {
+    let data = serialize_to_vec((u128::MAX, i128::MIN)).unwrap();
+    let value: (u128, i128) = de_serialize_from_slice(&data).unwrap();
+    assert_eq!((u128::MAX, i128::MIN), value);
+
+    // do not fit into 64 bit
+    let data = serialize_to_vec(u128::MAX).unwrap();
+    assert!(de_serialize_from_slice::<Dynamic>(&data).is_err());
+}
@@ -0,0 +1,114 @@
+use liquesco_serialization::canonical::compare;
+use liquesco_serialization::serde::{
+    de_serialize_from_slice, serialize_set, serialize_to_vec, serialize_to_vec_canonical,
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+#[test]
+fn hash_map_sorted_by_key() {
+    let mut hash_map = HashMap::new();
+    let mut btree_map = BTreeMap::new();
+    for key in -50..50i32 {
+        hash_map.insert(key, format!("value {}", key));
+        btree_map.insert(key, format!("value {}", key));
+    }
+    // integers are sorted by value (not by their encoded bytes)
+    assert_eq!(
+        serialize_to_vec(&btree_map).unwrap(),
+        serialize_to_vec_canonical(&hash_map).unwrap()
+    );
+}
+
+#[test]
+fn nested_maps() {
+    let mut hash_map: HashMap<String, HashMap<u64, bool>> = HashMap::new();
+    let mut btree_map: BTreeMap<String, BTreeMap<u64, bool>> = BTreeMap::new();
+    for outer in &["z", "a", "hello", "", "b"] {
+        let mut inner_hash = HashMap::new();
+        let mut inner_btree = BTreeMap::new();
+        for key in &[300u64, 2, 70_000, 0, 5_000_000_000] {
+            inner_hash.insert(*key, key % 2 == 0);
+            inner_btree.insert(*key, key % 2 == 0);
+        }
+        hash_map.insert(outer.to_string(), inner_hash);
+        btree_map.insert(outer.to_string(), inner_btree);
+    }
+    let data = serialize_to_vec_canonical(&hash_map).unwrap();
+    assert_eq!(serialize_to_vec(&btree_map).unwrap(), data);
+    let de_serialized: HashMap<String, HashMap<u64, bool>> =
+        de_serialize_from_slice(&data).unwrap();
+    assert_eq!(hash_map, de_serialized);
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Tags {
+    #[serde(serialize_with = "serialize_set")]
+    tags: HashSet<String>,
+    not_a_set: Vec<u32>,
+}
+
+#[derive(Serialize)]
+struct SortedTags {
+    tags: BTreeSet<String>,
+    not_a_set: Vec<u32>,
+}
+
+#[test]
+fn sets() {
+    let tags = Tags {
+        tags: ["b", "c", "a", "aa"]
+            .iter()
+            .map(|tag| tag.to_string())
+            .collect(),
+        not_a_set: vec![3, 1, 2],
+    };
+    let sorted_tags = SortedTags {
+        tags: ["b", "c", "a", "aa"]
+            .iter()
+            .map(|tag| tag.to_string())
+            .collect(),
+        not_a_set: vec![3, 1, 2],
+    };
+    let data = serialize_to_vec_canonical(&tags).unwrap();
+    assert_eq!(serialize_to_vec(&sorted_tags).unwrap(), data);
+    assert_eq!(tags, de_serialize_from_slice::<Tags>(&data).unwrap());
+
+    // the non-canonical serializer ignores the set marker
+    let data = serialize_to_vec(&tags).unwrap();
+    assert_eq!(tags, de_serialize_from_slice::<Tags>(&data).unwrap());
+}
+
+#[derive(Serialize)]
+struct WithDuplicates(#[serde(serialize_with = "serialize_set")] Vec<u32>);
+
+#[test]
+fn duplicates_are_rejected() {
+    assert!(serialize_to_vec_canonical(WithDuplicates(vec![1, 2, 1])).is_err());
+    assert_eq!(
+        serialize_to_vec(vec![1u32, 2, 3]).unwrap(),
+        serialize_to_vec_canonical(WithDuplicates(vec![3, 2, 1])).unwrap()
+    );
+}
+
+fn cmp<S: Serialize>(value1: S, value2: S) -> Ordering {
+    compare(
+        &serialize_to_vec(value1).unwrap(),
+        &serialize_to_vec(value2).unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn compare_values() {
+    assert_eq!(Ordering::Less, cmp(255u32, 256u32));
+    assert_eq!(Ordering::Less, cmp(-300i32, 2i32));
+    assert_eq!(Ordering::Equal, cmp("abc", "abc"));
+    assert_eq!(Ordering::Greater, cmp("b", "abc"));
+    assert_eq!(Ordering::Less, cmp(None, Some(0u8)));
+    assert_eq!(Ordering::Less, cmp(vec![1u8, 2], vec![1, 2, 0]));
+    assert_eq!(Ordering::Greater, cmp(vec![1u8, 3], vec![1, 2, 0]));
+    assert_eq!(Ordering::Less, cmp(-0.5f64, 0.25f64));
+    assert_eq!(Ordering::Less, cmp(Ok::<u8, u8>(9), Err(0)));
+}
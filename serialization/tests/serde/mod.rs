@@ -1,6 +1,8 @@
-use liquesco_serialization::serde::{de_serialize_from_slice, serialize_to_vec};
+use liquesco_serialization::serde::{de_serialize_from_slice, serialize_to_vec, serialized_len};
 use std::fmt::Debug;
 
+pub mod any;
+pub mod canonical;
 pub mod enum_extensible;
 pub mod map;
 pub mod seq_extensible;
@@ -10,6 +12,7 @@ pub mod simple_scalars;
 pub mod simple_sequences;
 pub mod struct_defaults;
 pub mod struct_demo;
+pub mod unknown_length;
 pub mod uuid;
 
 pub fn assert_serde<S>(item: S)
@@ -17,6 +20,10 @@ where
     S: serde::Serialize + serde::de::DeserializeOwned + PartialEq + Debug + 'static,
 {
     let serialized_data = serialize_to_vec(&item).expect("Unable to serialize value");
+    assert_eq!(
+        serialized_data.len() as u64,
+        serialized_len(&item).expect("Unable to compute the serialized length")
+    );
 
     // now de-serialize the data
     let value = de_serialize_from_slice::<S>(&serialized_data).expect("Unable to de-serialize");
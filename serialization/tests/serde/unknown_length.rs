@@ -0,0 +1,100 @@
+use crate::serde::assert_serde;
+use liquesco_serialization::serde::serialize_to_vec;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// Serializes only the even numbers; the iterator does not know its length.
+struct EvenNumbers(Vec<u32>);
+
+impl Serialize for EvenNumbers {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().filter(|number| *number % 2 == 0))
+    }
+}
+
+/// Serializes only the entries with even keys; the iterator does not know its length.
+struct EvenEntries(BTreeMap<u32, String>);
+
+impl Serialize for EvenEntries {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().filter(|(key, _)| *key % 2 == 0))
+    }
+}
+
+#[test]
+fn seq_with_unknown_length() {
+    let numbers: Vec<u32> = (0..1000).collect();
+    let even_numbers: Vec<u32> = (0..1000).filter(|number| number % 2 == 0).collect();
+    assert_eq!(
+        serialize_to_vec(&even_numbers).unwrap(),
+        serialize_to_vec(EvenNumbers(numbers)).unwrap()
+    );
+
+    assert_eq!(
+        serialize_to_vec(Vec::<u32>::new()).unwrap(),
+        serialize_to_vec(EvenNumbers(vec![1, 3])).unwrap()
+    );
+}
+
+#[test]
+fn map_with_unknown_length() {
+    let mut map = BTreeMap::new();
+    let mut even_map = BTreeMap::new();
+    for key in 0..20u32 {
+        map.insert(key, format!("value {}", key));
+        if key % 2 == 0 {
+            even_map.insert(key, format!("value {}", key));
+        }
+    }
+    assert_eq!(
+        serialize_to_vec(&even_map).unwrap(),
+        serialize_to_vec(EvenEntries(map)).unwrap()
+    );
+}
+
+#[test]
+fn nested_unknown_length() {
+    let nested = vec![
+        EvenNumbers(vec![1, 2, 3, 4]),
+        EvenNumbers(vec![]),
+        EvenNumbers(vec![6]),
+    ];
+    let expected: Vec<Vec<u32>> = vec![vec![2, 4], vec![], vec![6]];
+    assert_eq!(
+        serialize_to_vec(&expected).unwrap(),
+        serialize_to_vec(NestedEvenNumbers(nested)).unwrap()
+    );
+}
+
+/// A sequence (with unknown length) of sequences with unknown length.
+struct NestedEvenNumbers(Vec<EvenNumbers>);
+
+impl Serialize for NestedEvenNumbers {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().filter(|_| true))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Metadata {
+    created_by: String,
+    version: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Document {
+    title: String,
+    #[serde(flatten)]
+    metadata: Metadata,
+}
+
+#[test]
+fn flatten() {
+    assert_serde(Document {
+        title: "Title".to_string(),
+        metadata: Metadata {
+            created_by: "Me".to_string(),
+            version: 3,
+        },
+    });
+}
@@ -0,0 +1,74 @@
+use liquesco_serialization::core::DeSerializer;
+use liquesco_serialization::core::LqReader;
+use liquesco_serialization::core::Serializer;
+use liquesco_serialization::core::ToVecLqWriter;
+use liquesco_serialization::io_reader::IoReader;
+use liquesco_serialization::io_reader::SliceArena;
+use liquesco_serialization::slice_reader::SliceReader;
+use liquesco_serialization::types::binary::Binary;
+use liquesco_serialization::types::boolean::Bool;
+use liquesco_serialization::types::seq::SeqHeader;
+use liquesco_serialization::vec_writer::VecWriter;
+
+fn binary(len: usize) -> Vec<u8> {
+    let mut writer = VecWriter::default();
+    Binary::serialize(&mut writer, &vec![7u8; len]).unwrap();
+    writer.into_vec()
+}
+
+#[test]
+fn skip_deeply_nested() {
+    // one million nested sequences: would overflow the stack if skip was recursive
+    let mut data = vec![3 * 13 + 7; 1_000_000];
+    data.push(0);
+    data.push(13);
+
+    let mut reader = SliceReader::from(&data);
+    reader.skip().unwrap();
+    assert!(Bool::de_serialize(&mut reader).unwrap());
+    reader.finish().unwrap();
+}
+
+#[test]
+fn skip_large_binary() {
+    let mut data = binary(64 * 1024 * 1024);
+    data.push(13);
+
+    let mut reader = SliceReader::from(&data);
+    reader.skip().unwrap();
+    assert_eq!(data.len() - 1, reader.offset());
+
+    let arena = SliceArena::new();
+    let mut reader = IoReader::new(data.as_slice(), &arena);
+    reader.skip().unwrap();
+    assert!(Bool::de_serialize(&mut reader).unwrap());
+    reader.finish().unwrap();
+    assert_eq!(0, arena.len());
+}
+
+#[test]
+fn skip_truncated() {
+    let data = binary(100);
+    let mut reader = SliceReader::from(&data[..data.len() - 1]);
+    assert!(reader.skip().is_err());
+
+    let arena = SliceArena::new();
+    let mut reader = IoReader::new(&data[..data.len() - 1], &arena);
+    assert!(reader.skip().is_err());
+}
+
+#[test]
+fn skip_n_values() {
+    let mut writer = VecWriter::default();
+    SeqHeader::serialize(&mut writer, &SeqHeader::new(2)).unwrap();
+    Binary::serialize(&mut writer, &[1, 2, 3]).unwrap();
+    Bool::serialize(&mut writer, &false).unwrap();
+    Binary::serialize(&mut writer, &[4]).unwrap();
+    Bool::serialize(&mut writer, &true).unwrap();
+    let data: Vec<u8> = writer.into_vec();
+
+    let mut reader = SliceReader::from(&data);
+    reader.skip_n_values(2).unwrap();
+    assert!(Bool::de_serialize(&mut reader).unwrap());
+    reader.finish().unwrap();
+}
@@ -0,0 +1,83 @@
+use liquesco_serialization::core::Serializer;
+use liquesco_serialization::serde::serialize_to_slice;
+use liquesco_serialization::serde::serialize_to_vec;
+use liquesco_serialization::slice_writer::{
+    buffer_too_small_error_category, BufferTooSmall, SliceWriter,
+};
+use liquesco_serialization::value::Value;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Message {
+    id: u64,
+    name: String,
+    payload: Vec<u8>,
+}
+
+fn message() -> Message {
+    Message {
+        id: 70_000,
+        name: "hello".to_string(),
+        payload: vec![1, 2, 3, 4, 5],
+    }
+}
+
+#[test]
+fn write_into_buffer() {
+    let expected = serialize_to_vec(message()).unwrap();
+
+    let mut buffer = [0u8; 64];
+    let written = serialize_to_slice(&mut buffer, message()).unwrap();
+    assert_eq!(expected.len(), written);
+    assert_eq!(expected.as_slice(), &buffer[..written]);
+
+    // exact size
+    let mut buffer = vec![0u8; expected.len()];
+    assert_eq!(
+        expected.len(),
+        serialize_to_slice(&mut buffer, message()).unwrap()
+    );
+    assert_eq!(expected, buffer);
+}
+
+#[test]
+fn buffer_too_small() {
+    let expected_len = serialize_to_vec(message()).unwrap().len();
+
+    let mut buffer = [0u8; 8];
+    let err = serialize_to_slice(&mut buffer, message()).unwrap_err();
+    assert!(err
+        .msg()
+        .contains(&format!("{} bytes are required", expected_len)));
+    assert_eq!(&buffer_too_small_error_category(), err.category());
+    assert_eq!(
+        Some(&BufferTooSmall {
+            required_len: expected_len,
+            len: 8
+        }),
+        err.data::<BufferTooSmall>()
+    );
+
+    let mut buffer = vec![0u8; expected_len - 1];
+    let mut writer = SliceWriter::from(buffer.as_mut_slice());
+    let value1: Value = "0123456789".into();
+    let value2: Value = vec![0u8; 100].into();
+    Value::serialize(&mut writer, &value1).unwrap();
+    Value::serialize(&mut writer, &value2).unwrap();
+    assert!(writer.is_too_small());
+    assert_eq!(
+        value1.serialized_len().unwrap() + value2.serialized_len().unwrap(),
+        writer.required_len() as u64
+    );
+    assert!(writer.finish().is_err());
+}
+
+#[test]
+fn empty_buffer() {
+    let mut writer = SliceWriter::from(&mut [][..]);
+    assert_eq!(0, writer.finish().unwrap());
+    Value::serialize(&mut writer, &Value::Bool(true)).unwrap();
+    assert_eq!(1, writer.required_len());
+    assert_eq!(0, writer.written());
+    assert!(writer.finish().is_err());
+}
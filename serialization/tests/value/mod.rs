@@ -14,6 +14,7 @@ pub mod signed_int;
 pub mod unsigned_int;
 pub mod utf8;
 pub mod uuid;
+pub mod view;
 use liquesco_serialization::core::ToVecLqWriter;
 
 pub fn check_value(value: &Value) {
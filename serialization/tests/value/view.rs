@@ -0,0 +1,122 @@
+use liquesco_serialization::core::Serializer;
+use liquesco_serialization::core::ToVecLqWriter;
+use liquesco_serialization::major_types::TYPE_SEQ;
+use liquesco_serialization::value::view::ValueView;
+use liquesco_serialization::value::Value;
+use liquesco_serialization::value::ValueVariant;
+use liquesco_serialization::vec_writer::VecWriter;
+
+fn serialize(value: &Value) -> Vec<u8> {
+    let mut writer = VecWriter::default();
+    Value::serialize(&mut writer, value).unwrap();
+    writer.into_vec()
+}
+
+fn sample_record() -> Value<'static> {
+    let large: Vec<Value<'static>> = (0..1000u32).map(|item| item.into()).collect();
+    let address: Vec<Value<'static>> = vec!["Main Street".into(), 42u32.into()];
+    let record: Vec<Value<'static>> = vec![
+        large.into(),
+        "name".into(),
+        Option::Some::<Value<'static>>(address.into()).into(),
+        Value::Enum(ValueVariant::new(7, (-3i32).into())),
+        vec![1u8, 2, 3].into(),
+    ];
+    record.into()
+}
+
+#[test]
+fn navigate_record() {
+    let data = serialize(&sample_record());
+    let view = ValueView::new(&data).unwrap();
+
+    assert_eq!(TYPE_SEQ, view.major_type());
+    assert_eq!(5, view.seq_len().unwrap());
+    assert_eq!(
+        "name",
+        view.seq_element(1).unwrap().unwrap().as_unicode().unwrap()
+    );
+    assert_eq!(
+        999,
+        view.embedded_path(&[0, 999])
+            .unwrap()
+            .unwrap()
+            .as_uint()
+            .unwrap()
+    );
+
+    let address = view
+        .seq_element(2)
+        .unwrap()
+        .unwrap()
+        .option_value()
+        .unwrap();
+    let street = address.unwrap().seq_element(0).unwrap().unwrap();
+    assert_eq!("Main Street", street.as_unicode().unwrap());
+
+    let variant = view.seq_element(3).unwrap().unwrap();
+    assert_eq!(7, variant.enum_header().unwrap().ordinal());
+    assert_eq!(
+        -3,
+        variant
+            .variant_value(0)
+            .unwrap()
+            .unwrap()
+            .as_sint()
+            .unwrap()
+    );
+    assert!(variant.variant_value(1).unwrap().is_none());
+
+    assert_eq!(
+        &[1u8, 2, 3],
+        view.seq_element(4).unwrap().unwrap().as_binary().unwrap()
+    );
+    assert!(view.seq_element(5).unwrap().is_none());
+}
+
+#[test]
+fn iterate_and_decode() {
+    let value = sample_record();
+    let data = serialize(&value);
+    let view = ValueView::new(&data).unwrap();
+    assert_eq!(value, view.to_value().unwrap());
+
+    let elements: Vec<ValueView> = view.iter().unwrap().map(Result::unwrap).collect();
+    assert_eq!(5, elements.len());
+    // elements are contiguous and end with the data (the sequence header is not included)
+    let total_len: usize = elements
+        .iter()
+        .map(|element| element.as_slice().len())
+        .sum();
+    let mut expected_rest = &data[data.len() - total_len..];
+    for element in &elements {
+        let len = element.as_slice().len();
+        assert_eq!(&expected_rest[..len], element.as_slice());
+        expected_rest = &expected_rest[len..];
+    }
+}
+
+#[test]
+fn wrong_types() {
+    let data = serialize(&sample_record());
+    let view = ValueView::new(&data).unwrap();
+    assert!(view.as_unicode().is_err());
+    assert!(view.option_value().is_err());
+    assert!(view.enum_header().is_err());
+    let name = view.seq_element(1).unwrap().unwrap();
+    assert!(name.seq_len().is_err());
+    assert!(name.embedded(0).unwrap().is_none());
+}
+
+#[test]
+fn prefix() {
+    let mut data = serialize(&"first".into());
+    data.extend(serialize(&"second".into()));
+    assert!(ValueView::new(&data).is_err());
+    let (first, rest) = ValueView::new_prefix(&data).unwrap();
+    assert_eq!("first", first.as_unicode().unwrap());
+    assert_eq!(
+        "second",
+        ValueView::new(rest).unwrap().as_unicode().unwrap()
+    );
+}
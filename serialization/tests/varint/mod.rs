@@ -0,0 +1,60 @@
+use liquesco_serialization::core::LqReader;
+use liquesco_serialization::core::LqWriter;
+use liquesco_serialization::core::ToVecLqWriter;
+use liquesco_serialization::slice_reader::SliceReader;
+use liquesco_serialization::vec_writer::VecWriter;
+use std::convert::TryFrom;
+use varuint::WriteVarint;
+
+fn values() -> Vec<u64> {
+    let mut values = vec![0, 1, 239, 240, 241, 2031, 2032, 67567, 67568];
+    for shift in 8..64 {
+        let value = 1u64 << shift;
+        values.extend_from_slice(&[value - 1, value, value + 1]);
+    }
+    values.push(u64::MAX);
+    values
+}
+
+/// The encoding must not change: Compare with the `varuint` crate (that has been used before).
+#[test]
+fn same_encoding_as_varuint() {
+    for value in values() {
+        let mut writer = VecWriter::default();
+        writer.write_varint_u64(value).unwrap();
+        let data = writer.into_vec();
+
+        let mut expected = Vec::new();
+        WriteVarint::<u64>::write_varint(&mut expected, value).unwrap();
+        assert_eq!(expected, data, "value {}", value);
+
+        let mut reader = SliceReader::from(&data);
+        assert_eq!(value, reader.read_varint_u64().unwrap());
+        reader.finish().unwrap();
+
+        if let Ok(value32) = u32::try_from(value) {
+            let mut writer = VecWriter::default();
+            writer.write_varint_u32(value32).unwrap();
+            assert_eq!(expected, writer.into_vec(), "value {}", value);
+            assert_eq!(value32, SliceReader::from(&data).read_varint_u32().unwrap());
+        } else {
+            assert!(SliceReader::from(&data).read_varint_u32().is_err());
+        }
+    }
+}
+
+#[test]
+fn invalid_var_ints() {
+    // 255 is not a valid first byte
+    let mut data = vec![255u8];
+    data.extend_from_slice(&[0u8; 16]);
+    assert!(SliceReader::from(&data).read_varint_u64().is_err());
+
+    // too short
+    assert!(SliceReader::from(&[254u8, 1, 2, 3][..])
+        .read_varint_u64()
+        .is_err());
+    assert!(SliceReader::from(&[248u8, 1][..])
+        .read_varint_u64()
+        .is_err());
+}